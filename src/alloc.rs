@@ -0,0 +1,133 @@
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  cell::Cell,
+  marker::PhantomData,
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+  /// The bytes allocated minus those freed by this thread, which is negative if it frees memory
+  /// allocated by other threads, and the highest that has been since the innermost scope started.
+  static THREAD: Cell<(isize, isize)> = const { Cell::new((0, 0)) };
+}
+
+/// Wraps the system allocator, counting the bytes currently allocated, and the peak per thread.
+pub struct CountingAlloc;
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn grow(bytes: usize) {
+  CURRENT.fetch_add(bytes, Ordering::Relaxed);
+  let _ = THREAD.try_with(|thread| {
+    let (current, peak) = thread.get();
+    let current = current.wrapping_add_unsigned(bytes);
+    thread.set((current, peak.max(current)));
+  });
+}
+
+fn shrink(bytes: usize) {
+  CURRENT.fetch_sub(bytes, Ordering::Relaxed);
+  let _ = THREAD.try_with(|thread| {
+    let (current, peak) = thread.get();
+    thread.set((current.wrapping_sub_unsigned(bytes), peak));
+  });
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let ptr = unsafe { System.alloc(layout) };
+    if !ptr.is_null() {
+      grow(layout.size());
+    }
+    ptr
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    let ptr = unsafe { System.alloc_zeroed(layout) };
+    if !ptr.is_null() {
+      grow(layout.size());
+    }
+    ptr
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { System.dealloc(ptr, layout) };
+    shrink(layout.size());
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+    if !new_ptr.is_null() {
+      if new_size > layout.size() {
+        grow(new_size - layout.size());
+      } else {
+        shrink(layout.size() - new_size);
+      }
+    }
+    new_ptr
+  }
+}
+
+/// The number of bytes currently allocated.
+pub fn current() -> usize {
+  CURRENT.load(Ordering::Relaxed)
+}
+
+/// Measures the peak allocation of a section of code, above what was allocated when it started.
+/// Sections can be nested. Only allocations on the current thread are counted, so that sections
+/// running concurrently on other threads do not affect each other.
+pub struct PeakScope {
+  base: isize,
+  outer_peak: isize,
+  _not_send: PhantomData<*const ()>,
+}
+
+impl PeakScope {
+  pub fn start() -> Self {
+    let (base, outer_peak) = THREAD.with(Cell::get);
+    THREAD.with(|thread| thread.set((base, base)));
+    Self {
+      base,
+      outer_peak,
+      _not_send: PhantomData,
+    }
+  }
+
+  /// The peak number of bytes allocated by this thread since the scope started.
+  pub fn finish(self) -> usize {
+    let (current, peak) = THREAD.with(Cell::get);
+    THREAD.with(|thread| thread.set((current, peak.max(self.outer_peak))));
+    peak.saturating_sub(self.base) as usize
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::hint::black_box;
+
+  use super::*;
+
+  #[test]
+  fn test_peak() {
+    let outer = PeakScope::start();
+    drop(black_box(vec![0u8; 1 << 20]));
+    let inner = PeakScope::start();
+    drop(black_box(vec![0u8; 1 << 10]));
+    let inner = inner.finish();
+    let outer = outer.finish();
+    assert!(inner >= 1 << 10, "{inner}");
+    assert!(outer >= 1 << 20, "{outer}");
+  }
+
+  #[test]
+  fn test_peak_per_thread() {
+    let scope = PeakScope::start();
+    std::thread::spawn(|| drop(black_box(vec![0u8; 1 << 24])))
+      .join()
+      .unwrap();
+    assert!(scope.finish() < 1 << 24);
+  }
+}
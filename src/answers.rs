@@ -0,0 +1,120 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Where the known answers are kept.
+pub const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+/// The known answers of each day and part, read from a file like
+///
+/// ```toml
+/// [day07]
+/// part1 = 1585
+/// part2 = "16716444407407"
+/// ```
+///
+/// Only this subset of TOML is supported, plus comments and blank lines.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u32, u8), String>);
+
+impl Answers {
+  pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)
+      .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", path.display()))?;
+    Self::parse(&text)
+  }
+
+  pub fn parse(text: &str) -> anyhow::Result<Self> {
+    let mut answers = BTreeMap::new();
+    let mut day = None;
+    for (i, line) in text.lines().enumerate() {
+      let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+      if line.is_empty() {
+        continue;
+      }
+      let error = |msg: &str| anyhow::anyhow!("line {}: {msg}: {line}", i + 1);
+      if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        let number = section.trim().strip_prefix("day").unwrap_or(section);
+        day = Some(
+          number
+            .parse::<u32>()
+            .map_err(|_| error("expected [dayNN]"))?,
+        );
+        continue;
+      }
+      let (key, value) = line
+        .split_once('=')
+        .ok_or_else(|| error("expected a key"))?;
+      let part = match key.trim() {
+        "part1" => 1,
+        "part2" => 2,
+        _ => return Err(error("expected part1 or part2")),
+      };
+      let day = day.ok_or_else(|| error("answer outside of a [dayNN] section"))?;
+      let value = value.trim();
+      let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+      if answers.insert((day, part), value.to_string()).is_some() {
+        return Err(error("duplicate answer"));
+      }
+    }
+    Ok(Self(answers))
+  }
+
+  pub fn get(&self, day: u32, part: u8) -> Option<&str> {
+    self.0.get(&(day, part)).map(String::as_str)
+  }
+
+  /// The day, part and answer of every known answer, in order of day and part.
+  pub fn iter(&self) -> impl Iterator<Item = (u32, u8, &str)> {
+    self
+      .0
+      .iter()
+      .map(|(&(day, part), answer)| (day, part, answer.as_str()))
+  }
+
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let answers = Answers::parse(
+      "# checked by aoc verify\n[day07]\npart1 = 1585\npart2 = \"16716444407407\" # big\n\n[12]\npart1 = 495\n",
+    )
+    .unwrap();
+    assert_eq!(answers.len(), 3);
+    assert_eq!(answers.get(7, 1), Some("1585"));
+    assert_eq!(answers.get(7, 2), Some("16716444407407"));
+    assert_eq!(answers.get(12, 1), Some("495"));
+    assert_eq!(answers.get(12, 2), None);
+    assert_eq!(
+      answers.iter().map(|(day, part, _)| (day, part)).collect::<Vec<_>>(),
+      [(7, 1), (7, 2), (12, 1)]
+    );
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    assert!(Answers::parse("part1 = 3").is_err());
+    assert!(Answers::parse("[dayx]").is_err());
+    assert!(Answers::parse("[day1]\npart3 = 3").is_err());
+    assert!(Answers::parse("[day1]\npart1 = 3\npart1 = 4").is_err());
+  }
+
+  #[test]
+  fn test_answers_file() {
+    let answers = Answers::load(ANSWERS_PATH).unwrap();
+    assert_eq!(answers.get(7, 1), Some("1585"));
+  }
+}
@@ -0,0 +1,287 @@
+use std::{
+  ops::RangeInclusive,
+  path::{Path, PathBuf},
+  time::Instant,
+};
+
+use aoc25::{
+  answers::{ANSWERS_PATH, Answers},
+  days,
+  error::AocError,
+  exts::duration::DurationExt,
+  input, scaffold,
+  solution::{BenchTable, Day},
+  time::{self, Report},
+  viz,
+};
+use clap::{Parser, Subcommand};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+// the days are also binaries of their own
+#[allow(dead_code)]
+#[path = "aoc01.rs"]
+mod aoc01;
+#[allow(dead_code)]
+#[path = "aoc02.rs"]
+mod aoc02;
+#[allow(dead_code)]
+#[path = "aoc03.rs"]
+mod aoc03;
+#[allow(dead_code)]
+#[path = "aoc04.rs"]
+mod aoc04;
+#[allow(dead_code)]
+#[path = "aoc05.rs"]
+mod aoc05;
+#[allow(dead_code)]
+#[path = "aoc06.rs"]
+mod aoc06;
+#[allow(dead_code)]
+#[path = "aoc07.rs"]
+mod aoc07;
+#[allow(dead_code)]
+#[path = "aoc08.rs"]
+mod aoc08;
+#[allow(dead_code)]
+#[path = "aoc09.rs"]
+mod aoc09;
+#[allow(dead_code)]
+#[path = "aoc10.rs"]
+mod aoc10;
+#[allow(dead_code)]
+#[path = "aoc11.rs"]
+mod aoc11;
+#[allow(dead_code)]
+#[path = "aoc12.rs"]
+mod aoc12;
+
+/// Where the timings of a run are saved with `run --save-timings`, for `run --compare`.
+const TIMINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target/aoc-timings.csv");
+
+/// Runs the solutions of any day.
+#[derive(Parser)]
+struct Args {
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Solves one day, or all of them.
+  Run {
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u32>,
+
+    #[arg(long)]
+    all: bool,
+
+    /// Only solve this part.
+    #[arg(short, long)]
+    part: Option<u8>,
+
+    /// Solve the days concurrently. The output is still in order of day.
+    #[arg(long, requires = "all")]
+    parallel: bool,
+
+    /// Write the frames that solutions emit, as text, png or gif.
+    #[arg(long, num_args = 0..=1, default_missing_value = "gif", conflicts_with = "parallel")]
+    viz: Option<viz::Format>,
+
+    /// Where frames are written, in a directory per day.
+    #[arg(long, default_value = "viz")]
+    viz_dir: PathBuf,
+
+    /// Print how the timings changed since they were last saved.
+    #[arg(long)]
+    compare: bool,
+
+    /// Save the timings of this run, to compare later runs with.
+    #[arg(long)]
+    save_timings: bool,
+
+    /// Check the answers to the samples instead of solving the input.
+    #[arg(long, conflicts_with_all = ["part", "parallel", "viz", "compare", "save_timings"])]
+    sample: bool,
+  },
+  /// Solves a part and submits the answer.
+  Submit { day: u32, part: u8 },
+  /// Creates a binary for a new day from a template and registers it here.
+  New { day: u32 },
+  /// Solves every day and compares the answers with those in `answers.toml`.
+  Verify,
+  /// Times parsing and both parts of every day, or of a range such as `1..12`.
+  Bench {
+    #[arg(long, value_parser = parse_days)]
+    days: Option<RangeInclusive<u32>>,
+
+    /// How many times each step is run.
+    #[arg(short = 'n', long, default_value_t = 10)]
+    iterations: usize,
+  },
+}
+
+/// Parses a single day, or an inclusive range written `a..b` or `a..=b`.
+fn parse_days(s: &str) -> Result<RangeInclusive<u32>, String> {
+  let parse = |n: &str| n.trim().parse::<u32>().map_err(|e| format!("{n:?}: {e}"));
+  match s.split_once("..") {
+    Some((start, end)) => Ok(parse(start)?..=parse(end.trim_start_matches('='))?),
+    None => parse(s).map(|day| day..=day),
+  }
+}
+
+fn find(days: &[Day], day: u32) -> anyhow::Result<&Day> {
+  days
+    .iter()
+    .find(|d| d.day == day)
+    .ok_or(AocError::NoSolution { day }.into())
+}
+
+fn main() -> anyhow::Result<()> {
+  let days = days![
+    aoc01::Day01,
+    aoc02::Day02,
+    aoc03::Day03,
+    aoc04::Day04,
+    aoc05::Day05,
+    aoc06::Day06,
+    aoc07::Day07,
+    aoc08::Day08,
+    aoc09::Day09,
+    aoc10::Day10,
+    aoc11::Day11,
+    aoc12::Day12,
+  ];
+  time::color_only_in_terminal();
+
+  match Args::parse().command {
+    Command::Run {
+      day,
+      all: _,
+      part,
+      parallel,
+      viz,
+      viz_dir,
+      compare,
+      save_timings,
+      sample,
+    } => {
+      let selected: Vec<&Day> = match day {
+        Some(day) => vec![find(&days, day)?],
+        None => days.iter().collect(),
+      };
+      if sample {
+        let mut failed = 0;
+        for day in selected {
+          let (out, day_failed) = (day.samples)()?;
+          print!("{out}");
+          failed += day_failed;
+        }
+        anyhow::ensure!(failed == 0, "{failed} samples failed");
+        return Ok(());
+      }
+      let start = Instant::now();
+      if parallel {
+        let outputs: Vec<_> = selected.par_iter().map(|day| (day.solve)(part)).collect();
+        for output in outputs {
+          print!("{}", output?);
+        }
+      } else {
+        for day in selected {
+          if let Some(format) = viz {
+            viz::enable(viz_dir.join(format!("day{:02}", day.day)), format);
+          }
+          print!("{}", (day.solve)(part)?);
+          let frames = viz::finish()?;
+          if frames > 0 {
+            println!("Wrote {frames} frames to {}", viz_dir.display());
+          }
+        }
+      }
+      let report = Report::collect();
+      print!("{report}");
+      println!("wall clock  {}", start.elapsed().display_precise(2));
+      if compare {
+        match Report::read_csv(TIMINGS_PATH) {
+          Ok(previous) => {
+            for delta in report.compare(&previous) {
+              println!("{delta}");
+            }
+          }
+          Err(e) => println!("No timings to compare with: {e}"),
+        }
+      }
+      if save_timings {
+        if let Some(dir) = Path::new(TIMINGS_PATH).parent() {
+          std::fs::create_dir_all(dir)?;
+        }
+        report.write_csv(TIMINGS_PATH)?;
+      }
+    }
+    Command::Submit { day, part } => {
+      let answer = (find(&days, day)?.answer)(part)?;
+      println!("Submitting {answer} for day {day} part {part}");
+      let verdict = input::submit(input::YEAR, day, part, &answer)?;
+      println!("{verdict}");
+    }
+    Command::New { day } => {
+      scaffold::create(
+        Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin")),
+        day,
+      )?;
+      println!("Created src/bin/aoc{day:02}.rs");
+    }
+    Command::Verify => {
+      let answers = Answers::load(ANSWERS_PATH)?;
+      let checks: Vec<(u32, u8)> = days
+        .iter()
+        .flat_map(|day| [(day.day, 1), (day.day, 2)])
+        .collect();
+      let results: Vec<_> = checks
+        .par_iter()
+        .map(|&(day, part)| (find(&days, day).unwrap().answer)(part))
+        .collect();
+      let mut failed = 0;
+      for (&(day, part), result) in checks.iter().zip(results) {
+        let (status, ok) = match (answers.get(day, part), result) {
+          (_, Err(e)) => (format!("failed: {e}"), false),
+          (None, Ok(answer)) => (format!("{answer} (no known answer)"), true),
+          (Some(expected), Ok(answer)) if expected == answer => (answer, true),
+          (Some(expected), Ok(answer)) => (format!("{answer}, expected {expected}"), false),
+        };
+        println!("Day {day:02} part {part}: {status}");
+        failed += usize::from(!ok);
+      }
+      let unverified: Vec<_> = answers
+        .iter()
+        .filter(|(day, _, _)| find(&days, *day).is_err())
+        .collect();
+      for (day, part, answer) in &unverified {
+        println!("Day {day:02} part {part}: {answer} (unverified, no registered solution)");
+      }
+      anyhow::ensure!(failed == 0, "{failed} answers are wrong");
+      if unverified.is_empty() {
+        println!("All answers are correct");
+      } else {
+        println!(
+          "All solved answers are correct, {} answers are unverified",
+          unverified.len()
+        );
+      }
+    }
+    Command::Bench {
+      days: range,
+      iterations,
+    } => {
+      anyhow::ensure!(iterations > 0, "iterations cannot be 0");
+      let mut table = BenchTable(vec![]);
+      for day in days
+        .iter()
+        .filter(|d| range.as_ref().is_none_or(|r| r.contains(&d.day)))
+      {
+        table.0.push((day.day, day.title, (day.bench)(iterations)?));
+      }
+      print!("{table}");
+    }
+  }
+  Ok(())
+}
@@ -1,14 +1,20 @@
+use std::{fmt::Display, str::FromStr};
+
 use anyhow::{bail, ensure};
+use aoc25::{
+  parse,
+  solution::{Sample, Solution, run_day},
+};
 
-const INPUT: &str = include_str!("data/01.txt");
+const SAMPLE_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
 
 #[derive(Debug, PartialEq, Eq)]
-struct Turn(i64);
+pub struct Turn(i64);
 
-impl TryFrom<&str> for Turn {
-  type Error = anyhow::Error;
+impl FromStr for Turn {
+  type Err = anyhow::Error;
 
-  fn try_from(value: &str) -> Result<Turn, anyhow::Error> {
+  fn from_str(value: &str) -> Result<Turn, anyhow::Error> {
     ensure!(value.len() >= 2, "input too short");
     Ok(match value.split_at(1) {
       ("R", val) => Turn(val.parse()?),
@@ -25,38 +31,78 @@ fn multiples_of_100_between(a: i64, b: i64) -> u64 {
   (high - low + adj) as u64
 }
 
-fn main() {
-  let turns = INPUT.lines().flat_map(Turn::try_from);
-
-  let mut zeroes: u64 = 0;
-  let mut total: i64 = 50;
-  for Turn(turn) in turns {
-    let prev = total;
-    total += turn;
-    let new_zeroes = multiples_of_100_between(prev, total);
-    eprintln!("Turned {} to {} ({} + {})", turn, total, zeroes, new_zeroes);
-    zeroes += new_zeroes;
+/// The positions of the dial after each turn, starting from 50.
+fn positions(turns: &[Turn]) -> impl Iterator<Item = i64> {
+  turns.iter().scan(50, |total, Turn(turn)| {
+    *total += turn;
+    Some(*total)
+  })
+}
+
+fn part_one(turns: &[Turn]) -> usize {
+  positions(turns).filter(|total| total % 100 == 0).count()
+}
+
+fn part_two(turns: &[Turn]) -> u64 {
+  let mut prev = 50;
+  positions(turns)
+    .map(|total| multiples_of_100_between(std::mem::replace(&mut prev, total), total))
+    .sum()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+  const DAY: u32 = 1;
+  const TITLE: &'static str = "Secret Entrance";
+
+  type Parsed = Vec<Turn>;
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("3"),
+    part2: Some("6"),
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    Ok(parse::lines_of(input)?)
   }
-  println!("{zeroes}");
+
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
+  }
+
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
+  }
+}
+
+fn main() -> anyhow::Result<()> {
+  run_day::<Day01>()
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use aoc25::aoc_test;
   use core::assert_matches;
 
+  use super::*;
+
+  aoc_test!(Day01, part1, SAMPLE_INPUT, 3);
+  aoc_test!(Day01, part2, SAMPLE_INPUT, 6);
+
   #[test]
   fn test_parsing() {
-    let a: Turn = "R56".try_into().unwrap();
+    let a: Turn = "R56".parse().unwrap();
     assert_matches!(a, Turn(56));
 
-    let a: Turn = "L33".try_into().unwrap();
+    let a: Turn = "L33".parse().unwrap();
     assert_matches!(a, Turn(-33));
 
-    let a: Turn = "L133".try_into().unwrap();
+    let a: Turn = "L133".parse().unwrap();
     assert_matches!(a, Turn(-133));
 
-    let a: Result<Turn, _> = "Q3".try_into();
+    let a: Result<Turn, _> = "Q3".parse();
     assert_matches!(a, Err(_));
   }
 
@@ -1,17 +1,23 @@
+use std::{fmt::Display, str::FromStr};
+
 use anyhow::anyhow;
+use aoc25::{
+  parse,
+  solution::{Sample, Solution, run_day},
+};
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("data/02.txt");
+const SAMPLE_INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\n1698522-1698528,446443-446449,38593856-38593862,565653-565659,\n824824821-824824827,2121212118-2121212124";
 
-struct IdRange {
+pub struct IdRange {
   min: u64,
   max: u64,
 }
 
-impl TryFrom<&str> for IdRange {
-  type Error = anyhow::Error;
+impl FromStr for IdRange {
+  type Err = anyhow::Error;
 
-  fn try_from(value: &str) -> Result<Self, Self::Error> {
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
     let (min, max) = value
       .trim()
       .split_once('-')
@@ -23,40 +29,66 @@ impl TryFrom<&str> for IdRange {
   }
 }
 
-#[allow(unused)]
 fn is_invalid_id(id: &u64) -> bool {
   let as_str = format!("{id}");
   let (left, right) = as_str.split_at(as_str.len() / 2);
-  let result = left == right;
-  if result {
-    eprintln!("{id} is invalid");
-  }
-  result
+  left == right
 }
 
-#[allow(unused)]
 fn is_invalid_id2(id: &u64) -> bool {
   let as_str = format!("{id}");
   let bytes = as_str.as_bytes();
 
-  let invalid = (1..=(bytes.len() / 2))
+  (1..=(bytes.len() / 2))
     .filter(|x| bytes.len() % x == 0)
-    .any(|chunk_size| bytes.chunks_exact(chunk_size).all_equal());
-  if invalid {
-    eprintln!("{id} is invalid");
+    .any(|chunk_size| bytes.chunks_exact(chunk_size).all_equal())
+}
+
+/// The sum of the IDs in all ranges that `is_invalid` rejects.
+fn sum_invalid(ranges: &[IdRange], is_invalid: fn(&u64) -> bool) -> u64 {
+  ranges
+    .iter()
+    .map(|IdRange { min, max }| (*min..=*max).filter(is_invalid).sum::<u64>())
+    .sum()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+  const DAY: u32 = 2;
+  const TITLE: &'static str = "Gift Shop";
+
+  type Parsed = Vec<IdRange>;
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("1227775554"),
+    part2: Some("4174379265"),
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    Ok(parse::comma_separated(input)?)
+  }
+
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    sum_invalid(parsed, is_invalid_id)
+  }
+
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    sum_invalid(parsed, is_invalid_id2)
   }
-  invalid
 }
 
 fn main() -> anyhow::Result<()> {
-  let ranges = INPUT.split(',').flat_map(IdRange::try_from);
+  run_day::<Day02>()
+}
 
-  let mut total = 0u64;
-  for IdRange { min, max } in ranges {
-    eprintln!("Checking IDs between {min} and {max}");
-    let invalids: u64 = (min..=max).filter(is_invalid_id2).sum();
-    total += invalids;
-  }
-  println!("{total}");
-  Ok(())
+#[cfg(test)]
+mod tests {
+  use aoc25::aoc_test;
+
+  use super::*;
+
+  aoc_test!(Day02, part1, SAMPLE_INPUT, 1227775554);
+  aoc_test!(Day02, part2, SAMPLE_INPUT, 4174379265u64);
 }
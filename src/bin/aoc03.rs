@@ -1,16 +1,20 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use aoc25::{exts::duration::DurationExt, time::time};
+use aoc25::{
+  exts::{iterator::IteratorExt, numbers::UnsignedExt},
+  parse,
+  solution::{Sample, Solution, run_day},
+};
 
-const INPUT: &str = include_str!("data/03.txt");
+const SAMPLE_INPUT: &str = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
 
 #[derive(Clone, Debug)]
-struct Bank(Vec<u32>);
+pub struct Bank(Vec<u32>);
 
-impl TryFrom<&str> for Bank {
-  type Error = anyhow::Error;
+impl FromStr for Bank {
+  type Err = anyhow::Error;
 
-  fn try_from(value: &str) -> Result<Self, Self::Error> {
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
     anyhow::ensure!(!value.is_empty(), "No data");
     let values: Vec<_> = value.bytes().map(|c| (c - b'0') as u32).collect();
     Ok(Self(values))
@@ -28,13 +32,11 @@ impl Display for Bank {
 impl Bank {
   // 16951: too low
   // 17193
-  #[allow(unused)]
   fn part_one(&self) -> Option<u32> {
     let Bank(bank) = self;
     // all except last
     let search = &bank[..(bank.len() - 1)];
-    let first = search.iter().max()?;
-    let first_index = search.iter().position(|x| x == first)?;
+    let (first, first_index) = search.iter().max_with_position()?;
 
     // all after first
     let offset = first_index + 1;
@@ -46,74 +48,89 @@ impl Bank {
   }
 
   // 171297349921310
-  #[allow(unused)]
   fn part_two(&self) -> Option<u64> {
     let Bank(bank) = self;
 
-    // finds the largest digit and its offset in the haystack
-    fn find_next(haystack: &[u32]) -> Option<(u32, usize)> {
-      let value = *haystack.iter().max()?;
-      let index = haystack.iter().position(|x| *x == value)?;
-      Some((value, index))
-    }
-
-    let mut joltage: u64 = 0;
+    let mut digits = vec![];
     let mut start = 0;
     for end in (0..12).rev().map(|x| bank.len() - x) {
-      let (value, offset) = find_next(&bank[start..end])?;
-      joltage = (joltage * 10) + value as u64;
+      // the largest digit, and its offset in the remaining bank
+      let (value, offset) = bank[start..end].iter().max_with_position()?;
+      digits.push(*value as u8);
       start += offset + 1;
     }
 
-    Some(joltage)
+    Some(u64::from_digits(digits))
   }
 }
 
-fn main() -> anyhow::Result<()> {
-  println!("AoC Day 03: Lobby");
-  let (banks, dur) = time(|| INPUT.lines().flat_map(Bank::try_from).collect::<Vec<_>>());
-  println!("Parsed input in {}", dur.display());
+pub struct Day03;
+
+impl Solution for Day03 {
+  const DAY: u32 = 3;
+  const TITLE: &'static str = "Lobby";
+
+  type Parsed = Vec<Bank>;
 
-  let banks1 = banks.clone();
-  let (part_one, dur) = time(|| banks1.iter().flat_map(|b| b.part_one()).sum::<u32>());
-  println!("Part 1: {part_one} (in {})", dur.display());
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("357"),
+    part2: Some("3121910778619"),
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    Ok(parse::lines_of(input)?)
+  }
+
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    parsed.iter().flat_map(Bank::part_one).sum::<u32>()
+  }
 
-  let (part_two, dur) = time(|| banks.iter().flat_map(|b| b.part_two()).sum::<u64>());
-  println!("Part 2: {part_two} (in {})", dur.display());
-  Ok(())
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    parsed.iter().flat_map(Bank::part_two).sum::<u64>()
+  }
+}
+
+fn main() -> anyhow::Result<()> {
+  run_day::<Day03>()
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use aoc25::aoc_test;
   use core::assert_matches;
 
+  use super::*;
+
+  aoc_test!(Day03, part1, SAMPLE_INPUT, 357);
+  aoc_test!(Day03, part2, SAMPLE_INPUT, 3121910778619u64);
+
   #[test]
   fn test_one() {
-    let bank: Bank = "123456123".try_into().unwrap();
+    let bank: Bank = "123456123".parse().unwrap();
     assert_matches!(bank.part_one(), Some(63));
 
-    let bank: Bank = "987654321111111".try_into().unwrap();
+    let bank: Bank = "987654321111111".parse().unwrap();
     assert_eq!(bank.part_one(), Some(98));
-    let bank: Bank = "811111111111119".try_into().unwrap();
+    let bank: Bank = "811111111111119".parse().unwrap();
     assert_eq!(bank.part_one(), Some(89));
-    let bank: Bank = "234234234234278".try_into().unwrap();
+    let bank: Bank = "234234234234278".parse().unwrap();
     assert_eq!(bank.part_one(), Some(78));
-    let bank: Bank = "818181911112111".try_into().unwrap();
+    let bank: Bank = "818181911112111".parse().unwrap();
     assert_eq!(bank.part_one(), Some(92));
-    let bank: Bank = "818181989111121".try_into().unwrap();
+    let bank: Bank = "818181989111121".parse().unwrap();
     assert_eq!(bank.part_one(), Some(99));
   }
 
   #[test]
   fn test_two() {
-    let bank: Bank = "987654321111111".try_into().unwrap();
+    let bank: Bank = "987654321111111".parse().unwrap();
     assert_eq!(bank.part_two(), Some(987654321111));
-    let bank: Bank = "811111111111119".try_into().unwrap();
+    let bank: Bank = "811111111111119".parse().unwrap();
     assert_eq!(bank.part_two(), Some(811111111119));
-    let bank: Bank = "234234234234278".try_into().unwrap();
+    let bank: Bank = "234234234234278".parse().unwrap();
     assert_eq!(bank.part_two(), Some(434234234278));
-    let bank: Bank = "818181911112111".try_into().unwrap();
+    let bank: Bank = "818181911112111".parse().unwrap();
     assert_eq!(bank.part_two(), Some(888911112111));
   }
 }
@@ -1,15 +1,15 @@
+use std::{fmt::Display, str::FromStr};
+
 use anyhow::bail;
 use aoc25::{
-  exts::duration::DurationExt,
   grid::Grid,
-  time::{time, time_try},
+  solution::{Sample, Solution, run_day},
 };
-use std::str::FromStr;
 
-const INPUT: &str = include_str!("data/04.txt");
+const SAMPLE_INPUT: &str = "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.";
 
 #[derive(Clone, Copy, Eq, PartialEq)]
-enum MapCell {
+pub enum MapCell {
   None,
   Roll,
 }
@@ -41,14 +41,12 @@ fn find_accessible(grid: &Grid<MapCell>) -> impl Iterator<Item = (usize, usize,
     .filter(|(row, col, _)| grid.count_neighbors(*row, *col, MapCell::is_roll) <= MAX_NEIGHBORS)
 }
 
-#[allow(unused)]
 fn part_one(grid: &Grid<MapCell>) -> usize {
   // 1376
   find_accessible(grid).count()
 }
 
-#[allow(unused)]
-fn part_two(grid: Grid<MapCell>) -> usize {
+fn part_two(grid: &Grid<MapCell>) -> usize {
   // 8587
   fn remove_accessible(grid: Grid<MapCell>) -> (Grid<MapCell>, usize) {
     let mut next = grid.clone();
@@ -61,11 +59,10 @@ fn part_two(grid: Grid<MapCell>) -> usize {
   }
 
   let mut total_removed = 0;
-  let mut grid = grid;
+  let mut grid = grid.clone();
   loop {
     let (next, removed) = remove_accessible(grid);
     total_removed += removed;
-    eprintln!("removed {removed} rolls");
     grid = next;
     if removed == 0 {
       break;
@@ -74,37 +71,43 @@ fn part_two(grid: Grid<MapCell>) -> usize {
   total_removed
 }
 
-fn main() -> anyhow::Result<()> {
-  println!("AoC Day 04: Printing Department");
-  let (grid, dur) = time_try(|| Grid::from_str(INPUT))?;
-  println!("Parsed input in {}", dur.display());
+pub struct Day04;
+
+impl Solution for Day04 {
+  const DAY: u32 = 4;
+  const TITLE: &'static str = "Printing Department";
+
+  type Parsed = Grid<MapCell>;
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("13"),
+    part2: Some("43"),
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    Grid::from_str(input)
+  }
+
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
+  }
 
-  let (part_one, dur) = time(|| part_one(&grid));
-  println!("Part 1: {part_one} (in {})", dur.display());
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
+  }
+}
 
-  let (part_two, dur) = time(|| part_two(grid));
-  println!("Part 2: {part_two} (in {})", dur.display());
-  Ok(())
+fn main() -> anyhow::Result<()> {
+  run_day::<Day04>()
 }
 
 #[cfg(test)]
 mod tests {
+  use aoc25::aoc_test;
 
   use super::*;
 
-  const SAMPLE_INPUT: &str = "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.";
-
-  #[test]
-  fn test_part_one() {
-    let grid = Grid::from_str(SAMPLE_INPUT).unwrap();
-    let accessible = part_one(&grid);
-    assert_eq!(accessible, 13);
-  }
-
-  #[test]
-  fn test_part_two() {
-    let grid = Grid::from_str(SAMPLE_INPUT).unwrap();
-    let accessible = part_two(grid);
-    assert_eq!(accessible, 43);
-  }
+  aoc_test!(Day04, part1, SAMPLE_INPUT, 13);
+  aoc_test!(Day04, part2, SAMPLE_INPUT, 43);
 }
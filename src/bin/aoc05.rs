@@ -1,14 +1,15 @@
-use std::ops::RangeInclusive;
+use std::{fmt::Display, ops::RangeInclusive};
 
 use aoc25::{
-  exts::{duration::DurationExt, iterator::IteratorExt},
-  time::{time, time_try},
+  exts::{iterator::IteratorExt, string::StrExt},
+  ranges::IntervalSet,
+  solution::{Sample, Solution, run_day},
 };
 
-const INPUT: &str = include_str!("data/05.txt");
+const SAMPLE_INPUT: &str = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32";
 
 #[derive(Clone, Eq, PartialEq)]
-struct Inventory {
+pub struct Inventory {
   fresh: Vec<RangeInclusive<u64>>,
   ids: Vec<u64>,
 }
@@ -17,9 +18,10 @@ impl TryFrom<&str> for Inventory {
   type Error = anyhow::Error;
 
   fn try_from(value: &str) -> Result<Self, Self::Error> {
-    let (ranges, ids) = value
-      .split_once("\n\n")
-      .ok_or(anyhow::anyhow!("part missing"))?;
+    let mut blocks = value.blocks();
+    let (Some(ranges), Some(ids)) = (blocks.next(), blocks.next()) else {
+      anyhow::bail!("part missing");
+    };
 
     let fresh_ranges = ranges
       .lines()
@@ -43,8 +45,7 @@ impl TryFrom<&str> for Inventory {
   }
 }
 
-#[allow(unused)]
-fn part_one(inventory: Inventory) -> usize {
+fn part_one(inventory: &Inventory) -> usize {
   // 712
   let Inventory { ids, fresh } = inventory;
   ids
@@ -53,73 +54,50 @@ fn part_one(inventory: Inventory) -> usize {
     .count()
 }
 
-fn merge_ranges(ranges: Vec<RangeInclusive<u64>>) -> (Vec<RangeInclusive<u64>>, usize) {
-  #[inline]
-  fn overlaps(a: &RangeInclusive<u64>, b: &RangeInclusive<u64>) -> bool {
-    a.start() <= b.end() && b.start() <= a.end()
-  }
-
-  let mut new = vec![];
-  let mut count = 0;
-  for range in ranges.into_iter() {
-    if let Some(overlap) = new.iter_mut().find(|r| overlaps(r, &range)) {
-      *overlap = (*overlap.start().min(range.start()))..=(*overlap.end().max(range.end()));
-      count += 1;
-    } else {
-      new.push(range);
-    }
-  }
-  (new, count)
-}
-
-#[allow(unused)]
-fn part_two(inventory: Inventory) -> usize {
+fn part_two(inventory: &Inventory) -> u128 {
   // 338348170606125: too high
   // 332998283036769
+  let fresh: IntervalSet<u64> = inventory.fresh.iter().cloned().collect();
+  fresh.total_len()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+  const DAY: u32 = 5;
+  const TITLE: &'static str = "Cafeteria";
+
+  type Parsed = Inventory;
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("3"),
+    part2: Some("14"),
+  }];
 
-  let mut ranges = inventory.fresh;
-  loop {
-    let (new, count) = merge_ranges(ranges);
-    ranges = new;
-    if count == 0 {
-      break;
-    }
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    input.try_into()
   }
 
-  ranges.into_iter().map(|r| r.count()).sum()
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
+  }
+
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
+  }
 }
 
 fn main() -> anyhow::Result<()> {
-  println!("AoC Day 05: Cafeteria");
-  let (inventory, dur) = time_try(|| INPUT.try_into())?;
-  println!("Parsed input in {}", dur.display());
-
-  let (part_one, dur) = time(|| part_one(inventory));
-  println!("Part 1: {part_one} (in {})", dur.display());
-
-  let inventory = INPUT.try_into()?;
-  let (part_two, dur) = time(|| part_two(inventory));
-  println!("Part 2: {part_two} (in {})", dur.display());
-  Ok(())
+  run_day::<Day05>()
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
-
-  const SAMPLE_INPUT: &str = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32";
+  use aoc25::aoc_test;
 
-  #[test]
-  fn test_one() {
-    let inventory = SAMPLE_INPUT.try_into().unwrap();
-    let fresh = part_one(inventory);
-    assert_eq!(fresh, 3);
-  }
+  use super::*;
 
-  #[test]
-  fn test_two() {
-    let inventory = SAMPLE_INPUT.try_into().unwrap();
-    let fresh = part_two(inventory);
-    assert_eq!(fresh, 14);
-  }
+  aoc_test!(Day05, part1, SAMPLE_INPUT, 3);
+  aoc_test!(Day05, part2, SAMPLE_INPUT, 14);
 }
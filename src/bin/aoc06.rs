@@ -1,7 +1,9 @@
+use std::fmt::Display;
+
 use anyhow::{anyhow, bail, ensure};
-use aoc25::{exts::duration::DurationExt, time::time_try};
+use aoc25::solution::{Sample, Solution, run_day};
 
-const INPUT: &str = include_str!("data/06.txt");
+const SAMPLE_INPUT: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
 
 #[derive(Clone, Eq, PartialEq)]
 enum Operator {
@@ -37,7 +39,7 @@ impl Problem {
 }
 
 #[derive(Clone, Eq, PartialEq)]
-struct ProblemsOne(Vec<Problem>);
+pub struct ProblemsOne(Vec<Problem>);
 
 impl TryFrom<&str> for ProblemsOne {
   type Error = anyhow::Error;
@@ -86,14 +88,13 @@ impl TryFrom<&str> for ProblemsOne {
   }
 }
 
-#[allow(unused)]
-fn part_one(problems: ProblemsOne) -> Vec<u64> {
+fn part_one(problems: &ProblemsOne) -> Vec<u64> {
   // 6605396225322
   problems.0.iter().map(Problem::compute).collect()
 }
 
 #[derive(Clone, Eq, PartialEq)]
-struct ProblemsTwo(Vec<Problem>);
+pub struct ProblemsTwo(Vec<Problem>);
 
 impl TryFrom<&str> for ProblemsTwo {
   type Error = anyhow::Error;
@@ -145,40 +146,56 @@ impl TryFrom<&str> for ProblemsTwo {
   }
 }
 
-#[allow(unused)]
-fn part_two(problems: ProblemsTwo) -> Vec<u64> {
+fn part_two(problems: &ProblemsTwo) -> Vec<u64> {
   // 11052310600986
   problems.0.iter().map(Problem::compute).collect()
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+  const DAY: u32 = 6;
+  const TITLE: &'static str = "Trash Compactor";
+
+  /// The parts read the numbers differently, so the input is parsed once for each.
+  type Parsed = (ProblemsOne, ProblemsTwo);
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("4277556"),
+    part2: Some("3263827"),
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    Ok((input.try_into()?, input.try_into()?))
+  }
+
+  fn part1((problems, _): &Self::Parsed) -> impl Display {
+    part_one(problems).iter().sum::<u64>()
+  }
+
+  fn part2((_, problems): &Self::Parsed) -> impl Display {
+    part_two(problems).iter().sum::<u64>()
+  }
+}
+
 fn main() -> anyhow::Result<()> {
-  println!("AoC Day 06: Trash Compactor");
-  let (part_one, dur) = time_try(|| -> anyhow::Result<u64> {
-    let problem = INPUT.try_into()?;
-    let totals_one = part_one(problem);
-    Ok(totals_one.iter().sum())
-  })?;
-  println!("Part 1: {part_one} (in {})", dur.display());
-
-  let (part_two, dur) = time_try(|| -> anyhow::Result<u64> {
-    let problem = INPUT.try_into()?;
-    let total = part_two(problem);
-    Ok(total.iter().sum())
-  })?;
-  println!("Part 2: {part_two} (in {})", dur.display());
-  Ok(())
+  run_day::<Day06>()
 }
 
 #[cfg(test)]
 mod tests {
+  use aoc25::aoc_test;
+
   use super::*;
 
-  const SAMPLE_INPUT: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
+  aoc_test!(Day06, part1, SAMPLE_INPUT, 4277556);
+  aoc_test!(Day06, part2, SAMPLE_INPUT, 3263827);
 
   #[test]
   fn test_one() {
     let problems = SAMPLE_INPUT.try_into().unwrap();
-    let totals = part_one(problems);
+    let totals = part_one(&problems);
     assert_eq!(totals, vec![33210, 490, 4243455, 401]);
     let grand_total: u64 = totals.iter().sum();
     assert_eq!(grand_total, 4277556);
@@ -187,7 +204,7 @@ mod tests {
   #[test]
   fn test_two() {
     let problems = SAMPLE_INPUT.try_into().unwrap();
-    let totals = part_two(problems);
+    let totals = part_two(&problems);
     assert_eq!(totals, vec![8544, 625, 3253600, 1058]);
     let grand_total: u64 = totals.iter().sum();
     assert_eq!(grand_total, 3263827);
@@ -1,18 +1,18 @@
-use std::{mem, str::FromStr};
+use std::{fmt::Display, mem, str::FromStr};
 
 use anyhow::bail;
 use aoc25::{
-  exts::duration::DurationExt,
   grid::Grid,
-  time::{time, time_try},
+  solution::{Sample, Solution, run_day},
+  viz,
 };
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("data/07.txt");
+const SAMPLE_INPUT: &str = ".......S.......\n...............\n.......^.......\n...............\n......^.^......\n...............\n.....^.^.^.....\n...............\n....^.^...^....\n...............\n...^.^...^.^...\n...............\n..^...^.....^..\n...............\n.^.^.^.^.^...^.\n...............";
 
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
-enum TachyonManifold {
+pub enum TachyonManifold {
   Empty,
   Splitter,
   Start,
@@ -40,6 +40,14 @@ fn part_one(manifold: &Grid<TachyonManifold>) -> u64 {
     .collect();
 
   let mut splits = 0;
+  let mut drawn = vec![];
+  let mut draw = |row: usize, beams: &[bool]| {
+    if viz::is_enabled() {
+      drawn.push(draw_row(manifold, row, beams));
+      viz::text(|| drawn.join("\n"));
+    }
+  };
+  draw(0, &beams);
 
   for row in 1..manifold.height() {
     for splitter in manifold
@@ -54,11 +62,26 @@ fn part_one(manifold: &Grid<TachyonManifold>) -> u64 {
         beams[splitter + 1] = true;
       }
     }
+    draw(row, &beams);
   }
 
   splits
 }
 
+/// A row of the manifold as text, with the beams passing through it.
+fn draw_row(manifold: &Grid<TachyonManifold>, row: usize, beams: &[bool]) -> String {
+  manifold
+    .row(row)
+    .zip(beams)
+    .map(|(c, &beam)| match c {
+      TachyonManifold::Start => 'S',
+      TachyonManifold::Splitter => '^',
+      TachyonManifold::Empty if beam => '|',
+      TachyonManifold::Empty => '.',
+    })
+    .collect()
+}
+
 #[allow(unused)]
 fn part_two(manifold: &Grid<TachyonManifold>) -> u64 {
   // 431691375: too low
@@ -88,36 +111,43 @@ fn part_two(manifold: &Grid<TachyonManifold>) -> u64 {
   timelines.iter().sum()
 }
 
-fn main() -> anyhow::Result<()> {
-  println!("AoC Day 07: Laboratories");
-  let (manifold, dur) = time_try(|| Grid::from_str(INPUT))?;
-  println!("Parsed input in {}", dur.display());
+pub struct Day07;
 
-  let (part_one, dur) = time(|| part_one(&manifold));
-  println!("Part 1: {part_one} (in {})", dur.display());
+impl Solution for Day07 {
+  const DAY: u32 = 7;
+  const TITLE: &'static str = "Laboratories";
 
-  let (part_two, dur) = time(|| part_two(&manifold));
-  println!("Part 2: {part_two} (in {})", dur.display());
-  Ok(())
-}
+  type Parsed = Grid<TachyonManifold>;
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("21"),
+    part2: Some("40"),
+  }];
 
-  const SAMPLE_INPUT: &str = ".......S.......\n...............\n.......^.......\n...............\n......^.^......\n...............\n.....^.^.^.....\n...............\n....^.^...^....\n...............\n...^.^...^.^...\n...............\n..^...^.....^..\n...............\n.^.^.^.^.^...^.\n...............";
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    Grid::from_str(input)
+  }
 
-  #[test]
-  fn test_one() {
-    let manifold = Grid::from_str(SAMPLE_INPUT).unwrap();
-    let splits = part_one(&manifold);
-    assert_eq!(splits, 21);
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
   }
 
-  #[test]
-  fn test_two() {
-    let manifold = Grid::from_str(SAMPLE_INPUT).unwrap();
-    let timelines = part_two(&manifold);
-    assert_eq!(timelines, 40);
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
   }
 }
+
+fn main() -> anyhow::Result<()> {
+  run_day::<Day07>()
+}
+
+#[cfg(test)]
+mod tests {
+  use aoc25::aoc_test;
+
+  use super::*;
+
+  aoc_test!(Day07, part1, SAMPLE_INPUT, 21);
+  aoc_test!(Day07, part2, SAMPLE_INPUT, 40);
+}
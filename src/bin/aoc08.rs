@@ -1,15 +1,17 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
 use aoc25::{
-  exts::duration::DurationExt,
   graph::Graph,
   graph_algo::search::SearchMode,
-  time::{time, time_try},
+  solution::{Sample, Solution, run_day},
+  spatial::KdTree,
+  unionfind::UnionFind,
+  vex::Vex,
 };
 use glam::I64Vec3;
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("data/08.txt");
+const SAMPLE_INPUT: &str = "162,817,812\n57,618,57\n906,360,560\n592,479,940\n352,342,300\n466,668,158\n542,29,236\n431,825,988\n739,650,466\n52,470,668\n216,146,977\n819,987,18\n117,168,530\n805,96,715\n346,949,466\n970,615,88\n941,993,340\n862,61,35\n984,92,344\n425,690,689";
 
 fn parse_graph(input: &str) -> anyhow::Result<Graph<I64Vec3, u64>> {
   let mut graph: Graph<I64Vec3, u64> = Graph::new();
@@ -25,25 +27,37 @@ fn parse_graph(input: &str) -> anyhow::Result<Graph<I64Vec3, u64>> {
     graph.add_node(*pos);
   }
 
-  for (n1, pos1) in nodes.iter().enumerate() {
-    let mut shortest = 10000000000; // big enough for our purposes
-    for (n2, pos2) in nodes.iter().enumerate() {
-      if n1 == n2 {
-        continue;
+  let points: Vec<Vex<i64, 3>> = nodes.iter().map(|pos| Vex(pos.to_array())).collect();
+  let tree = KdTree::new(points.iter().copied());
+  // both parts only look at the shortest edges, so every edge up to some radius is enough:
+  // part 1 needs the `count` shortest, with `count` less than the number of nodes,
+  // and part 2 needs the edges to connect every node into a single circuit
+  let all_pairs = points.len() * points.len().saturating_sub(1) / 2;
+  let mut radius = points
+    .iter()
+    .flat_map(|pos| tree.nearest(pos, 2).get(1).map(|(_, dist)| *dist))
+    .max()
+    .unwrap_or(0)
+    .isqrt()
+    + 1;
+  let edges = loop {
+    let mut edges = vec![];
+    let mut circuits = UnionFind::new(0..points.len());
+    for (n1, pos1) in points.iter().enumerate() {
+      for (n2, dist) in tree.within_radius(pos1, radius) {
+        if n1 < n2 {
+          edges.push((n1, n2, dist));
+          let _ = circuits.join(n1, n2);
+        }
       }
-      let dist = (*pos2 - *pos1).length_squared() as u64;
-
-      if dist >= 7 * shortest {
-        // this cutoff is somewhat arbitrary, but saves a lot of time
-        // part 2 works even with the cutoff = 1
-        // part 1 relies on the globally shortest nodes, not locally shortest
-        // but seems to work with cutoff >= 7, but that is probably a coincidence
-        continue;
-      }
-      shortest = shortest.min(dist);
-
-      graph.add_edge(n1, n2, dist);
     }
+    if edges.len() == all_pairs || (edges.len() >= points.len() && circuits.sets() == 1) {
+      break edges;
+    }
+    radius *= 2;
+  };
+  for (n1, n2, dist) in edges {
+    graph.add_edge(n1, n2, dist);
   }
 
   eprintln!("{} nodes, {} edges", graph.num_nodes(), graph.num_edges());
@@ -125,24 +139,42 @@ fn part_two(graph: &Graph<I64Vec3, u64>) -> u64 {
   unreachable!()
 }
 
-fn main() -> anyhow::Result<()> {
-  println!("AoC Day 08: Playground");
-  let (graph, dur) = time_try(|| parse_graph(INPUT))?;
-  println!("Parsed input in {}", dur.display());
+pub struct Day08;
+
+impl Solution for Day08 {
+  const DAY: u32 = 8;
+  const TITLE: &'static str = "Playground";
+
+  type Parsed = Graph<I64Vec3, u64>;
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: None,
+    part2: Some("25272"),
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    parse_graph(input)
+  }
 
-  let (part_one, dur) = time(|| part_one(&graph, 998));
-  println!("Part 1: {part_one} (in {})", dur.display());
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed, 998)
+  }
 
-  let (part_two, dur) = time(|| part_two(&graph));
-  println!("Part 2: {part_two} (in {})", dur.display());
-  Ok(())
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
+  }
+}
+
+fn main() -> anyhow::Result<()> {
+  run_day::<Day08>()
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use aoc25::aoc_test;
 
-  const SAMPLE_INPUT: &str = "162,817,812\n57,618,57\n906,360,560\n592,479,940\n352,342,300\n466,668,158\n542,29,236\n431,825,988\n739,650,466\n52,470,668\n216,146,977\n819,987,18\n117,168,530\n805,96,715\n346,949,466\n970,615,88\n941,993,340\n862,61,35\n984,92,344\n425,690,689";
+  use super::*;
 
   #[test]
   fn test_one() {
@@ -152,9 +184,10 @@ mod tests {
   }
 
   #[test]
-  fn test_two() {
-    let graph = parse_graph(SAMPLE_INPUT).unwrap();
-    let total = part_two(&graph);
-    assert_eq!(total, 25272);
+  fn test_few_points() {
+    assert_eq!(parse_graph("1,2,3").unwrap().num_edges(), 0);
+    assert_eq!(parse_graph("1,2,3\n4,5,6").unwrap().num_edges(), 1);
   }
+
+  aoc_test!(Day08, part2, SAMPLE_INPUT, 25272);
 }
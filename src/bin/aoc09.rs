@@ -1,242 +1,106 @@
-use std::fmt::Display;
+use std::{cmp::Reverse, fmt::Display};
 
 use aoc25::{
-  exts::duration::DurationExt,
-  grid::Grid,
-  time::{time, time_try},
+  exts::iterator::IteratorExt,
+  geom::polygon::Polygon,
+  solution::{Sample, Solution, run_day},
+  vex::Vex,
 };
-use glam::I64Vec2;
-use itertools::Itertools;
 
-const INPUT: &str = include_str!("data/09.txt");
+const SAMPLE_INPUT: &str = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n";
 
-fn parse(input: &str) -> anyhow::Result<Vec<I64Vec2>> {
-  let nums: Vec<I64Vec2> = input
+const ONLINE_SAMPLE_INPUT: &str =
+  "1,0\n3,0\n3,6\n16,6\n16,0\n18,0\n18,9\n13,9\n13,7\n6,7\n6,9\n1,9";
+
+fn parse(input: &str) -> anyhow::Result<Vec<Vex<i64, 2>>> {
+  let nums: Vec<Vex<i64, 2>> = input
     .lines()
     .flat_map(|l| {
       l.split_once(',')
         .into_iter()
-        .flat_map(|(x, y)| -> Result<_, anyhow::Error> { Ok(I64Vec2::new(x.parse()?, y.parse()?)) })
+        .flat_map(|(x, y)| -> Result<_, anyhow::Error> { Ok(Vex([x.parse()?, y.parse()?])) })
     })
     .collect();
   Ok(nums)
 }
 
-fn compute_rect(a: &I64Vec2, b: &I64Vec2) -> u64 {
-  let xdiff = a.x.abs_diff(b.x) + 1;
-  let ydiff = a.y.abs_diff(b.y) + 1;
+fn compute_rect(a: &Vex<i64, 2>, b: &Vex<i64, 2>) -> u64 {
+  let xdiff = a.x().abs_diff(b.x()) + 1;
+  let ydiff = a.y().abs_diff(b.y()) + 1;
   xdiff * ydiff
 }
 
-fn part_one(points: &Vec<I64Vec2>) -> u64 {
+fn part_one(points: &[Vex<i64, 2>]) -> u64 {
   // 4777824480
-  let mut largest = 0;
-  for v1 in points {
-    for v2 in points {
-      if v1 == v2 {
-        continue;
-      }
-
-      let area = compute_rect(v1, v2);
-      if area > largest {
-        //eprintln!("rect: {:?}, {:?} = {}", v1, v2, area);
-        largest = area;
-      }
-    }
-  }
-  largest
-}
-
-fn add_line(grid: &mut Grid<Tile>, a: &I64Vec2, b: &I64Vec2) {
-  let dir = *b - *a;
-  if dir.x == 0 {
-    let x = a.x as usize;
-    let start = a.y;
-    let dirsign = dir.y.signum();
-    for y in 0..=(dir.y.abs()) {
-      grid.set(x, (start + (y * dirsign)) as usize, Tile::Edge);
-    }
-  } else if dir.y == 0 {
-    let y = a.y as usize;
-    let start = a.x;
-    let dirsign = dir.x.signum();
-    for x in 0..=(dir.x.abs()) {
-      grid.set((start + (x * dirsign)) as usize, y, Tile::Edge);
-    }
-  }
-}
-
-fn flood_fill(grid: &mut Grid<Tile>) {
-  // (0,0) is guaranteed to not be in the polygon due to padding
-  let mut queue = vec![(0, 0)];
-  while let Some((x, y)) = queue.pop() {
-    if let Some(Tile::Inside) = grid.get(x, y) {
-      grid.set(x, y, Tile::Outside);
-      if x != 0 {
-        queue.push((x - 1, y));
-      }
-      queue.push((x + 1, y));
-      if y != 0 {
-        queue.push((x, y - 1));
-      }
-      queue.push((x, y + 1));
-    }
-  }
-}
-#[repr(u8)]
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Tile {
-  Outside,
-  Inside,
-  Edge,
-}
-
-impl Display for Tile {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    match self {
-      Tile::Outside => write!(f, " "),
-      Tile::Edge => write!(f, "#"),
-      Tile::Inside => write!(f, "."),
-    }
-  }
+  points
+    .iter()
+    .unordered_pairs()
+    .map(|(v1, v2)| compute_rect(v1, v2))
+    .max()
+    .unwrap_or(0)
 }
 
-fn in_polygon(grid: &Grid<Tile>, x1: i64, y1: i64, x2: i64, y2: i64) -> bool {
-  if x1 == x2 {
-    let x = x1;
-    for y in y1.min(y2)..y1.max(y2) {
-      if let Some(Tile::Outside) = grid.get(x as usize, y as usize) {
-        return false;
-      }
-    }
-    true
-  } else if y1 == y2 {
-    let y = y1;
-    for x in x1.min(x2)..x1.max(x2) {
-      if let Some(Tile::Outside) = grid.get(x as usize, y as usize) {
-        return false;
-      }
-    }
-    true
-  } else {
-    unreachable!()
-  }
-}
-
-struct Compressed {
-  points: Vec<I64Vec2>,
-  xs: Vec<i64>,
-  ys: Vec<i64>,
+fn part_two(points: &[Vex<i64, 2>]) -> u64 {
+  // 1542119040
+  let polygon = Polygon::new(points.to_vec());
+  let mut rects: Vec<_> = points
+    .iter()
+    .unordered_pairs()
+    .map(|(v1, v2)| (compute_rect(v1, v2), v1, v2))
+    .collect();
+  // the first rectangle inside the polygon is the largest
+  rects.sort_unstable_by_key(|(area, _, _)| Reverse(*area));
+  rects
+    .into_iter()
+    .find(|(_, v1, v2)| polygon.contains_rect(**v1, **v2))
+    .map_or(0, |(area, _, _)| area)
 }
 
-impl Compressed {
-  fn compress(original: &[I64Vec2]) -> Self {
-    let (mut xs, mut ys): (Vec<i64>, Vec<i64>) = original.iter().map(|v| (v.x, v.y)).collect();
-    xs.sort();
-    ys.sort();
-
-    let points: Vec<I64Vec2> = original
-      .iter()
-      .map(|v| {
-        I64Vec2::new(
-          xs.iter().position(|x| *x == v.x).unwrap() as i64 + 1,
-          ys.iter().position(|x| *x == v.y).unwrap() as i64 + 1,
-        )
-      })
-      .collect();
-
-    Self { points, xs, ys }
-  }
-
-  fn uncompress(&self, point: &I64Vec2) -> I64Vec2 {
-    let x = self.xs[point.x as usize - 1];
-    let y = self.ys[point.y as usize - 1];
-    I64Vec2::new(x, y)
+pub struct Day09;
+
+impl Solution for Day09 {
+  const DAY: u32 = 9;
+  const TITLE: &'static str = "Movie Theater";
+
+  type Parsed = Vec<Vex<i64, 2>>;
+
+  const SAMPLES: &'static [Sample] = &[
+    Sample {
+      input: SAMPLE_INPUT,
+      part1: Some("50"),
+      part2: Some("24"),
+    },
+    Sample {
+      input: ONLINE_SAMPLE_INPUT,
+      part1: None,
+      part2: Some("30"),
+    },
+  ];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    parse(input)
   }
-}
 
-fn part_two(points: &[I64Vec2]) -> u64 {
-  // 1542119040
-  let compressed = Compressed::compress(points);
-
-  let mut grid = Grid::new(
-    compressed.xs.len() + 2,
-    compressed.ys.len() + 2,
-    Tile::Inside,
-  );
-
-  for (a, b) in compressed.points.iter().circular_tuple_windows() {
-    add_line(&mut grid, a, b);
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
   }
 
-  flood_fill(&mut grid);
-
-  #[cfg(test)]
-  eprintln!("{grid}");
-
-  let mut largest = 0;
-  for v1 in &compressed.points {
-    for v2 in &compressed.points {
-      if v1 == v2 {
-        continue;
-      }
-
-      let area = compute_rect(&compressed.uncompress(v1), &compressed.uncompress(v2));
-      if area <= largest {
-        // Skip expensive polygon check if this cannot be a candidate
-        continue;
-      }
-
-      // x1y1 -> x1y2 -> x2y2 -> x2y1 -> x1y1
-      if in_polygon(&grid, v1.x, v1.y, v1.x, v2.y)
-        && in_polygon(&grid, v1.x, v2.y, v2.x, v2.y)
-        && in_polygon(&grid, v2.x, v2.y, v2.x, v1.y)
-        && in_polygon(&grid, v2.x, v1.y, v1.x, v1.y)
-      {
-        largest = area;
-      }
-    }
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
   }
-  largest
 }
 
 fn main() -> anyhow::Result<()> {
-  println!("AoC Day 09: Movie Theater");
-  let (points, dur) = time_try(|| parse(INPUT))?;
-  println!("Parsed input in {}", dur.display());
-
-  let (part_one, dur) = time(|| part_one(&points));
-  println!("Part 1: {part_one} (in {})", dur.display());
-
-  let (part_two, dur) = time(|| part_two(&points));
-  println!("Part 2: {part_two} (in {})", dur.display());
-  Ok(())
+  run_day::<Day09>()
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
-
-  const SAMPLE_INPUT: &str = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n";
-
-  #[test]
-  fn test_one() {
-    let points = parse(SAMPLE_INPUT).unwrap();
-    let total = part_one(&points);
-    assert_eq!(total, 50);
-  }
+  use aoc25::aoc_test;
 
-  #[test]
-  fn test_two() {
-    let points = parse(SAMPLE_INPUT).unwrap();
-    let total = part_two(&points);
-    assert_eq!(total, 24);
-  }
+  use super::*;
 
-  #[test]
-  fn from_online() {
-    let points = parse("1,0\n3,0\n3,6\n16,6\n16,0\n18,0\n18,9\n13,9\n13,7\n6,7\n6,9\n1,9").unwrap();
-    let total = part_two(&points);
-    assert_eq!(total, 30);
-  }
+  aoc_test!(Day09, part1, SAMPLE_INPUT, 50);
+  aoc_test!(Day09, part2, SAMPLE_INPUT, 24);
+  aoc_test!(from_online: Day09, part2, ONLINE_SAMPLE_INPUT, 30);
 }
@@ -1,14 +1,16 @@
-use std::{collections::VecDeque, str::FromStr};
+use std::{fmt::Display, str::FromStr};
 
 use aoc25::{
-  exts::duration::DurationExt,
-  time::{time, time_try},
+  bitset::BitSet,
+  linalg::gf2::{self, Matrix},
+  opt::Ilp,
+  parse,
+  solution::{Sample, Solution, run_day},
 };
-use microlp::{ComparisonOp, LinearExpr, OptimizationDirection, Problem};
 
-const INPUT: &str = include_str!("data/10.txt");
+const SAMPLE_INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}\n[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}\n[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
 
-struct Machine {
+pub struct Machine {
   target: Vec<bool>,
   buttons: Vec<Vec<u32>>,
   joltage: Vec<u32>,
@@ -49,56 +51,58 @@ impl FromStr for Machine {
         .flat_map(|n| n.parse())
         .collect()
     };
-    Ok(Self {
+    let machine = Self {
       target,
       buttons,
       joltage,
-    })
+    };
+    let free = gf2::nullspace(&machine.light_system().0).len();
+    anyhow::ensure!(
+      free <= gf2::MAX_FREE_VARIABLES,
+      "too many buttons to search: {free} are free, at most {} can be",
+      gf2::MAX_FREE_VARIABLES
+    );
+    Ok(machine)
   }
 }
 
-fn parse(input: &str) -> anyhow::Result<Vec<Machine>> {
-  let problems = input.lines().flat_map(|l| l.parse()).collect();
-  Ok(problems)
-}
-
-fn toggle(current: &[bool], button: &Vec<u32>) -> Vec<bool> {
-  let mut new = current.to_owned();
-  for x in button {
-    new[*x as usize] ^= true;
+impl Machine {
+  /// The lights each button toggles and the target lights, as `a * x = b` over GF(2),
+  /// where `x` is the buttons pressed an odd number of times.
+  fn light_system(&self) -> (Matrix, BitSet) {
+    let mut buttons = Matrix::new(self.target.len(), self.buttons.len());
+    for (button, lights) in self.buttons.iter().enumerate() {
+      for light in lights {
+        buttons.set(*light as usize, button, true);
+      }
+    }
+    let mut target = BitSet::new(self.target.len());
+    for (light, on) in self.target.iter().enumerate() {
+      target.set(light, *on);
+    }
+    (buttons, target)
   }
-  new
 }
 
-fn search_buttons(initial: Vec<bool>, machine: &Machine) -> usize {
-  let mut stack: VecDeque<(Vec<bool>, usize)> = VecDeque::new();
-  stack.push_back((initial, 0));
-  //search(initial, machine);
-
-  while let Some((current, steps)) = stack.pop_back() {
-    // eprintln!("{current:?} == {:?}", machine.target);
-    if current == machine.target {
-      return steps;
-    }
+fn parse(input: &str) -> anyhow::Result<Vec<Machine>> {
+  Ok(parse::lines_of(input)?)
+}
 
-    machine.buttons.iter().for_each(|buttons| {
-      let toggled = toggle(&current, buttons);
-      // eprintln!("{current:?} + {buttons:?} = {toggled:?}");
-      stack.push_front((toggled, steps + 1))
-    })
-  }
-  0
+/// The fewest presses to reach the target lights. Each light is the xor of the buttons
+/// toggling it, so this is a linear system over GF(2).
+fn fewest_presses(machine: &Machine) -> Option<usize> {
+  let (buttons, target) = machine.light_system();
+  gf2::fewest_ones(&buttons, &target)
+    .expect("the number of free buttons is checked when parsing")
+    .map(|presses| presses.count_ones())
 }
 
 fn part_one(machines: &[Machine]) -> usize {
   // 399
-  let mut total = 0;
-  for machine in machines {
-    let initial = vec![false; machine.target.len()];
-    let steps = search_buttons(initial, machine);
-    total += steps;
-  }
-  total
+  machines
+    .iter()
+    .map(|machine| fewest_presses(machine).unwrap_or(0))
+    .sum()
 }
 
 /*
@@ -240,23 +244,23 @@ fn search2(current: Vec<u32>, machine: &Machine) -> u64 {
 */
 
 fn solve_joltage(machine: &Machine) -> u64 {
-  let mut problem = Problem::new(OptimizationDirection::Minimize);
-  let vars: Vec<_> = machine
+  let mut ilp = Ilp::minimize();
+  let presses: Vec<_> = machine
     .buttons
     .iter()
-    .map(|_| problem.add_integer_var(1.0, (0, i32::MAX)))
+    .map(|_| ilp.int_var(1.0, 0..=i32::MAX))
     .collect();
 
   for (index, joltage) in machine.joltage.iter().enumerate() {
-    let mut equation = LinearExpr::empty();
-    for (button, var) in machine.buttons.iter().zip(vars.iter()) {
-      if button.contains(&(index as u32)) {
-        equation.add(*var, 1.0);
-      }
-    }
-    problem.add_constraint(equation, ComparisonOp::Eq, *joltage as f64);
+    let terms = machine
+      .buttons
+      .iter()
+      .zip(presses.iter())
+      .filter(|(button, _)| button.contains(&(index as u32)))
+      .map(|(_, var)| (*var, 1.0));
+    ilp.eq(terms, *joltage as f64);
   }
-  problem.solve().unwrap().objective().round() as u64
+  ilp.solve().unwrap().objective_int() as u64
 }
 
 fn part_two(machines: &Vec<Machine>) -> u64 {
@@ -270,37 +274,50 @@ fn part_two(machines: &Vec<Machine>) -> u64 {
   total
 }
 
-fn main() -> anyhow::Result<()> {
-  println!("AoC Day 10: Factory");
-  let (machines, dur) = time_try(|| parse(INPUT))?;
-  println!("Parsed input in {}", dur.display());
+pub struct Day10;
+
+impl Solution for Day10 {
+  const DAY: u32 = 10;
+  const TITLE: &'static str = "Factory";
+
+  type Parsed = Vec<Machine>;
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("7"),
+    part2: Some("33"),
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    parse(input)
+  }
 
-  let (part_one, dur) = time(|| part_one(&machines));
-  println!("Part 1: {part_one} (in {})", dur.display());
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
+  }
 
-  let (part_two, dur) = time(|| part_two(&machines));
-  println!("Part 2: {part_two} (in {})", dur.display());
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
+  }
+}
 
-  Ok(())
+fn main() -> anyhow::Result<()> {
+  run_day::<Day10>()
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use aoc25::aoc_test;
 
-  const SAMPLE_INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}\n[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}\n[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+  use super::*;
 
-  #[test]
-  fn test_one() {
-    let points = parse(SAMPLE_INPUT).unwrap();
-    let total = part_one(&points);
-    assert_eq!(total, 7);
-  }
+  aoc_test!(Day10, part1, SAMPLE_INPUT, 7);
+  aoc_test!(Day10, part2, SAMPLE_INPUT, 33);
 
   #[test]
-  fn test_two() {
-    let machine = parse(SAMPLE_INPUT).unwrap();
-    let total = part_two(&machine);
-    assert_eq!(total, 33);
+  fn test_too_many_buttons() {
+    let buttons = "(0) ".repeat(gf2::MAX_FREE_VARIABLES + 2);
+    let error = Day10::parse(&format!("[#] {buttons}{{1}}")).err().unwrap();
+    assert!(error.to_string().contains("too many buttons"), "{error}");
   }
 }
@@ -1,17 +1,20 @@
 use std::{
   collections::{HashMap, VecDeque},
+  fmt::Display,
   ops::Add,
   str::FromStr,
 };
 
 use aoc25::{
-  exts::duration::DurationExt,
-  time::{time, time_try},
+  memo::memoize,
+  solution::{Sample, Solution, run_day},
 };
 
-const INPUT: &str = include_str!("data/11.txt");
+const SAMPLE_INPUT: &str = "aaa: you hhh\nyou: bbb ccc\nbbb: ddd eee\nccc: ddd eee fff\nddd: ggg\neee: out\nfff: out\nggg: out\nhhh: ccc fff iii\niii: out";
 
-struct Network(HashMap<String, Vec<String>>);
+const SAMPLE_INPUT_TWO: &str = "svr: aaa bbb\naaa: fft\nfft: ccc\nbbb: tty\ntty: ccc\nccc: ddd eee\nddd: hub\nhub: fff\neee: dac\ndac: fff\nfff: ggg hhh\nggg: out\nhhh: out";
+
+pub struct Network(HashMap<String, Vec<String>>);
 
 impl FromStr for Network {
   type Err = anyhow::Error;
@@ -83,19 +86,15 @@ impl Add for Counter {
   }
 }
 
-fn part_two(net: Network) -> u64 {
+fn part_two(net: &Network) -> u64 {
   // 2844318424: too low
   // 6547319709817560: too high
   // 473741288064360
-  fn count<'a>(net: &'a Network, node: &'a str, cache: &mut HashMap<&'a str, Counter>) -> Counter {
-    if let Some(cached) = cache.get(node) {
-      return *cached;
-    }
-
+  let mut count = memoize(|count, node: &str| {
     let mut counter = if let Some(neighbors) = net.0.get(node) {
       neighbors
         .iter()
-        .map(|node| count(net, node, cache))
+        .map(|node| count(node))
         .reduce(Counter::add)
         .unwrap()
     } else {
@@ -110,44 +109,58 @@ fn part_two(net: Network) -> u64 {
       counter.fft = counter.count;
       counter.both = counter.fft.min(counter.dac);
     }
-    cache.insert(node, counter);
     counter
-  }
+  });
 
-  let res = count(&net, "svr", &mut HashMap::new());
+  let res = count("svr");
   res.both
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+  const DAY: u32 = 11;
+  const TITLE: &'static str = "Reactor";
+
+  type Parsed = Network;
+
+  // each part starts from a device that is only in its own sample
+  const SAMPLES: &'static [Sample] = &[
+    Sample {
+      input: SAMPLE_INPUT,
+      part1: Some("5"),
+      part2: None,
+    },
+    Sample {
+      input: SAMPLE_INPUT_TWO,
+      part1: None,
+      part2: Some("2"),
+    },
+  ];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    input.parse()
+  }
+
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
+  }
+
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
+  }
+}
+
 fn main() -> anyhow::Result<()> {
-  println!("AoC Day 11: Reactor");
-  let (network, dur) = time_try(|| INPUT.parse())?;
-  println!("Parsed input in {}", dur.display());
-  let (part_one, dur) = time(|| part_one(&network));
-  println!("Part 1: {part_one} (in {})", dur.display());
-  let (part_two, dur) = time(|| part_two(network));
-  println!("Part 2: {part_two} (in {})", dur.display());
-  Ok(())
+  run_day::<Day11>()
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
-
-  const SAMPLE_INPUT: &str = "aaa: you hhh\nyou: bbb ccc\nbbb: ddd eee\nccc: ddd eee fff\nddd: ggg\neee: out\nfff: out\nggg: out\nhhh: ccc fff iii\niii: out";
+  use aoc25::aoc_test;
 
-  #[test]
-  fn test_one() {
-    let points = SAMPLE_INPUT.parse().unwrap();
-    let total = part_one(&points);
-    assert_eq!(total, 5);
-  }
-
-  const SAMPLE_INPUT_TWO: &str = "svr: aaa bbb\naaa: fft\nfft: ccc\nbbb: tty\ntty: ccc\nccc: ddd eee\nddd: hub\nhub: fff\neee: dac\ndac: fff\nfff: ggg hhh\nggg: out\nhhh: out";
+  use super::*;
 
-  #[test]
-  fn test_two() {
-    let machine = SAMPLE_INPUT_TWO.parse().unwrap();
-    let total = part_two(machine);
-    assert_eq!(total, 2);
-  }
+  aoc_test!(Day11, part1, SAMPLE_INPUT, 5);
+  aoc_test!(Day11, part2, SAMPLE_INPUT_TWO, 2);
 }
@@ -1,16 +1,17 @@
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, iter, str::FromStr};
 
 use aoc25::{
-  exts::duration::DurationExt,
+  exact_cover::{self, ExactCover},
+  exts::string::StrExt,
   grid::Grid,
-  time::{time, time_try},
+  solution::{Sample, Solution, run_day},
 };
 
-const INPUT: &str = include_str!("data/12.txt");
+const SAMPLE_INPUT: &str = "0:\n###\n##.\n##.\n\n1:\n###\n##.\n.##\n\n2:\n.##\n###\n##.\n\n3:\n##.\n###\n##.\n\n4:\n###\n#..\n###\n\n5:\n###\n.#.\n###\n\n4x4: 0 0 0 0 2 0\n12x5: 1 0 1 0 2 2\n12x5: 1 0 1 0 3 2";
 
 #[repr(u8)]
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
-enum Shape {
+pub enum Shape {
   No,
   Yes,
 }
@@ -34,7 +35,7 @@ impl From<char> for Shape {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Presents {
+pub struct Presents {
   presents: Vec<Grid<Shape>>,
   areas: Vec<(usize, usize, Vec<usize>)>,
 }
@@ -43,7 +44,7 @@ impl FromStr for Presents {
   type Err = anyhow::Error;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let mut regions: Vec<_> = s.split("\n\n").collect();
+    let mut regions: Vec<_> = s.blocks().collect();
 
     let areas = regions.pop().unwrap();
     let areas = areas
@@ -74,133 +75,101 @@ impl FromStr for Presents {
   }
 }
 
-/*
-fn fits(grid: &Grid<Shape>, present: &Grid<Shape>, row: usize, col: usize) -> Option<Grid<Shape>> {
-  let fits = present
-    .cells()
-    .filter(|(_, _, c)| matches!(c, Shape::Yes))
-    .all(|(r, c, _)| match grid.get(row + r, col + c) {
-      Some(Shape::Yes) => false,
-      Some(Shape::No) => true,
-      None => false,
-    });
-
-  if fits {
-    let mut new = grid.clone();
-    for (r, c, _) in present.cells().filter(|(_, _, c)| matches!(c, Shape::Yes)) {
-      new.set(row + r, col + c, Shape::Yes);
+/// Whether all the presents fit in a region, by exact cover: each present must be placed once,
+/// and each cell can be used by at most one present.
+fn can_fit_all(cols: usize, rows: usize, shapes: &[&Grid<bool>]) -> bool {
+  let region = Grid::new(cols, rows, true);
+  let mut cover = ExactCover::new(shapes.len(), cols * rows);
+  for (i, shape) in shapes.iter().enumerate() {
+    for placement in exact_cover::placements(&region, shape) {
+      let columns: Vec<usize> = iter::once(i)
+        .chain(placement.iter().map(|cell| shapes.len() + cell))
+        .collect();
+      cover.add_row(&columns);
     }
-    Some(new)
-  } else {
-    None
   }
+  cover.solve().is_some()
 }
 
-fn rotations(shape: &Grid<Shape>) -> Vec<Grid<Shape>> {
-  let r0 = shape.clone();
-  let r0f = r0.flip();
-  let r1 = r0.rotate();
-  let r1f = r1.flip();
-  let r2 = r1.rotate();
-  let r2f = r2.flip();
-  let r3 = r2.rotate();
-  let r3f = r3.flip();
-  vec![r0, r0f, r1, r1f, r2, r2f, r3, r3f]
-}
-
-fn can_fit_all(grid: Grid<Shape>, shapes: &[Grid<Shape>]) -> bool {
-  if shapes.is_empty() {
-    eprintln!("all shapes fit\n{grid}");
-    return true;
-  }
-
-  for shape in rotations(&shapes[0]) {
-    /* eprintln!(
-      "trying:\n{shape}\nin\n{grid}\n{}x{}",
-      grid.rows(),
-      grid.cols()
-    ); */
-    for (row, col, _) in grid.cells() {
-      // eprintln!("trying at {row},{col}");
-      let new = fits(&grid, &shape, row, col);
-      if new.is_none() {
-        continue;
-      }
-      let new = new.unwrap();
-      // eprintln!("fit at {row},{col}:\n{new}\n---");
-      if can_fit_all(new, &shapes[1..]) {
-        return true;
-      }
-    }
-  }
-  // eprintln!("failed\n{grid}");
-  false
-}
-*/
-
 fn part_one(presents: &Presents) -> u64 {
   // 495
-
-  let shape_sizes: Vec<_> = presents
+  let shapes: Vec<Grid<bool>> = presents
     .presents
     .iter()
-    .map(|shape| {
-      shape
-        .cells()
-        .filter(|(_, _, c)| matches!(c, Shape::Yes))
-        .count()
-    })
+    .map(|shape| shape.clone().map(|c| c == Shape::Yes))
+    .collect();
+  let shape_sizes: Vec<usize> = shapes
+    .iter()
+    .map(|shape| shape.iter().filter(|c| **c).count())
     .collect();
+  let shape_width = shapes.iter().map(Grid::width).max().unwrap_or(0).max(1);
+  let shape_height = shapes.iter().map(Grid::height).max().unwrap_or(0).max(1);
 
   let mut works = 0;
   for (cols, rows, counts) in &presents.areas {
-    let max = *rows * *cols;
-    let best: usize = counts
+    let needed: usize = counts
       .iter()
       .zip(shape_sizes.iter())
       .map(|(count, size)| count * size)
       .sum();
-    if best < max {
+    if needed > cols * rows {
+      continue;
+    }
+    // if every present gets its own box there is nothing to search
+    let pieces: usize = counts.iter().sum();
+    if (cols / shape_width) * (rows / shape_height) >= pieces {
       works += 1;
+      continue;
     }
-    /*
-
-      let grid = Grid::new(*rows, *cols, Shape::No);
-
-      let shapes: Vec<Grid<Shape>> = counts
+    let pieces: Vec<&Grid<bool>> = counts
       .iter()
       .enumerate()
-      .flat_map(|(index, count)| repeat(presents.presents[index].clone()).take(*count))
+      .flat_map(|(index, count)| iter::repeat_n(&shapes[index], *count))
       .collect();
-
-    if can_fit_all(grid, shapes.as_slice()) {
+    if can_fit_all(*cols, *rows, &pieces) {
       works += 1;
     }
-    */
   }
   works
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+  const DAY: u32 = 12;
+  const TITLE: &'static str = "Christmas Tree Farm";
+
+  type Parsed = Presents;
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: Some("2"),
+    part2: None,
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    input.parse()
+  }
+
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
+  }
+
+  /// There is no second puzzle on the last day.
+  fn part2(_: &Self::Parsed) -> impl Display {
+    "Merry Christmas!"
+  }
+}
+
 fn main() -> anyhow::Result<()> {
-  println!("AoC Day 12: Christmas Tree Farm");
-  let (presents, dur) = time_try(|| INPUT.parse())?;
-  println!("Parsed input in {}", dur.display());
-  let (part_one, dur) = time(|| part_one(&presents));
-  println!("Part 1: {part_one} (in {})", dur.display());
-  println!("Part 2: Merry Christmas!");
-  Ok(())
+  run_day::<Day12>()
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use aoc25::aoc_test;
 
-  const SAMPLE_INPUT: &str = "0:\n###\n##.\n##.\n\n1:\n###\n##.\n.##\n\n2:\n.##\n###\n##.\n\n3:\n##.\n###\n##.\n\n4:\n###\n#..\n###\n\n5:\n###\n.#.\n###\n\n4x4: 0 0 0 0 2 0\n12x5: 1 0 1 0 2 2\n12x5: 1 0 1 0 3 2";
+  use super::*;
 
-  #[test]
-  fn test_one() {
-    let presents = SAMPLE_INPUT.parse().unwrap();
-    let total = part_one(&presents);
-    assert_eq!(total, 3); // This is a lie
-  }
+  aoc_test!(Day12, part1, SAMPLE_INPUT, 2);
 }
@@ -1,78 +1,355 @@
-use std::{env, fmt::Display, time::Duration};
-use yansi::Paint;
+use std::{
+  collections::HashSet,
+  fmt::Display,
+  fs,
+  io::{self, Write},
+  path::{Path, PathBuf},
+  time::{Duration, Instant, SystemTime},
+};
+
+use clap::{Parser, ValueEnum};
+use crossterm::{
+  event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+  terminal,
+};
+use yansi::{Color, Paint};
 
 use aoc25::{
   grid::Grid,
-  seq::{SILENCE, Seq},
+  seq::{Seq, clock::Clock},
 };
 
+/// Scrolling piano roll for sequence patterns.
+#[derive(Parser)]
+struct Args {
+  /// Patterns to play, one track each.
+  #[arg(allow_hyphen_values = true)]
+  patterns: Vec<String>,
+
+  /// Read patterns from a file instead, one per line. The file is reloaded when it changes.
+  #[arg(short, long, conflicts_with = "patterns")]
+  file: Option<PathBuf>,
+
+  /// Steps per minute.
+  #[arg(short, long, default_value_t = 240.0)]
+  tempo: f64,
+
+  /// Number of pitches shown, centered around note 0. Fits the terminal by default.
+  #[arg(short, long)]
+  rows: Option<usize>,
+
+  /// Number of steps of history shown. Fits the terminal by default.
+  #[arg(short, long)]
+  width: Option<usize>,
+
+  /// Direction in which the history scrolls.
+  #[arg(long, value_enum, default_value_t = Scroll::Vertical)]
+  scroll: Scroll,
+
+  /// Colors of the tracks, in order. Tracks beyond the palette are white.
+  #[arg(
+    short,
+    long,
+    value_delimiter = ',',
+    value_parser = parse_color,
+    default_value = "red,blue,green,cyan,yellow,magenta"
+  )]
+  palette: Vec<Color>,
+
+  /// Also send the notes to this MIDI output port, by name or index.
+  #[cfg(feature = "midi")]
+  #[arg(short, long)]
+  midi: Option<String>,
+}
+
+fn parse_color(name: &str) -> Result<Color, String> {
+  Ok(match name {
+    "black" => Color::Black,
+    "red" => Color::Red,
+    "green" => Color::Green,
+    "yellow" => Color::Yellow,
+    "blue" => Color::Blue,
+    "magenta" => Color::Magenta,
+    "cyan" => Color::Cyan,
+    "white" => Color::White,
+    "bright-red" => Color::BrightRed,
+    "bright-green" => Color::BrightGreen,
+    "bright-yellow" => Color::BrightYellow,
+    "bright-blue" => Color::BrightBlue,
+    "bright-magenta" => Color::BrightMagenta,
+    "bright-cyan" => Color::BrightCyan,
+    _ => return Err(format!("unknown color: {name}")),
+  })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Scroll {
+  /// Time runs to the left, pitches are rows.
+  Horizontal,
+  /// Time runs upwards, pitches are columns.
+  Vertical,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum PianoRoll {
   Silence,
-  Note(usize),
+  Note(Color),
 }
 
 impl Display for PianoRoll {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     match self {
-      PianoRoll::Note(color) => {
-        let text = match color {
-          0 => "█".red(),
-          1 => "█".blue(),
-          2 => "█".green(),
-          3 => "█".cyan(),
-          4 => "█".yellow(),
-          5 => "█".magenta(),
-          _ => "█".white(),
-        };
-        write!(f, "{}", text)
-      }
+      PianoRoll::Note(color) => write!(f, "{}", "█".fg(*color)),
       PianoRoll::Silence => write!(f, " "),
     }
   }
 }
 
+/// The scrolling history of played notes, with the newest step at the right or bottom edge.
+struct Roll {
+  grid: Grid<PianoRoll>,
+  scroll: Scroll,
+  pitches: usize,
+}
+
+impl Roll {
+  fn new(scroll: Scroll, pitches: usize, steps: usize) -> Self {
+    let (width, height) = match scroll {
+      Scroll::Horizontal => (steps, pitches),
+      Scroll::Vertical => (pitches, steps),
+    };
+    Self {
+      grid: Grid::new(width, height, PianoRoll::Silence),
+      scroll,
+      pitches,
+    }
+  }
+
+  /// Sizes the roll from the flags, filling the rest of the terminal.
+  fn fit(args: &Args) -> Self {
+    // leave a line for the status bar and one for the cursor
+    let (cols, lines) = terminal::size().map_or((60, 24), |(c, l)| (c as usize, l as usize));
+    let lines = lines.saturating_sub(2).max(1);
+    let (pitches, steps) = match args.scroll {
+      Scroll::Horizontal => (lines, cols),
+      Scroll::Vertical => (cols, lines),
+    };
+    Self::new(
+      args.scroll,
+      args.rows.unwrap_or(pitches),
+      args.width.unwrap_or(steps),
+    )
+  }
+
+  /// Scrolls by one step and draws `notes` at the new edge.
+  fn push(&mut self, notes: impl IntoIterator<Item = (i8, Color)>) {
+    let center = (self.pitches / 2) as isize;
+    let mut cells = vec![PianoRoll::Silence; self.pitches];
+    for (note, color) in notes {
+      let pitch = (note as isize + center).clamp(0, self.pitches as isize - 1) as usize;
+      cells[pitch] = PianoRoll::Note(color);
+    }
+    match self.scroll {
+      Scroll::Horizontal => {
+        for (cell, new) in self.grid.col_mut(0).zip(cells) {
+          *cell = new;
+        }
+        self.grid.rotate_cols(-1);
+      }
+      Scroll::Vertical => {
+        for (cell, new) in self.grid.row_mut(0).zip(cells) {
+          *cell = new;
+        }
+        self.grid.rotate_rows(-1);
+      }
+    }
+  }
+}
+
 fn clear() {
   print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
 }
 
-fn main() {
-  let args: Vec<String> = env::args().collect();
+/// Reads one pattern per line, skipping blank lines and `#` comments.
+fn load_patterns(path: &Path) -> anyhow::Result<Vec<Seq>> {
+  fs::read_to_string(path)?
+    .lines()
+    .map(str::trim)
+    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+    .map(Seq::try_from)
+    .collect()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+  fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn build_clock(patterns: Vec<Seq>, tempo: f64) -> Clock {
+  let mut clock = Clock::new(tempo);
+  for pattern in patterns {
+    clock.add_track(pattern, 1);
+  }
+  clock.start();
+  clock
+}
+
+/// Keeps the terminal in raw mode while alive, so single key presses can be read.
+struct RawMode;
 
-  let mut patterns: Vec<Seq> = args[1..]
+impl RawMode {
+  fn enable() -> io::Result<Self> {
+    terminal::enable_raw_mode()?;
+    Ok(Self)
+  }
+}
+
+impl Drop for RawMode {
+  fn drop(&mut self) {
+    let _ = terminal::disable_raw_mode();
+  }
+}
+
+/// Which tracks are heard and shown.
+#[derive(Default)]
+struct Mixer {
+  muted: HashSet<usize>,
+  solo: Option<usize>,
+  /// Set by `s`, the next number key picks the solo track.
+  picking_solo: bool,
+}
+
+impl Mixer {
+  fn is_audible(&self, track: usize) -> bool {
+    match self.solo {
+      Some(solo) => solo == track,
+      None => !self.muted.contains(&track),
+    }
+  }
+
+  fn press(&mut self, track: usize) {
+    if std::mem::take(&mut self.picking_solo) {
+      self.solo = if self.solo == Some(track) {
+        None
+      } else {
+        Some(track)
+      };
+    } else if !self.muted.remove(&track) {
+      self.muted.insert(track);
+    }
+  }
+}
+
+fn reseed(patterns: &[Seq]) -> Vec<Seq> {
+  patterns
     .iter()
-    .map(|p| Seq::try_from(p.as_str()).unwrap())
-    .collect();
+    .map(|p| p.clone().with_seed(rand::random()))
+    .collect()
+}
 
-  let freq = {
-    let tempo = 240f64;
-    Duration::from_secs_f64(60.0 / tempo)
+fn main() -> anyhow::Result<()> {
+  let args = Args::parse();
+  anyhow::ensure!(
+    args.rows != Some(0) && args.width != Some(0),
+    "the grid must not be empty"
+  );
+  let file = args.file.as_deref();
+
+  let mut patterns: Vec<Seq> = match file {
+    Some(path) => load_patterns(path)?,
+    None => args
+      .patterns
+      .iter()
+      .map(|p| Seq::try_from(p.as_str()))
+      .collect::<anyhow::Result<_>>()?,
   };
+  let mut last_modified = file.and_then(modified);
+  let mut clock = build_clock(patterns.clone(), args.tempo);
+  let mut mixer = Mixer::default();
+
+  let mut roll = Roll::fit(&args);
 
-  let mut grid = Grid::new(24, 60, PianoRoll::Silence);
+  #[cfg(feature = "midi")]
+  let mut midi = args
+    .midi
+    .as_deref()
+    .map(aoc25::seq::midi::MidiSender::connect)
+    .transpose()?;
 
+  let _raw = RawMode::enable()?;
   loop {
-    for x in 0..grid.height() {
-      grid[(x, 0)] = PianoRoll::Silence;
-    }
-    for (idx, note) in patterns
-      .iter_mut()
-      .map(|pat| pat.next().unwrap())
-      .enumerate()
-      .filter(|(_, x)| *x != SILENCE)
-    {
-      let note = (note.clamp(-12, 12) + 12) as usize;
-      if let Some(cell) = grid.get_mut(note, 0) {
-        *cell = PianoRoll::Note(idx)
+    // wait for key presses until the next step is due
+    let timeout = clock
+      .next_deadline()
+      .map_or(Duration::from_millis(100), |deadline| {
+        deadline.saturating_duration_since(Instant::now())
+      });
+    if event::poll(timeout)? {
+      match event::read()? {
+        Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+          KeyCode::Char('q') | KeyCode::Esc => break,
+          KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+          KeyCode::Char('s') => mixer.picking_solo = true,
+          KeyCode::Char('r') => clock = build_clock(reseed(&patterns), args.tempo),
+          KeyCode::Char(c @ '1'..='9') => mixer.press(c as usize - '1' as usize),
+          _ => {}
+        },
+        Event::Resize(..) => roll = Roll::fit(&args),
+        _ => {}
       }
+      continue;
     }
+    let Some(notes) = clock.poll() else {
+      continue;
+    };
+    let notes: Vec<_> = notes
+      .into_iter()
+      .filter(|(idx, _)| mixer.is_audible(*idx))
+      .collect();
 
-    grid.rotate_cols(-1);
+    #[cfg(feature = "midi")]
+    if let Some(midi) = &mut midi {
+      midi.play_step(&notes)?;
+    }
+
+    roll.push(notes.iter().filter_map(|&(idx, note)| {
+      let color = args.palette.get(idx).copied().unwrap_or(Color::White);
+      Some((note?, color))
+    }));
 
     clear();
-    print!("{}", grid);
-    std::thread::sleep(freq);
+    // raw mode does not turn newlines into carriage returns
+    print!("{}", roll.grid.to_string().replace('\n', "\r\n"));
+    for idx in 0..patterns.len() {
+      let color = args.palette.get(idx).copied().unwrap_or(Color::White);
+      let label = format!("{} ", idx + 1);
+      match mixer.is_audible(idx) {
+        true => print!("{}", label.fg(color)),
+        false => print!("{}", label.dim()),
+      }
+    }
+    let solo = if mixer.picking_solo {
+      "[1-9] solo"
+    } else {
+      "[s] solo"
+    };
+    print!("  [1-9] mute  {solo}  [r] reseed  [q] quit\r\n");
+    io::stdout().flush()?;
+
+    if let Some(path) = file
+      && modified(path) != last_modified
+    {
+      last_modified = modified(path);
+      // keep playing the old patterns if the new ones do not parse
+      match load_patterns(path) {
+        Ok(new) => {
+          patterns = new;
+          clock = build_clock(patterns.clone(), args.tempo);
+        }
+        Err(e) => print!("{}\r\n", e.red()),
+      }
+    }
   }
+  Ok(())
 }
 
 // "\033[2J"
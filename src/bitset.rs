@@ -0,0 +1,162 @@
+const WORD: usize = u64::BITS as usize;
+
+/// The word holding bit `idx`, and the mask of the bit within it.
+pub(crate) fn locate(idx: usize) -> (usize, u64) {
+  (idx / WORD, 1 << (idx % WORD))
+}
+
+/// A fixed number of bits, packed into words.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitSet {
+  words: Box<[u64]>,
+  len: usize,
+}
+
+impl BitSet {
+  /// Creates `len` unset bits.
+  pub fn new(len: usize) -> Self {
+    Self::from_words(vec![0; len.div_ceil(WORD)], len)
+  }
+
+  pub(crate) fn from_words(words: impl Into<Box<[u64]>>, len: usize) -> Self {
+    let words = words.into();
+    assert_eq!(words.len(), len.div_ceil(WORD), "wrong number of words");
+    Self { words, len }
+  }
+
+  /// The number of words needed to hold `len` bits.
+  pub(crate) fn words_for(len: usize) -> usize {
+    len.div_ceil(WORD)
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// True if no bits are set.
+  pub fn is_clear(&self) -> bool {
+    self.words.iter().all(|word| *word == 0)
+  }
+
+  /// Panics if `idx` is out of bounds.
+  pub fn get(&self, idx: usize) -> bool {
+    assert!(idx < self.len, "index out of bounds: {idx}");
+    let (word, mask) = locate(idx);
+    self.words[word] & mask != 0
+  }
+
+  /// Panics if `idx` is out of bounds.
+  pub fn set(&mut self, idx: usize, value: bool) {
+    assert!(idx < self.len, "index out of bounds: {idx}");
+    let (word, mask) = locate(idx);
+    if value {
+      self.words[word] |= mask;
+    } else {
+      self.words[word] &= !mask;
+    }
+  }
+
+  /// Sets bit `idx`, and returns true if it was not already set.
+  pub fn insert(&mut self, idx: usize) -> bool {
+    let was_set = self.get(idx);
+    self.set(idx, true);
+    !was_set
+  }
+
+  /// Unsets all bits.
+  pub fn clear(&mut self) {
+    self.words.fill(0);
+  }
+
+  pub fn count_ones(&self) -> usize {
+    self
+      .words
+      .iter()
+      .map(|word| word.count_ones() as usize)
+      .sum()
+  }
+
+  /// Sets every bit that is set in `other`. Panics if the lengths differ.
+  pub fn union(&mut self, other: &BitSet) {
+    assert_eq!(self.len, other.len, "lengths differ");
+    for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+      *word |= other;
+    }
+  }
+
+  /// Flips every bit that is set in `other`, like xor. Panics if the lengths differ.
+  pub fn symmetric_difference(&mut self, other: &BitSet) {
+    assert_eq!(self.len, other.len, "lengths differ");
+    for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+      *word ^= other;
+    }
+  }
+
+  /// The indices of all set bits, in increasing order.
+  pub fn iter_ones(&self) -> impl Iterator<Item = usize> {
+    self.words.iter().enumerate().flat_map(|(i, word)| {
+      let mut word = *word;
+      std::iter::from_fn(move || {
+        if word == 0 {
+          return None;
+        }
+        let bit = word.trailing_zeros() as usize;
+        // clear the lowest set bit
+        word &= word - 1;
+        Some(i * WORD + bit)
+      })
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_set_get() {
+    let mut bits = BitSet::new(100);
+    assert_eq!(bits.len(), 100);
+    assert!(bits.is_clear());
+
+    bits.set(3, true);
+    bits.set(64, true);
+    bits.set(99, true);
+    assert!(bits.get(3) && bits.get(64) && bits.get(99));
+    assert!(!bits.get(4));
+    assert_eq!(bits.count_ones(), 3);
+
+    bits.set(64, false);
+    assert!(!bits.get(64));
+    assert!(bits.insert(5));
+    assert!(!bits.insert(5));
+
+    bits.clear();
+    assert!(bits.is_clear());
+    assert_eq!(bits.len(), 100);
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_out_of_bounds() {
+    BitSet::new(10).get(10);
+  }
+
+  #[test]
+  fn test_union_iter() {
+    let mut a = BitSet::new(130);
+    let mut b = BitSet::new(130);
+    a.set(0, true);
+    a.set(70, true);
+    b.set(70, true);
+    b.set(129, true);
+    a.union(&b);
+    assert_eq!(a.iter_ones().collect::<Vec<_>>(), [0, 70, 129]);
+    a.symmetric_difference(&b);
+    assert_eq!(a.iter_ones().collect::<Vec<_>>(), [0]);
+  }
+}
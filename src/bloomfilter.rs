@@ -2,32 +2,54 @@ use std::{
   borrow::Borrow,
   hash::{DefaultHasher, Hash, Hasher},
   marker::PhantomData,
+  sync::atomic::{AtomicU64, Ordering},
 };
 
-use num_traits::Euclid;
+use crate::bitset::{self, BitSet};
 
 pub struct BloomFilter<T> {
-  bits: Box<[u64]>,
+  bits: BitSet,
   hashes: usize,
   _marker: PhantomData<T>,
 }
 
-const BITS: usize = std::mem::size_of::<u64>();
-
-fn hash<T: Hash>(item: &T, offset: usize) -> usize {
+fn hash<T: Hash>(item: &T, seed: u64) -> usize {
   let mut s = DefaultHasher::new();
-  (0x1 << offset).hash(&mut s);
+  seed.hash(&mut s);
   item.hash(&mut s);
   s.finish() as usize
 }
 
+/// The bits of `item`, using double hashing so only two real hashes are computed.
+/// https://en.wikipedia.org/wiki/Double_hashing
+fn indices<T: Hash>(item: &T, hashes: usize, num_bits: usize) -> impl Iterator<Item = usize> {
+  let first = hash(item, 0) % num_bits;
+  // the step is taken modulo the number of bits, and is never 0 so it never stays on one bit
+  let step = 1 + hash(item, 1) % (num_bits - 1).max(1);
+  (0..hashes).scan(first, move |idx, _| {
+    let current = *idx;
+    *idx = (*idx + step) % num_bits;
+    Some(current)
+  })
+}
+
+/// The number of bits and hashes giving `false_positive_rate` after inserting `expected_items`.
+fn optimal_size(expected_items: usize, false_positive_rate: f64) -> (usize, usize) {
+  // https://en.wikipedia.org/wiki/Bloom_filter#Optimal_number_of_hash_functions
+  let hashes = -(false_positive_rate.ln()) / (2.0f64).ln();
+  let bits = expected_items as f64 * -2.08 * false_positive_rate.ln();
+  let hashes = (hashes as usize).max(1);
+  // round up to use the whole last word
+  let bits = (bits as usize).next_multiple_of(u64::BITS as usize);
+  (bits, hashes)
+}
+
 impl<T: Hash> BloomFilter<T> {
   pub fn new(bits: usize, hashes: usize) -> Self {
     assert!(hashes > 0, "must use at least 1 hash");
-    assert!(hashes <= BITS, "too many hashes");
     Self {
       // changing the length of bits would invalidate the entire bloom filter
-      bits: vec![0u64; bits.div_ceil(BITS)].into_boxed_slice(),
+      bits: BitSet::new(bits),
       hashes,
       _marker: PhantomData,
     }
@@ -38,51 +60,68 @@ impl<T: Hash> BloomFilter<T> {
   }
 
   pub fn num_bits(&self) -> usize {
-    self.bits.len() * BITS
+    self.bits.len()
   }
 
   pub fn num_set_bits(&self) -> usize {
-    self
-      .bits
-      .iter()
-      .map(|word| word.count_ones() as usize)
-      .sum()
+    self.bits.count_ones()
+  }
+
+  /// True if nothing has been inserted since the filter was created or cleared.
+  pub fn is_empty(&self) -> bool {
+    self.bits.is_clear()
+  }
+
+  /// Removes all items, keeping the allocated bits for reuse.
+  pub fn clear(&mut self) {
+    self.bits.clear();
   }
 
   pub fn insert(&mut self, item: impl Borrow<T>) {
-    let item = item.borrow();
-    let num_bits = self.num_bits();
-    (0..self.hashes)
-      .map(|x| hash(item, x))
-      .map(|x| x % num_bits)
-      .for_each(|idx| {
-        // eprintln!("{}", idx);
-        let (word, bit) = idx.div_rem_euclid(&BITS);
-        self.bits[word] |= 0x1 << bit;
-      })
+    for idx in indices(item.borrow(), self.hashes, self.num_bits()) {
+      self.bits.set(idx, true);
+    }
   }
 
   pub fn has(&self, item: impl Borrow<T>) -> bool {
-    let item = item.borrow();
-    let num_bits = self.num_bits();
-    (0..self.hashes)
-      .map(|x| hash(item, x))
-      .map(|x| x % num_bits)
-      .all(|idx| {
-        let (word, bit) = idx.div_rem_euclid(&BITS);
-        self.bits[word] & (0x1 << bit) != 0
-      })
+    indices(item.borrow(), self.hashes, self.num_bits()).all(|idx| self.bits.get(idx))
+  }
+
+  /// None if `item` is definitely not in the filter,
+  /// otherwise the chance that it is a false positive.
+  pub fn has_with_confidence(&self, item: impl Borrow<T>) -> Option<f64> {
+    self.has(item).then(|| self.false_positive_chance())
+  }
+
+  /// Whether bit `idx` is set. Panics if `idx` is out of bounds.
+  pub fn bit_at(&self, idx: usize) -> bool {
+    self.bits.get(idx)
+  }
+
+  /// The indices of all set bits, in increasing order.
+  pub fn set_bits(&self) -> impl Iterator<Item = usize> {
+    self.bits.iter_ones()
+  }
+
+  pub fn bits(&self) -> &BitSet {
+    &self.bits
   }
 
   pub fn optimal(expected_items: usize, false_positive_rate: f64) -> Self {
-    // https://en.wikipedia.org/wiki/Bloom_filter#Optimal_number_of_hash_functions
-    let hashes = -(false_positive_rate.ln()) / (2.0f64).ln();
-    let bits = expected_items as f64 * -2.08 * false_positive_rate.ln();
-    let hashes = (hashes as usize).clamp(0, BITS);
-    let bits = (bits as usize).next_multiple_of(BITS);
+    let (bits, hashes) = optimal_size(expected_items, false_positive_rate);
     Self::new(bits, hashes)
   }
 
+  /// Builds an optimally sized filter holding every item of `iter`.
+  /// The size is taken from the size hint of `iter`, so it should be exact or have an upper bound.
+  pub fn from_iter_optimal(iter: impl IntoIterator<Item = T>, false_positive_rate: f64) -> Self {
+    let iter = iter.into_iter();
+    let (lower, upper) = iter.size_hint();
+    let mut bf = Self::optimal(upper.unwrap_or(lower).max(1), false_positive_rate);
+    bf.extend(iter);
+    bf
+  }
+
   pub fn approx_items(&self) -> usize {
     // https://en.wikipedia.org/wiki/Bloom_filter#Approximating_the_number_of_items_in_a_Bloom_filter
     let k = self.hashes as f64;
@@ -103,8 +142,99 @@ impl<T: Hash> BloomFilter<T> {
   }
 }
 
+/// A Bloom filter that can be shared between threads, e.g. as a visited set for rayon workers.
+/// Inserting only ever sets bits, so relaxed atomics are enough and no lock is needed.
+pub struct AtomicBloomFilter<T> {
+  words: Box<[AtomicU64]>,
+  num_bits: usize,
+  hashes: usize,
+  _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash> AtomicBloomFilter<T> {
+  pub fn new(bits: usize, hashes: usize) -> Self {
+    assert!(hashes > 0, "must use at least 1 hash");
+    Self {
+      words: (0..BitSet::words_for(bits))
+        .map(|_| AtomicU64::new(0))
+        .collect(),
+      num_bits: bits,
+      hashes,
+      _marker: PhantomData,
+    }
+  }
+
+  pub fn optimal(expected_items: usize, false_positive_rate: f64) -> Self {
+    let (bits, hashes) = optimal_size(expected_items, false_positive_rate);
+    Self::new(bits, hashes)
+  }
+
+  pub fn num_hashes(&self) -> usize {
+    self.hashes
+  }
+
+  pub fn num_bits(&self) -> usize {
+    self.num_bits
+  }
+
+  pub fn num_set_bits(&self) -> usize {
+    self
+      .words
+      .iter()
+      .map(|word| word.load(Ordering::Relaxed).count_ones() as usize)
+      .sum()
+  }
+
+  pub fn insert(&self, item: impl Borrow<T>) {
+    for idx in indices(item.borrow(), self.hashes, self.num_bits()) {
+      let (word, mask) = bitset::locate(idx);
+      self.words[word].fetch_or(mask, Ordering::Relaxed);
+    }
+  }
+
+  pub fn has(&self, item: impl Borrow<T>) -> bool {
+    indices(item.borrow(), self.hashes, self.num_bits()).all(|idx| {
+      let (word, mask) = bitset::locate(idx);
+      self.words[word].load(Ordering::Relaxed) & mask != 0
+    })
+  }
+
+  /// Converts into a regular filter once all threads are done inserting.
+  pub fn into_inner(self) -> BloomFilter<T> {
+    BloomFilter {
+      bits: BitSet::from_words(
+        self
+          .words
+          .into_iter()
+          .map(AtomicU64::into_inner)
+          .collect::<Vec<_>>(),
+        self.num_bits,
+      ),
+      hashes: self.hashes,
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<T: Hash> Extend<T> for BloomFilter<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for item in iter {
+      self.insert(item);
+    }
+  }
+}
+
+/// Collects into an optimally sized filter with a 1% false positive rate.
+impl<T: Hash> FromIterator<T> for BloomFilter<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self::from_iter_optimal(iter, 0.01)
+  }
+}
+
 #[cfg(test)]
 mod tests {
+  use itertools::Itertools;
+
   use super::*;
 
   #[test]
@@ -122,6 +252,71 @@ mod tests {
     assert!(bf.has(234823));
   }
 
+  #[test]
+  fn test_indices_distinct() {
+    // with a prime number of bits, any nonzero step visits every bit before repeating
+    for item in 0..1000 {
+      let idx: Vec<_> = indices(&item, 5, 97).collect();
+      assert!(idx.iter().all_unique(), "{item}: {idx:?}");
+    }
+  }
+
+  #[test]
+  fn test_clear() {
+    let mut bf: BloomFilter<u32> = BloomFilter::new(1024, 3);
+    assert!(bf.is_empty());
+
+    bf.insert(404);
+    assert!(!bf.is_empty());
+
+    bf.clear();
+    assert!(bf.is_empty());
+    assert!(!bf.has(404));
+    assert_eq!(bf.num_bits(), 1024);
+  }
+
+  #[test]
+  fn test_from_iter() {
+    let bf = BloomFilter::from_iter_optimal(0..1000u32, 0.01);
+    assert_eq!(
+      bf.num_bits(),
+      BloomFilter::<u32>::optimal(1000, 0.01).num_bits()
+    );
+    assert!((0..1000).all(|i| bf.has(i)));
+
+    let mut bf: BloomFilter<u32> = (0..10).collect();
+    bf.extend(10..20);
+    assert!((0..20).all(|i| bf.has(i)));
+  }
+
+  #[test]
+  fn test_introspection() {
+    let mut bf: BloomFilter<u32> = BloomFilter::new(256, 2);
+    assert_eq!(bf.has_with_confidence(7), None);
+    assert_eq!(bf.set_bits().count(), 0);
+
+    bf.insert(7);
+    let set: Vec<_> = bf.set_bits().collect();
+    assert!(!set.is_empty() && set.len() <= 2);
+    assert_eq!(set.len(), bf.num_set_bits());
+    assert!(set.iter().all(|idx| bf.bit_at(*idx)));
+    assert_eq!(bf.has_with_confidence(7), Some(bf.false_positive_chance()));
+  }
+
+  #[test]
+  fn test_atomic() {
+    use rayon::prelude::*;
+
+    let bf: AtomicBloomFilter<u32> = AtomicBloomFilter::optimal(1000, 0.01);
+    (0..1000).into_par_iter().for_each(|i| bf.insert(i));
+    assert!((0..1000).all(|i| bf.has(i)));
+
+    let set_bits = bf.num_set_bits();
+    let bf = bf.into_inner();
+    assert_eq!(bf.num_set_bits(), set_bits);
+    assert!((0..1000).all(|i| bf.has(i)));
+  }
+
   #[test]
   fn test_approx_items() {
     let num = 100;
@@ -152,7 +347,7 @@ mod tests {
     };
 
     let fpc_percent = (bf.false_positive_chance() * 100.0).round();
-    assert_eq!(fpc_percent, 87.0);
+    assert_eq!(fpc_percent, 84.0);
 
     let num = 10;
     let bf = {
@@ -170,15 +365,24 @@ mod tests {
   #[test]
   fn test_optimal() {
     let bf = BloomFilter::<u32>::optimal(10000, 0.1);
-    assert_eq!(bf.num_bits(), 47896);
+    assert_eq!(bf.num_bits(), 47936);
     assert_eq!(bf.num_hashes(), 3);
 
     let bf = BloomFilter::<u32>::optimal(1000, 0.01);
-    assert_eq!(bf.num_bits(), 9584);
+    assert_eq!(bf.num_bits(), 9600);
     assert_eq!(bf.num_hashes(), 6);
 
     let bf = BloomFilter::<u32>::optimal(20, 0.001);
-    assert_eq!(bf.num_bits(), 288);
-    assert_eq!(bf.num_hashes(), 8);
+    assert_eq!(bf.num_bits(), 320);
+    assert_eq!(bf.num_hashes(), 9);
+  }
+
+  #[test]
+  fn test_many_hashes() {
+    let mut bf: BloomFilter<u32> = BloomFilter::new(4096, 100);
+    bf.insert(404);
+    assert!(bf.has(404));
+    assert!(bf.num_set_bits() > 64);
+    assert!(!bf.has(405));
   }
 }
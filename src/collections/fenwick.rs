@@ -0,0 +1,168 @@
+use std::ops::{Bound, RangeBounds, Sub};
+
+use num_traits::Zero;
+
+/// A binary indexed tree: prefix sums in O(log n), while allowing values to be changed.
+#[derive(Clone, Debug)]
+pub struct Fenwick<T> {
+  /// 1-based, where node `i` holds the sum of the `i & -i` values ending at `i`.
+  tree: Vec<T>,
+}
+
+impl<T: Copy + Zero + Sub<Output = T> + PartialOrd> Fenwick<T> {
+  /// A tree of `len` zeros.
+  pub fn new(len: usize) -> Self {
+    Self {
+      tree: vec![T::zero(); len + 1],
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.tree.len() - 1
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Adds `delta` to the value at `index`. Panics if it is out of bounds.
+  pub fn add(&mut self, index: usize, delta: T) {
+    assert!(index < self.len(), "index out of bounds: {index}");
+    let mut i = index + 1;
+    while i < self.tree.len() {
+      self.tree[i] = self.tree[i] + delta;
+      i += i & i.wrapping_neg();
+    }
+  }
+
+  /// The sum of the first `end` values. Panics if `end` is past the end.
+  pub fn prefix_sum(&self, end: usize) -> T {
+    assert!(end <= self.len(), "index out of bounds: {end}");
+    let mut sum = T::zero();
+    let mut i = end;
+    while i > 0 {
+      sum = sum + self.tree[i];
+      i -= i & i.wrapping_neg();
+    }
+    sum
+  }
+
+  /// The sum of the values in `range`.
+  pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+    let start = match range.start_bound() {
+      Bound::Included(&s) => s,
+      Bound::Excluded(&s) => s + 1,
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(&e) => e + 1,
+      Bound::Excluded(&e) => e,
+      Bound::Unbounded => self.len(),
+    };
+    if start >= end {
+      return T::zero();
+    }
+    self.prefix_sum(end) - self.prefix_sum(start)
+  }
+
+  pub fn get(&self, index: usize) -> T {
+    self.sum(index..=index)
+  }
+
+  /// Replaces the value at `index`.
+  pub fn set(&mut self, index: usize, value: T) {
+    let old = self.get(index);
+    let mut i = index + 1;
+    while i < self.tree.len() {
+      // every node covering `index` includes `old`, so this cannot underflow
+      self.tree[i] = self.tree[i] - old + value;
+      i += i & i.wrapping_neg();
+    }
+  }
+
+  /// The index of the `k`th item (from 0), treating the values as counts of each index,
+  /// or `None` if there are not more than `k` items. Only valid when no value is negative.
+  pub fn find_kth(&self, k: T) -> Option<usize> {
+    let mut pos = 0;
+    let mut remaining = k;
+    let mut step = self.len().checked_next_power_of_two()?;
+    while step > 0 {
+      let next = pos + step;
+      if next <= self.len() && self.tree[next] <= remaining {
+        pos = next;
+        remaining = remaining - self.tree[next];
+      }
+      step /= 2;
+    }
+    (pos < self.len()).then_some(pos)
+  }
+}
+
+impl<T: Copy + Zero + Sub<Output = T> + PartialOrd> FromIterator<T> for Fenwick<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut tree = vec![T::zero()];
+    tree.extend(iter);
+    // push each node's sum up to its parent, building the tree in O(n)
+    for i in 1..tree.len() {
+      let parent = i + (i & i.wrapping_neg());
+      if parent < tree.len() {
+        tree[parent] = tree[parent] + tree[i];
+      }
+    }
+    Self { tree }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_sums() {
+    let values = [3, -1, 4, 1, -5, 9, 2, 6];
+    let mut fenwick: Fenwick<i64> = values.into_iter().collect();
+    assert_eq!(fenwick.len(), 8);
+    for end in 0..=values.len() {
+      assert_eq!(fenwick.prefix_sum(end), values[..end].iter().sum::<i64>());
+    }
+    assert_eq!(fenwick.sum(2..5), 0);
+    assert_eq!(fenwick.sum(5..), 17);
+    assert_eq!(fenwick.get(4), -5);
+
+    fenwick.add(4, 10);
+    fenwick.set(0, 0);
+    assert_eq!(fenwick.get(4), 5);
+    assert_eq!(fenwick.sum(..), 26);
+  }
+
+  #[test]
+  fn test_inversions() {
+    let permutation = [3, 1, 4, 0, 2];
+    let mut seen = Fenwick::new(permutation.len());
+    let mut inversions = 0;
+    for &p in &permutation {
+      // count the larger values already seen
+      inversions += seen.sum(p + 1..);
+      seen.add(p, 1);
+    }
+    assert_eq!(inversions, 6);
+  }
+
+  #[test]
+  fn test_find_kth() {
+    // a multiset of 0, 2, 2, 5
+    let mut counts = Fenwick::new(7);
+    for i in [5, 2, 0, 2] {
+      counts.add(i, 1u32);
+    }
+    assert_eq!(counts.find_kth(0), Some(0));
+    assert_eq!(counts.find_kth(1), Some(2));
+    assert_eq!(counts.find_kth(2), Some(2));
+    assert_eq!(counts.find_kth(3), Some(5));
+    assert_eq!(counts.find_kth(4), None);
+
+    counts.set(2, 0);
+    assert_eq!(counts.find_kth(1), Some(5));
+    assert_eq!(Fenwick::<u32>::new(0).find_kth(0), None);
+  }
+}
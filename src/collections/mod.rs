@@ -0,0 +1,3 @@
+pub mod fenwick;
+
+pub use fenwick::Fenwick;
@@ -0,0 +1,210 @@
+use std::{
+  collections::HashMap,
+  hash::Hash,
+  ops::{Add, AddAssign, Mul, Sub, SubAssign},
+};
+
+/// A multiset, counting how many of each item there are.
+/// Items whose count drops to 0 are removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counter<T: Hash + Eq> {
+  counts: HashMap<T, u64>,
+}
+
+impl<T: Hash + Eq> Default for Counter<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Hash + Eq> Counter<T> {
+  pub fn new() -> Self {
+    Self {
+      counts: HashMap::new(),
+    }
+  }
+
+  pub fn add(&mut self, item: T) {
+    self.add_n(item, 1);
+  }
+
+  pub fn add_n(&mut self, item: T, n: u64) {
+    if n > 0 {
+      *self.counts.entry(item).or_default() += n;
+    }
+  }
+
+  /// Removes one of `item`, returning false if there was none.
+  pub fn remove(&mut self, item: &T) -> bool {
+    self.remove_n(item, 1) == 1
+  }
+
+  /// Removes up to `n` of `item`, returning how many were removed.
+  pub fn remove_n(&mut self, item: &T, n: u64) -> u64 {
+    let Some(count) = self.counts.get_mut(item) else {
+      return 0;
+    };
+    let removed = n.min(*count);
+    *count -= removed;
+    if *count == 0 {
+      self.counts.remove(item);
+    }
+    removed
+  }
+
+  pub fn get(&self, item: &T) -> u64 {
+    self.counts.get(item).copied().unwrap_or(0)
+  }
+
+  /// The number of different items.
+  pub fn len(&self) -> usize {
+    self.counts.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.counts.is_empty()
+  }
+
+  /// The number of items, counting every copy.
+  pub fn total(&self) -> u64 {
+    self.counts.values().sum()
+  }
+
+  /// Each different item and its count, in no particular order.
+  pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> {
+    self.counts.iter().map(|(item, count)| (item, *count))
+  }
+
+  /// The `n` items with the highest counts, highest first. Ties are in no particular order.
+  pub fn most_common(&self, n: usize) -> Vec<(&T, u64)> {
+    let mut counts: Vec<_> = self.iter().collect();
+    counts.sort_unstable_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts.truncate(n);
+    counts
+  }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut counter = Self::new();
+    counter.extend(iter);
+    counter
+  }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for item in iter {
+      self.add(item);
+    }
+  }
+}
+
+impl<T: Hash + Eq> IntoIterator for Counter<T> {
+  type Item = (T, u64);
+  type IntoIter = std::collections::hash_map::IntoIter<T, u64>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.counts.into_iter()
+  }
+}
+
+impl<T: Hash + Eq> AddAssign for Counter<T> {
+  fn add_assign(&mut self, rhs: Self) {
+    for (item, count) in rhs {
+      self.add_n(item, count);
+    }
+  }
+}
+
+impl<T: Hash + Eq> Add for Counter<T> {
+  type Output = Self;
+
+  fn add(mut self, rhs: Self) -> Self::Output {
+    self += rhs;
+    self
+  }
+}
+
+/// Removes the counts of `rhs`, stopping at 0.
+impl<T: Hash + Eq> SubAssign for Counter<T> {
+  fn sub_assign(&mut self, rhs: Self) {
+    for (item, count) in rhs {
+      self.remove_n(&item, count);
+    }
+  }
+}
+
+impl<T: Hash + Eq> Sub for Counter<T> {
+  type Output = Self;
+
+  fn sub(mut self, rhs: Self) -> Self::Output {
+    self -= rhs;
+    self
+  }
+}
+
+/// Multiplies every count.
+impl<T: Hash + Eq> Mul<u64> for Counter<T> {
+  type Output = Self;
+
+  fn mul(mut self, rhs: u64) -> Self::Output {
+    if rhs == 0 {
+      self.counts.clear();
+    }
+    self.counts.values_mut().for_each(|count| *count *= rhs);
+    self
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_counts() {
+    let mut counter: Counter<char> = "abracadabra".chars().collect();
+    assert_eq!(counter.get(&'a'), 5);
+    assert_eq!(counter.get(&'z'), 0);
+    assert_eq!(counter.len(), 5);
+    assert_eq!(counter.total(), 11);
+    let common = counter.most_common(2);
+    assert_eq!(common[0], (&'a', 5));
+    // b and r are tied
+    assert_eq!(common[1].1, 2);
+
+    assert!(counter.remove(&'c'));
+    assert!(!counter.remove(&'c'));
+    assert_eq!(counter.len(), 4);
+    assert_eq!(counter.remove_n(&'a', 10), 5);
+    assert_eq!(counter.total(), 5);
+  }
+
+  #[test]
+  fn test_arithmetic() {
+    // lanternfish: each fish at 0 makes a new one at 8 and goes back to 6
+    let fish: Counter<u8> = [3, 4, 3, 1, 2].into_iter().collect();
+    let mut day = fish.clone();
+    for _ in 0..18 {
+      let mut next = Counter::new();
+      for (timer, count) in day {
+        if timer == 0 {
+          next.add_n(6, count);
+          next.add_n(8, count);
+        } else {
+          next.add_n(timer - 1, count);
+        }
+      }
+      day = next;
+    }
+    assert_eq!(day.total(), 26);
+
+    let doubled = fish.clone() + fish.clone();
+    assert_eq!(doubled, fish.clone() * 2);
+    assert_eq!(doubled.get(&3), 4);
+    let less = fish - [3, 3, 3, 1].into_iter().collect();
+    assert_eq!(less.get(&3), 0);
+    assert_eq!(less.get(&1), 0);
+    assert_eq!(less.total(), 2);
+  }
+}
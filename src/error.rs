@@ -0,0 +1,61 @@
+use std::fmt::Display;
+
+/// Why a day failed, for the parsing helpers and the runner.
+/// Solutions return `anyhow::Result`, which can carry these through `?`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AocError {
+  /// A line of the input could not be parsed. Lines are counted from 1.
+  Parse { line: usize, reason: String },
+  /// The input as a whole cannot be used, e.g. it is empty or malformed.
+  BadInput(String),
+  /// There is no solution for the day.
+  NoSolution { day: u32 },
+}
+
+impl AocError {
+  pub fn parse(line: usize, reason: impl Display) -> Self {
+    Self::Parse {
+      line,
+      reason: reason.to_string(),
+    }
+  }
+
+  pub fn bad_input(reason: impl Display) -> Self {
+    Self::BadInput(reason.to_string())
+  }
+}
+
+impl Display for AocError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      AocError::Parse { line, reason } => write!(f, "line {line}: {reason}"),
+      AocError::BadInput(reason) => write!(f, "bad input: {reason}"),
+      AocError::NoSolution { day } => write!(f, "day {day} has no solution"),
+    }
+  }
+}
+
+impl std::error::Error for AocError {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_display() {
+    assert_eq!(
+      AocError::parse(3, "invalid digit").to_string(),
+      "line 3: invalid digit"
+    );
+    assert_eq!(
+      AocError::bad_input("no rows").to_string(),
+      "bad input: no rows"
+    );
+
+    let error: anyhow::Error = AocError::NoSolution { day: 13 }.into();
+    assert_eq!(
+      error.downcast_ref::<AocError>(),
+      Some(&AocError::NoSolution { day: 13 })
+    );
+  }
+}
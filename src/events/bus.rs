@@ -0,0 +1,86 @@
+use std::sync::mpsc::Sender;
+
+enum Subscriber<E> {
+  Callback(Box<dyn FnMut(&E)>),
+  Channel(Sender<E>),
+}
+
+/// Notifies subscribers of every published event, so side effects can hook in without touching the state.
+pub struct EventBus<E> {
+  subscribers: Vec<Subscriber<E>>,
+}
+
+impl<E> EventBus<E> {
+  pub fn new() -> Self {
+    Self {
+      subscribers: vec![],
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.subscribers.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.subscribers.is_empty()
+  }
+
+  pub fn subscribe(&mut self, callback: impl FnMut(&E) + 'static) {
+    self
+      .subscribers
+      .push(Subscriber::Callback(Box::new(callback)));
+  }
+
+  /// Sends a copy of every event to `sender`, until its receiver is dropped.
+  pub fn subscribe_channel(&mut self, sender: Sender<E>) {
+    self.subscribers.push(Subscriber::Channel(sender));
+  }
+}
+
+impl<E: Clone> EventBus<E> {
+  /// Notifies all subscribers in the order they subscribed.
+  pub fn publish(&mut self, event: &E) {
+    self.subscribers.retain_mut(|subscriber| match subscriber {
+      Subscriber::Callback(callback) => {
+        callback(event);
+        true
+      }
+      // unsubscribe channels whose receiver is gone
+      Subscriber::Channel(sender) => sender.send(event.clone()).is_ok(),
+    });
+  }
+}
+
+impl<E> Default for EventBus<E> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, rc::Rc, sync::mpsc};
+
+  use super::*;
+
+  #[test]
+  fn test_publish() {
+    let seen = Rc::new(RefCell::new(vec![]));
+    let mut bus = EventBus::new();
+    {
+      let seen = seen.clone();
+      bus.subscribe(move |e: &u32| seen.borrow_mut().push(*e));
+    }
+    let (tx, rx) = mpsc::channel();
+    bus.subscribe_channel(tx);
+
+    bus.publish(&1);
+    bus.publish(&2);
+    assert_eq!(*seen.borrow(), [1, 2]);
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), [1, 2]);
+
+    drop(rx);
+    bus.publish(&3);
+    assert_eq!(bus.len(), 1);
+  }
+}
@@ -0,0 +1,85 @@
+use super::FromEvent;
+
+/// Folds a history of events lazily, remembering the state every `interval` events,
+/// so that once a checkpoint has been reached `state_at` applies fewer than `interval` events.
+///
+/// Unlike `EventLog`, events are not validated up front; errors surface when a state is first folded.
+pub struct CachedState<S: FromEvent> {
+  events: Vec<S::Event>,
+  interval: usize,
+  /// `checkpoints[i]` is the state after `i * interval` events, filled in as they are reached.
+  checkpoints: Vec<S>,
+}
+
+impl<S> CachedState<S>
+where
+  S: FromEvent + Clone + Default,
+  S::Event: Clone,
+{
+  pub fn new(events: impl IntoIterator<Item = S::Event>, interval: usize) -> Self {
+    assert!(interval > 0, "interval cannot be 0");
+    Self {
+      events: events.into_iter().collect(),
+      interval,
+      checkpoints: vec![S::default()],
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.events.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.events.is_empty()
+  }
+
+  pub fn push(&mut self, event: S::Event) {
+    self.events.push(event);
+  }
+
+  /// The number of checkpoints computed so far, including the initial state.
+  pub fn checkpoints(&self) -> usize {
+    self.checkpoints.len()
+  }
+
+  /// The state after the first `k` events. Panics if there are fewer than `k` events.
+  pub fn state_at(&mut self, k: usize) -> Result<S, S::Error> {
+    assert!(k <= self.len(), "only {} events", self.len());
+    let target = k / self.interval;
+    while self.checkpoints.len() <= target {
+      let start = (self.checkpoints.len() - 1) * self.interval;
+      let next = self.fold(self.checkpoints.len() - 1, start + self.interval)?;
+      self.checkpoints.push(next);
+    }
+    self.fold(target, k)
+  }
+
+  /// Folds the events from checkpoint `checkpoint` up to event `end`.
+  fn fold(&self, checkpoint: usize, end: usize) -> Result<S, S::Error> {
+    let events = &self.events[checkpoint * self.interval..end];
+    self.checkpoints[checkpoint]
+      .clone()
+      .add_events(events.iter().cloned())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::events::fixtures::Balance;
+
+  use super::*;
+
+  #[test]
+  fn test_state_at() {
+    let mut cache: CachedState<Balance> = CachedState::new(1..=10, 4);
+    assert_eq!(cache.state_at(10).unwrap(), Balance(55));
+    assert_eq!(cache.checkpoints(), 3);
+    assert_eq!(cache.state_at(5).unwrap(), Balance(15));
+    assert_eq!(cache.state_at(0).unwrap(), Balance(0));
+    assert_eq!(cache.checkpoints(), 3);
+
+    cache.push(-100);
+    assert!(cache.state_at(11).is_err());
+    assert_eq!(cache.state_at(8).unwrap(), Balance(36));
+  }
+}
@@ -0,0 +1,35 @@
+//! A state shared by the tests of the event modules.
+
+use super::{ApplyEvent, projection::Projection};
+
+/// An account balance, changed by signed amounts. Events that would make it negative,
+/// or overflow it, fail and leave it unchanged.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Balance(pub u32);
+
+impl ApplyEvent for Balance {
+  type Event = i32;
+  type Error = anyhow::Error;
+
+  fn apply(&mut self, event: Self::Event) -> Result<(), Self::Error> {
+    self.0 = self
+      .0
+      .checked_add_signed(event)
+      .ok_or(anyhow::anyhow!("not enough funds"))?;
+    Ok(())
+  }
+}
+
+impl Projection<i32> for Balance {
+  fn validate(&self, event: &i32) -> anyhow::Result<()> {
+    anyhow::ensure!(
+      self.0.checked_add_signed(*event).is_some(),
+      "not enough funds"
+    );
+    Ok(())
+  }
+
+  fn project(&mut self, event: i32) {
+    self.apply(event).expect("validated");
+  }
+}
@@ -0,0 +1,117 @@
+use super::{FromEvent, bus::EventBus};
+
+/// Stores the events of a state, so that any earlier state can be rebuilt.
+///
+/// A snapshot is kept every `interval` events, so rebuilding a state replays at most `interval - 1` events.
+pub struct EventLog<S: FromEvent> {
+  events: Vec<S::Event>,
+  /// `snapshots[i]` is the state after `i * interval` events.
+  snapshots: Vec<S>,
+  interval: usize,
+  current: S,
+  bus: EventBus<S::Event>,
+}
+
+impl<S> EventLog<S>
+where
+  S: FromEvent + Clone + Default,
+  S::Event: Clone,
+{
+  pub fn new(interval: usize) -> Self {
+    assert!(interval > 0, "interval cannot be 0");
+    Self {
+      events: vec![],
+      snapshots: vec![S::default()],
+      interval,
+      current: S::default(),
+      bus: EventBus::new(),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.events.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.events.is_empty()
+  }
+
+  pub fn events(&self) -> &[S::Event] {
+    &self.events
+  }
+
+  /// The state after all events.
+  pub fn state(&self) -> &S {
+    &self.current
+  }
+
+  /// Subscribers are notified of every event after it has been appended.
+  pub fn bus(&mut self) -> &mut EventBus<S::Event> {
+    &mut self.bus
+  }
+
+  /// Applies `event` to the current state and stores it.
+  /// Events that fail to apply are not stored, and leave the state unchanged.
+  pub fn append(&mut self, event: S::Event) -> Result<(), S::Error> {
+    self.current = self.current.clone().add_event(event.clone())?;
+    self.bus.publish(&event);
+    self.events.push(event);
+    if self.events.len().is_multiple_of(self.interval) {
+      self.snapshots.push(self.current.clone());
+    }
+    Ok(())
+  }
+
+  pub fn append_all<I>(&mut self, events: I) -> Result<(), S::Error>
+  where
+    I: IntoIterator<Item = S::Event>,
+  {
+    events.into_iter().try_for_each(|event| self.append(event))
+  }
+
+  /// The state after the first `n` events, or None if there are fewer than `n` events.
+  pub fn state_at(&self, n: usize) -> Option<S> {
+    if n > self.len() {
+      return None;
+    }
+    let snapshot = n / self.interval;
+    let replay = self.events[snapshot * self.interval..n].iter().cloned();
+    let state = self.snapshots[snapshot]
+      .clone()
+      .add_events(replay)
+      .ok()
+      .expect("events were validated when appended");
+    Some(state)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::events::fixtures::Balance;
+
+  use super::*;
+
+  #[test]
+  fn test_state_at() {
+    let mut log: EventLog<Balance> = EventLog::new(3);
+    log.append_all([10, 20, -5, 7, 1, 1, 1]).unwrap();
+    assert_eq!(log.len(), 7);
+    assert_eq!(log.state(), &Balance(35));
+
+    let states: Vec<_> = (0..=7).map(|n| log.state_at(n).unwrap().0).collect();
+    assert_eq!(states, [0, 10, 30, 25, 32, 33, 34, 35]);
+    assert_eq!(log.state_at(8), None);
+  }
+
+  #[test]
+  fn test_append_invalid() {
+    let mut log: EventLog<Balance> = EventLog::new(2);
+    let (tx, rx) = std::sync::mpsc::channel();
+    log.bus().subscribe_channel(tx);
+    log.append(10).unwrap();
+    assert!(log.append(-20).is_err());
+    assert_eq!(log.events(), [10]);
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), [10]);
+    assert_eq!(log.state(), &Balance(10));
+  }
+}
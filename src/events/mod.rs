@@ -1,3 +1,13 @@
+pub mod bus;
+pub mod cache;
+#[cfg(test)]
+mod fixtures;
+pub mod log;
+pub mod projection;
+pub mod repository;
+#[cfg(feature = "serde")]
+pub mod store;
+
 pub trait FromEvent
 where
   Self: Sized,
@@ -24,27 +34,116 @@ where
     let res: Self = Default::default();
     res.add_events(events)
   }
+
+  /// Applies `events` to a copy of `self`, collecting every event that fails along with its index.
+  /// Failing events are skipped, so later events are checked against the state without them.
+  fn check_events<I>(&self, events: I) -> Result<(), Vec<(usize, Self::Error)>>
+  where
+    Self: Clone,
+    I: IntoIterator<Item = Self::Event>,
+  {
+    let mut state = self.clone();
+    let mut errors = vec![];
+    for (index, event) in events.into_iter().enumerate() {
+      match state.clone().add_event(event) {
+        Ok(next) => state = next,
+        Err(e) => errors.push((index, e)),
+      }
+    }
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
 }
 
-/*
-impl<T, I, E, Err> TryFrom<I> for T
-where
-T: Default + FromEvent<Event = E, Error = Err>,
-I: IntoIterator<Item = E>,
-{
-  type Error = Err;
+/// Decides which events a command results in, given the current state.
+/// Together with `FromEvent`, which evolves the state from the events, this forms the event sourcing loop.
+pub trait HandleCommand: FromEvent {
+  type Command;
+
+  fn handle(&self, command: Self::Command) -> Result<Vec<Self::Event>, Self::Error>;
+
+  /// Handles `command` and applies the resulting events,
+  /// returning the new state together with the events so they can be stored.
+  fn execute(self, command: Self::Command) -> Result<(Self, Vec<Self::Event>), Self::Error>
+  where
+    Self::Event: Clone,
+  {
+    let events = self.handle(command)?;
+    let state = self.add_events(events.clone())?;
+    Ok((state, events))
+  }
+}
 
-  fn try_from(events: I) -> Result<Self, Self::Error> {
-    T::from_events(events)
+/// Applies events in place, without moving the state for every event.
+/// Every `ApplyEvent` is also a `FromEvent`.
+pub trait ApplyEvent {
+  type Event;
+  type Error;
+
+  /// On error, the state may be left partially updated.
+  fn apply(&mut self, event: Self::Event) -> Result<(), Self::Error>;
+
+  fn apply_events<I>(&mut self, events: I) -> Result<(), Self::Error>
+  where
+    I: IntoIterator<Item = Self::Event>,
+  {
+    events.into_iter().try_for_each(|event| self.apply(event))
+  }
+}
+
+impl<T: ApplyEvent> FromEvent for T {
+  type Event = T::Event;
+  type Error = T::Error;
+
+  fn add_event(mut self, event: Self::Event) -> Result<Self, Self::Error> {
+    self.apply(event)?;
+    Ok(self)
   }
 }
-*/
+
+/// A stream of events, to be folded into a state with `TryFrom`.
+///
+/// A blanket `impl<T: FromEvent> TryFrom<I> for T` is not allowed by the orphan rules,
+/// and would overlap with the blanket `TryFrom` of the standard library.
+/// Wrapping the events lets `impl_try_from_events!` implement it per type instead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Events<I>(pub I);
+
+impl<I: IntoIterator> IntoIterator for Events<I> {
+  type Item = I::Item;
+  type IntoIter = I::IntoIter;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.0.into_iter()
+  }
+}
+
+/// Implements `TryFrom<Events<I>>` for a `FromEvent + Default` type, so that
+/// `T::try_from(Events(events))` folds the events into a new state.
+#[macro_export]
+macro_rules! impl_try_from_events {
+  ($type:ty) => {
+    impl<I> TryFrom<$crate::events::Events<I>> for $type
+    where
+      I: IntoIterator<Item = <$type as $crate::events::FromEvent>::Event>,
+    {
+      type Error = <$type as $crate::events::FromEvent>::Error;
+
+      fn try_from(events: $crate::events::Events<I>) -> Result<Self, Self::Error> {
+        <$type as $crate::events::FromEvent>::from_events(events)
+      }
+    }
+  };
+}
 
 #[cfg(test)]
 mod test {
   use anyhow::ensure;
 
-  use super::*;
+  use super::{fixtures::Balance, *};
 
   #[derive(Debug, Clone, PartialEq)]
   struct Account {
@@ -124,6 +223,84 @@ mod test {
     assert!(err.to_string().contains("not enough funds"));
   }
 
+  #[test]
+  fn test_apply() {
+    let mut balance = Balance::default();
+    balance.apply_events([1, 2, 3]).unwrap();
+    assert_eq!(balance, Balance(6));
+
+    let balance = Balance::from_events([4, 5]).unwrap();
+    assert_eq!(balance, Balance(9));
+    assert!(balance.add_event(-10).is_err());
+  }
+
+  enum AccountCommand {
+    /// Pays `amount` plus a fee of 1.
+    Pay { amount: usize },
+  }
+
+  impl HandleCommand for Account {
+    type Command = AccountCommand;
+
+    fn handle(&self, command: Self::Command) -> Result<Vec<Self::Event>, Self::Error> {
+      match command {
+        AccountCommand::Pay { amount } => {
+          ensure!(self.balance > amount, "not enough funds");
+          Ok(vec![
+            AccountEvent::Withdraw { amount },
+            AccountEvent::Withdraw { amount: 1 },
+          ])
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_execute() {
+    let acc = Account::from_events([AccountEvent::Deposit { amount: 100 }]).unwrap();
+    let (acc, events) = acc.execute(AccountCommand::Pay { amount: 50 }).unwrap();
+    assert_eq!(acc.balance, 49);
+    assert_eq!(
+      events,
+      [
+        AccountEvent::Withdraw { amount: 50 },
+        AccountEvent::Withdraw { amount: 1 }
+      ]
+    );
+    assert!(acc.handle(AccountCommand::Pay { amount: 49 }).is_err());
+  }
+
+  impl_try_from_events!(Account);
+
+  #[test]
+  fn test_try_from_events() {
+    let evs = vec![
+      AccountEvent::Deposit { amount: 200 },
+      AccountEvent::Withdraw { amount: 50 },
+    ];
+    let acc = Account::try_from(Events(evs)).unwrap();
+    assert_eq!(acc.balance, 150);
+
+    let res: Result<Account, _> = Events([AccountEvent::Withdraw { amount: 1 }]).try_into();
+    assert!(res.is_err());
+  }
+
+  #[test]
+  fn test_check_events() {
+    let evs = vec![
+      AccountEvent::Withdraw { amount: 10 },
+      AccountEvent::Deposit { amount: 100 },
+      AccountEvent::Withdraw { amount: 150 },
+      AccountEvent::Withdraw { amount: 100 },
+    ];
+    let errors = Account::default().check_events(evs).unwrap_err();
+    assert_eq!(errors.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [0, 2]);
+    assert!(errors[0].1.to_string().contains("not enough funds"));
+
+    let evs = [AccountEvent::Deposit { amount: 1 }];
+    assert!(Account::default().check_events(evs).is_ok());
+  }
+
   #[test]
   fn test_from_events() {
     let evs = vec![
@@ -0,0 +1,151 @@
+use std::{any::Any, convert::Infallible};
+
+use super::ApplyEvent;
+
+/// A read model folded from a stream of `E`, which can be driven alongside others in a `ProjectionSet`.
+/// Every `ApplyEvent` state that cannot fail is also a projection.
+pub trait Projection<E>: Any {
+  /// Checks that `event` can be projected, without changing the projection.
+  fn validate(&self, event: &E) -> anyhow::Result<()>;
+
+  /// Applies `event` in place. Only called with events that passed `validate`.
+  fn project(&mut self, event: E);
+}
+
+impl<S> Projection<S::Event> for S
+where
+  S: ApplyEvent<Error = Infallible> + 'static,
+{
+  fn validate(&self, _event: &S::Event) -> anyhow::Result<()> {
+    Ok(())
+  }
+
+  fn project(&mut self, event: S::Event) {
+    let Ok(()) = self.apply(event);
+  }
+}
+
+/// Folds several projections over the same events in a single pass.
+/// Projections are looked up by type, so each type can be added once.
+pub struct ProjectionSet<E> {
+  projections: Vec<Box<dyn Projection<E>>>,
+}
+
+impl<E: Clone + 'static> ProjectionSet<E> {
+  pub fn new() -> Self {
+    Self {
+      projections: vec![],
+    }
+  }
+
+  /// Adds `projection`. Panics if a projection of the same type was already added.
+  pub fn add<P: Projection<E>>(&mut self, projection: P) -> &mut Self {
+    assert!(self.get::<P>().is_none(), "projection already added");
+    self.projections.push(Box::new(projection));
+    self
+  }
+
+  /// Applies `event` to every projection, or to none of them if any projection rejects it.
+  /// Every projection validates the event before any of them is changed.
+  pub fn project(&mut self, event: E) -> anyhow::Result<()> {
+    for projection in &self.projections {
+      projection.validate(&event)?;
+    }
+    for projection in &mut self.projections {
+      projection.project(event.clone());
+    }
+    Ok(())
+  }
+
+  /// Applies `events` in order, stopping at the first event that fails.
+  /// The events before it stay applied.
+  pub fn project_all(&mut self, events: impl IntoIterator<Item = E>) -> anyhow::Result<()> {
+    events.into_iter().try_for_each(|event| self.project(event))
+  }
+
+  /// The current state of the projection of type `P`, or None if there is none.
+  pub fn get<P: Projection<E>>(&self) -> Option<&P> {
+    self.projections.iter().find_map(|projection| {
+      let any: &dyn Any = projection.as_ref();
+      any.downcast_ref::<P>()
+    })
+  }
+}
+
+impl<E: Clone + 'static> Default for ProjectionSet<E> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::events::fixtures::Balance;
+
+  use super::*;
+
+  /// The deposits, which cannot fail.
+  #[derive(Debug, Default, PartialEq)]
+  struct Deposits(Vec<i32>);
+
+  impl ApplyEvent for Deposits {
+    type Event = i32;
+    type Error = Infallible;
+
+    fn apply(&mut self, event: i32) -> Result<(), Infallible> {
+      if event > 0 {
+        self.0.push(event);
+      }
+      Ok(())
+    }
+  }
+
+  /// The largest deposit, which does not allow deposits over 100.
+  #[derive(Debug, Default, PartialEq)]
+  struct Largest(i32);
+
+  impl Projection<i32> for Largest {
+    fn validate(&self, event: &i32) -> anyhow::Result<()> {
+      anyhow::ensure!(*event <= 100, "deposit too large");
+      Ok(())
+    }
+
+    fn project(&mut self, event: i32) {
+      self.0 = self.0.max(event);
+    }
+  }
+
+  #[test]
+  fn test_projections() {
+    let mut set = ProjectionSet::new();
+    set.add(Balance::default()).add(Deposits::default());
+    set.project_all([3, 5, -3]).unwrap();
+
+    assert_eq!(set.get::<Balance>(), Some(&Balance(5)));
+    assert_eq!(set.get::<Deposits>(), Some(&Deposits(vec![3, 5])));
+
+    assert!(set.project(-10).is_err());
+    assert_eq!(set.get::<Balance>(), Some(&Balance(5)));
+    set.project(-5).unwrap();
+    assert_eq!(set.get::<Balance>(), Some(&Balance(0)));
+  }
+
+  #[test]
+  fn test_failure_applies_nothing() {
+    let mut set = ProjectionSet::new();
+    set
+      .add(Balance::default())
+      .add(Deposits::default())
+      .add(Largest::default());
+    set.project(3).unwrap();
+
+    // the projections before `Largest` accept the event, but must not apply it
+    assert!(set.project(1000).is_err());
+    assert_eq!(set.get::<Balance>(), Some(&Balance(3)));
+    assert_eq!(set.get::<Deposits>(), Some(&Deposits(vec![3])));
+    assert_eq!(set.get::<Largest>(), Some(&Largest(3)));
+
+    set.project(5).unwrap();
+    assert_eq!(set.get::<Largest>(), Some(&Largest(5)));
+  }
+}
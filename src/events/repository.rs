@@ -0,0 +1,87 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::FromEvent;
+
+/// Keeps one aggregate per key, routing each event to the aggregate it is tagged with.
+/// Aggregates are created from their default the first time an event arrives for them.
+pub struct Repository<K, S> {
+  aggregates: HashMap<K, S>,
+}
+
+impl<K, S> Repository<K, S>
+where
+  K: Hash + Eq,
+  S: FromEvent + Clone + Default,
+{
+  pub fn new() -> Self {
+    Self {
+      aggregates: HashMap::new(),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.aggregates.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.aggregates.is_empty()
+  }
+
+  pub fn get(&self, key: &K) -> Option<&S> {
+    self.aggregates.get(key)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&K, &S)> {
+    self.aggregates.iter()
+  }
+
+  /// Applies `event` to the aggregate at `key`.
+  /// If the event fails to apply, the aggregate is left unchanged, or not created if it is new.
+  pub fn apply(&mut self, key: K, event: S::Event) -> Result<(), S::Error> {
+    let aggregate = self.aggregates.get(&key).cloned().unwrap_or_default();
+    self.aggregates.insert(key, aggregate.add_event(event)?);
+    Ok(())
+  }
+
+  pub fn apply_all<I>(&mut self, events: I) -> Result<(), S::Error>
+  where
+    I: IntoIterator<Item = (K, S::Event)>,
+  {
+    events
+      .into_iter()
+      .try_for_each(|(key, event)| self.apply(key, event))
+  }
+
+  pub fn into_states(self) -> HashMap<K, S> {
+    self.aggregates
+  }
+}
+
+impl<K: Hash + Eq, S: FromEvent + Clone + Default> Default for Repository<K, S> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::events::fixtures::Balance;
+
+  use super::*;
+
+  #[test]
+  fn test_routing() {
+    let mut repo: Repository<&str, Balance> = Repository::new();
+    repo
+      .apply_all([("alice", 10), ("bob", 5), ("alice", -3)])
+      .unwrap();
+    assert!(repo.apply("bob", -6).is_err());
+    assert_eq!(repo.get(&"bob"), Some(&Balance(5)));
+    assert!(repo.apply("carol", -1).is_err());
+    assert_eq!(repo.get(&"carol"), None);
+
+    let states = repo.into_states();
+    assert_eq!(states.len(), 2);
+    assert_eq!(states["alice"], Balance(7));
+  }
+}
@@ -0,0 +1,87 @@
+use std::{
+  fs::{File, OpenOptions},
+  io::{BufRead, BufReader, Write},
+  path::Path,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::FromEvent;
+
+/// Keeps a state on disk by appending its events to a file, one JSON object per line.
+/// Opening the store replays the file to rebuild the state.
+pub struct FileEventStore<S> {
+  file: File,
+  state: S,
+}
+
+impl<S> FileEventStore<S>
+where
+  S: FromEvent + Clone + Default,
+  S::Event: Serialize + DeserializeOwned,
+  S::Error: Into<anyhow::Error>,
+{
+  /// Opens or creates the store at `path`, replaying all stored events.
+  pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+    let path = path.as_ref();
+    let mut state = S::default();
+    if path.exists() {
+      for (n, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+          continue;
+        }
+        let event = serde_json::from_str(&line)
+          .map_err(|e| anyhow::anyhow!("invalid event on line {}: {}", n + 1, e))?;
+        state = state.add_event(event).map_err(Into::into)?;
+      }
+    }
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    Ok(Self { file, state })
+  }
+
+  pub fn state(&self) -> &S {
+    &self.state
+  }
+
+  pub fn into_state(self) -> S {
+    self.state
+  }
+
+  /// Applies `event` and appends it to the file.
+  /// Events that fail to apply are not stored, and leave the state unchanged.
+  pub fn append(&mut self, event: S::Event) -> anyhow::Result<()> {
+    let line = serde_json::to_string(&event)?;
+    let state = self.state.clone().add_event(event).map_err(Into::into)?;
+    writeln!(self.file, "{}", line)?;
+    self.file.flush()?;
+    self.state = state;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::events::fixtures::Balance;
+
+  use super::*;
+
+  #[test]
+  fn test_replay() {
+    let path = std::env::temp_dir().join(format!("aoc25-events-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut store: FileEventStore<Balance> = FileEventStore::open(&path).unwrap();
+    store.append(5).unwrap();
+    store.append(-5).unwrap();
+    store.append(7).unwrap();
+    assert!(store.append(-8).is_err());
+    assert_eq!(store.state(), &Balance(7));
+    drop(store);
+
+    let store: FileEventStore<Balance> = FileEventStore::open(&path).unwrap();
+    assert_eq!(store.into_state(), Balance(7));
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+    std::fs::remove_file(&path).unwrap();
+  }
+}
@@ -0,0 +1,319 @@
+use crate::grid::Grid;
+
+const ROOT: usize = 0;
+
+/// An exact cover problem, solved with Knuth's Algorithm X on dancing links.
+///
+/// Each row covers some columns. A solution is a set of rows that covers every primary column
+/// exactly once, and every secondary column at most once.
+pub struct ExactCover {
+  left: Vec<usize>,
+  right: Vec<usize>,
+  up: Vec<usize>,
+  down: Vec<usize>,
+  /// The column header of each node.
+  column: Vec<usize>,
+  /// The row of each node, unused for the root and the column headers.
+  row: Vec<usize>,
+  /// The number of rows in each column that are not covered, indexed by header.
+  size: Vec<usize>,
+  columns: usize,
+  rows: usize,
+}
+
+impl ExactCover {
+  /// Columns `0..primary` are primary, and the next `secondary` columns are secondary.
+  pub fn new(primary: usize, secondary: usize) -> Self {
+    let columns = primary + secondary;
+    let headers = 0..=columns;
+    let mut cover = Self {
+      left: headers.clone().collect(),
+      right: headers.clone().collect(),
+      up: headers.clone().collect(),
+      down: headers.clone().collect(),
+      column: headers.collect(),
+      row: vec![usize::MAX; columns + 1],
+      size: vec![0; columns + 1],
+      columns,
+      rows: 0,
+    };
+    // only primary columns are linked to the root, so only they have to be covered
+    for header in 1..=primary {
+      cover.left[header] = header - 1;
+      cover.right[header - 1] = header;
+    }
+    cover.left[ROOT] = primary;
+    cover.right[primary] = ROOT;
+    cover
+  }
+
+  /// Adds a row covering `columns`, and returns its index. Panics if a column is out of range.
+  pub fn add_row(&mut self, columns: &[usize]) -> usize {
+    let row = self.rows;
+    self.rows += 1;
+    let mut first = None;
+    for &column in columns {
+      assert!(column < self.columns, "column out of range: {column}");
+      let header = column + 1;
+      let node = self.left.len();
+      self.column.push(header);
+      self.row.push(row);
+      // insert at the bottom of the column
+      self.up.push(self.up[header]);
+      self.down.push(header);
+      self.down[self.up[header]] = node;
+      self.up[header] = node;
+      self.size[header] += 1;
+      // insert at the end of the row
+      match first {
+        None => {
+          self.left.push(node);
+          self.right.push(node);
+          first = Some(node);
+        }
+        Some(first) => {
+          self.left.push(self.left[first]);
+          self.right.push(first);
+          self.right[self.left[first]] = node;
+          self.left[first] = node;
+        }
+      }
+    }
+    row
+  }
+
+  pub fn rows(&self) -> usize {
+    self.rows
+  }
+
+  /// The rows of a solution, in increasing order, or None if there is none.
+  pub fn solve(&mut self) -> Option<Vec<usize>> {
+    let mut found = None;
+    self.search(&mut vec![], &mut |rows| {
+      let mut rows = rows.to_vec();
+      rows.sort_unstable();
+      found = Some(rows);
+      true
+    });
+    found
+  }
+
+  pub fn count_solutions(&mut self) -> usize {
+    let mut count = 0;
+    self.search(&mut vec![], &mut |_| {
+      count += 1;
+      false
+    });
+    count
+  }
+
+  /// Calls `found` with every solution until it returns true, and returns whether it did.
+  /// The links are restored before returning either way.
+  fn search(&mut self, rows: &mut Vec<usize>, found: &mut dyn FnMut(&[usize]) -> bool) -> bool {
+    if self.right[ROOT] == ROOT {
+      return found(rows);
+    }
+    // branching on the column with the fewest rows keeps the search small
+    let mut header = self.right[ROOT];
+    let mut next = self.right[header];
+    while next != ROOT {
+      if self.size[next] < self.size[header] {
+        header = next;
+      }
+      next = self.right[next];
+    }
+    if self.size[header] == 0 {
+      return false;
+    }
+
+    self.cover(header);
+    let mut node = self.down[header];
+    let mut stop = false;
+    while node != header && !stop {
+      rows.push(self.row[node]);
+      let mut other = self.right[node];
+      while other != node {
+        self.cover(self.column[other]);
+        other = self.right[other];
+      }
+      stop = self.search(rows, found);
+      let mut other = self.left[node];
+      while other != node {
+        self.uncover(self.column[other]);
+        other = self.left[other];
+      }
+      rows.pop();
+      node = self.down[node];
+    }
+    self.uncover(header);
+    stop
+  }
+
+  /// Removes a column, and every row that covers it from the other columns.
+  fn cover(&mut self, header: usize) {
+    self.right[self.left[header]] = self.right[header];
+    self.left[self.right[header]] = self.left[header];
+    let mut node = self.down[header];
+    while node != header {
+      let mut other = self.right[node];
+      while other != node {
+        self.down[self.up[other]] = self.down[other];
+        self.up[self.down[other]] = self.up[other];
+        self.size[self.column[other]] -= 1;
+        other = self.right[other];
+      }
+      node = self.down[node];
+    }
+  }
+
+  /// Undoes `cover`, in exactly the reverse order.
+  fn uncover(&mut self, header: usize) {
+    let mut node = self.up[header];
+    while node != header {
+      let mut other = self.left[node];
+      while other != node {
+        self.size[self.column[other]] += 1;
+        self.down[self.up[other]] = other;
+        self.up[self.down[other]] = other;
+        other = self.left[other];
+      }
+      node = self.up[node];
+    }
+    self.right[self.left[header]] = header;
+    self.left[self.right[header]] = header;
+  }
+}
+
+/// The distinct rotations and reflections of a shape given by its cells,
+/// each moved to touch the axes and sorted.
+pub fn orientations(cells: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+  let cells: Vec<(isize, isize)> = cells
+    .iter()
+    .map(|&(x, y)| (x as isize, y as isize))
+    .collect();
+  let mut orientations: Vec<Vec<(usize, usize)>> = vec![];
+  for turns in 0..8 {
+    // mirror for the last four, then turn a quarter at a time
+    let moved: Vec<_> = cells
+      .iter()
+      .map(|&(x, y)| {
+        let (mut x, mut y) = if turns < 4 { (x, y) } else { (-x, y) };
+        for _ in 0..turns % 4 {
+          (x, y) = (-y, x);
+        }
+        (x, y)
+      })
+      .collect();
+    let min_x = moved.iter().map(|c| c.0).min().unwrap_or(0);
+    let min_y = moved.iter().map(|c| c.1).min().unwrap_or(0);
+    let mut normalized: Vec<(usize, usize)> = moved
+      .iter()
+      .map(|&(x, y)| ((x - min_x) as usize, (y - min_y) as usize))
+      .collect();
+    normalized.sort_unstable();
+    if !orientations.contains(&normalized) {
+      orientations.push(normalized);
+    }
+  }
+  orientations
+}
+
+/// Every way to place a shape in the free cells of a region, in any orientation.
+/// A placement is given by the cells it covers, as `y * width + x` in the region,
+/// which can be used as exact cover columns.
+pub fn placements(region: &Grid<bool>, shape: &Grid<bool>) -> Vec<Vec<usize>> {
+  let cells: Vec<(usize, usize)> = shape
+    .cells()
+    .filter(|(_, _, filled)| **filled)
+    .map(|(x, y, _)| (x, y))
+    .collect();
+  let mut placements = vec![];
+  for orientation in orientations(&cells) {
+    for y in 0..region.height() {
+      for x in 0..region.width() {
+        let covered: Option<Vec<usize>> = orientation
+          .iter()
+          .map(|&(dx, dy)| {
+            let (cx, cy) = (x + dx, y + dy);
+            region
+              .get(cx, cy)
+              .filter(|free| **free)
+              .map(|_| cy * region.width() + cx)
+          })
+          .collect();
+        placements.extend(covered);
+      }
+    }
+  }
+  placements
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_knuth_example() {
+    // the example from Knuth's paper, with a unique solution of rows 0, 3 and 4
+    let mut cover = ExactCover::new(7, 0);
+    cover.add_row(&[2, 4, 5]);
+    cover.add_row(&[0, 3, 6]);
+    cover.add_row(&[1, 2, 5]);
+    cover.add_row(&[0, 3]);
+    cover.add_row(&[1, 6]);
+    cover.add_row(&[3, 4, 6]);
+    assert_eq!(cover.solve(), Some(vec![0, 3, 4]));
+    assert_eq!(cover.count_solutions(), 1);
+    // solving leaves the links as they were
+    assert_eq!(cover.solve(), Some(vec![0, 3, 4]));
+  }
+
+  #[test]
+  fn test_secondary() {
+    let mut cover = ExactCover::new(2, 1);
+    cover.add_row(&[0, 2]);
+    cover.add_row(&[1, 2]);
+    assert_eq!(cover.solve(), None);
+    cover.add_row(&[1]);
+    assert_eq!(cover.solve(), Some(vec![0, 2]));
+    assert_eq!(cover.count_solutions(), 1);
+  }
+
+  #[test]
+  fn test_orientations() {
+    assert_eq!(orientations(&[(0, 0), (1, 0), (0, 1), (1, 1)]).len(), 1);
+    assert_eq!(orientations(&[(0, 0), (1, 0), (2, 0)]).len(), 2);
+    // the L tromino has 4, the L tetromino 8
+    assert_eq!(orientations(&[(0, 0), (0, 1), (1, 1)]).len(), 4);
+    assert_eq!(orientations(&[(0, 0), (0, 1), (0, 2), (1, 2)]).len(), 8);
+  }
+
+  #[test]
+  fn test_domino_tilings() {
+    // a 3x2 rectangle has 3 domino tilings
+    let domino = Grid::from_data([true, true], 2).unwrap();
+    let count = |region: &Grid<bool>, dominoes| {
+      let cells = region.width() * region.height();
+      let mut cover = ExactCover::new(dominoes, cells);
+      for piece in 0..dominoes {
+        for placement in placements(region, &domino) {
+          let columns: Vec<usize> = std::iter::once(piece)
+            .chain(placement.iter().map(|cell| dominoes + cell))
+            .collect();
+          cover.add_row(&columns);
+        }
+      }
+      cover.count_solutions()
+    };
+    let open = Grid::new(3, 2, true);
+    // every tiling is counted once per ordering of the identical pieces
+    assert_eq!(count(&open, 3), 3 * 6);
+    assert_eq!(placements(&open, &domino).len(), 7);
+
+    // blocked cells cannot be covered, leaving 2 tilings of the 3x3 ring
+    let mut ring = Grid::new(3, 3, true);
+    ring.set(1, 1, false);
+    assert_eq!(placements(&ring, &domino).len(), 8);
+    assert_eq!(count(&ring, 4), 2 * 24);
+  }
+}
@@ -2,24 +2,136 @@ use std::{fmt::Display, time::Duration};
 
 pub trait DurationExt {
   fn display(&self) -> impl Display;
+
+  /// Shows the duration with `decimals` decimals in the largest unit that keeps it above 1,
+  /// padded to a fixed width so that columns of durations line up.
+  fn display_precise(&self, decimals: usize) -> impl Display;
+
+  /// The duration with three decimals and no padding, e.g. `1.234ms`.
+  fn as_compact_string(&self) -> String;
 }
 
 impl DurationExt for Duration {
   fn display(&self) -> impl Display {
     DurationDisplay(self)
   }
+
+  fn display_precise(&self, decimals: usize) -> impl Display {
+    PreciseDisplay(self, decimals)
+  }
+
+  fn as_compact_string(&self) -> String {
+    let (value, unit) = in_unit(self, 3);
+    format!("{value:.3}{unit}")
+  }
+}
+
+const UNITS: [(f64, &str); 4] = [(1.0, "ns"), (1e3, "μs"), (1e6, "ms"), (1e9, "s")];
+
+/// The duration in the largest unit that keeps it at least 1, or in nanoseconds.
+/// If rounding to `decimals` would reach 1000, the next unit is used instead.
+fn in_unit(duration: &Duration, decimals: usize) -> (f64, &'static str) {
+  let nanos = duration.as_nanos() as f64;
+  let mut index = UNITS
+    .iter()
+    .rposition(|&(scale, _)| nanos >= scale)
+    .unwrap_or(0);
+  let rounding = 10f64.powi(decimals as i32);
+  if index + 1 < UNITS.len() && (nanos / UNITS[index].0 * rounding).round() >= 1000.0 * rounding {
+    index += 1;
+  }
+  let (scale, unit) = UNITS[index];
+  (nanos / scale, unit)
+}
+
+/// A duration that displays and serializes in its compact form, for writing out results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CompactDuration(pub Duration);
+
+impl Display for CompactDuration {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.pad(&self.0.as_compact_string())
+  }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CompactDuration {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&self.0.as_compact_string())
+  }
 }
 
 struct DurationDisplay<'a>(&'a Duration);
 
 impl Display for DurationDisplay<'_> {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    if self.0.as_secs_f64() > 0.5 {
-      write!(f, "{:.1}s", self.0.as_secs_f64())
+    let text = if self.0.as_secs_f64() > 0.5 {
+      format!("{:.1}s", self.0.as_secs_f64())
     } else if self.0.as_millis() > 5 {
-      write!(f, "{}ms", self.0.as_millis())
+      format!("{}ms", self.0.as_millis())
+    } else if self.0.as_micros() > 0 {
+      format!("{}μs", self.0.as_micros())
     } else {
-      write!(f, "{}μs", self.0.as_micros())
-    }
+      format!("{}ns", self.0.as_nanos())
+    };
+    f.pad(&text)
+  }
+}
+
+struct PreciseDisplay<'a>(&'a Duration, usize);
+
+impl Display for PreciseDisplay<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let PreciseDisplay(duration, decimals) = *self;
+    let (value, unit) = in_unit(duration, decimals);
+    // room for three integer digits, the point and the decimals
+    let width = if decimals > 0 { 4 + decimals } else { 3 };
+    write!(f, "{value:>width$.decimals$}{unit:<2}")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_display() {
+    assert_eq!(Duration::from_secs(2).display().to_string(), "2.0s");
+    assert_eq!(Duration::from_millis(20).display().to_string(), "20ms");
+    assert_eq!(Duration::from_micros(20).display().to_string(), "20μs");
+    assert_eq!(Duration::from_nanos(20).display().to_string(), "20ns");
+    assert_eq!(
+      format!("{:>6}", Duration::from_millis(20).display()),
+      "  20ms"
+    );
+  }
+
+  #[test]
+  fn test_display_precise() {
+    let precise = |nanos| Duration::from_nanos(nanos).display_precise(2).to_string();
+    assert_eq!(precise(1_234_567), "  1.23ms");
+    assert_eq!(precise(999), "999.00ns");
+    assert_eq!(precise(999_999), "  1.00ms");
+    assert_eq!(precise(999_994), "999.99μs");
+    assert_eq!(precise(12_345), " 12.35μs");
+    assert_eq!(precise(3_000_000_000), "  3.00s ");
+    assert_eq!(
+      Duration::from_micros(5).display_precise(0).to_string(),
+      "  5μs"
+    );
+  }
+
+  #[test]
+  fn test_compact() {
+    assert_eq!(
+      Duration::from_nanos(1_234_567).as_compact_string(),
+      "1.235ms"
+    );
+    assert_eq!(Duration::from_nanos(42).as_compact_string(), "42.000ns");
+    let compact = CompactDuration(Duration::from_secs(90));
+    assert_eq!(compact.to_string(), "90.000s");
+
+    #[cfg(feature = "serde")]
+    assert_eq!(serde_json::to_string(&compact).unwrap(), "\"90.000s\"");
   }
 }
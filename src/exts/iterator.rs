@@ -1,7 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
 use std::hash::Hash;
 
+use num_traits::{ToPrimitive, Zero};
+
 pub trait IteratorExt: Iterator + Sized {
   /// Removes duplicate items from the iterator by the specified key.
   fn unique_by<F, M>(self, mapper: F) -> UniqueIterator<Self, F, M>
@@ -52,10 +54,304 @@ pub trait IteratorExt: Iterator + Sized {
   {
     VerboseFlatten { iter: self }
   }
+
+  /// Groups runs of consecutive items with equal keys into `(key, items)`.
+  fn chunk_by_key<F, K>(self, key: F) -> ChunkByKey<Self, F, K>
+  where
+    F: FnMut(&Self::Item) -> K,
+    K: PartialEq,
+  {
+    ChunkByKey {
+      inner: self,
+      key,
+      pending: None,
+    }
+  }
+
+  /// Overlapping windows of `N` consecutive items, e.g. `[a, b, c]`, `[b, c, d]`.
+  fn array_windows<const N: usize>(self) -> ArrayWindows<Self, N>
+  where
+    Self::Item: Clone,
+  {
+    assert!(N != 0, "window size cannot be 0");
+    ArrayWindows {
+      inner: self,
+      window: VecDeque::with_capacity(N),
+    }
+  }
+
+  /// The smallest item of each window of `k` consecutive items, in O(n) overall.
+  /// Ties go to the earliest item.
+  fn window_min(self, k: usize) -> WindowExtrema<Self>
+  where
+    Self::Item: Clone + Ord,
+  {
+    WindowExtrema::new(self, k, true)
+  }
+
+  /// The largest item of each window of `k` consecutive items, in O(n) overall.
+  /// Ties go to the earliest item.
+  fn window_max(self, k: usize) -> WindowExtrema<Self>
+  where
+    Self::Item: Clone + Ord,
+  {
+    WindowExtrema::new(self, k, false)
+  }
+
+  /// Every pair of items at distinct positions, each pair once: `(a, b)` but not `(b, a)`.
+  fn unordered_pairs(self) -> Pairs<Self>
+  where
+    Self: Clone,
+    Self::Item: Clone,
+  {
+    Pairs {
+      rest: self,
+      current: None,
+      with_replacement: false,
+    }
+  }
+
+  /// Like `unordered_pairs`, but also pairs every item with itself.
+  fn pairs_with_replacement(self) -> Pairs<Self>
+  where
+    Self: Clone,
+    Self::Item: Clone,
+  {
+    Pairs {
+      rest: self,
+      current: None,
+      with_replacement: true,
+    }
+  }
+
+  /// How many times each item occurs.
+  fn histogram(self) -> HashMap<Self::Item, usize>
+  where
+    Self::Item: Hash + Eq,
+  {
+    self.histogram_by(|item| item)
+  }
+
+  /// How many items map to each key.
+  fn histogram_by<F, K>(self, mut key: F) -> HashMap<K, usize>
+  where
+    F: FnMut(Self::Item) -> K,
+    K: Hash + Eq,
+  {
+    let mut counts = HashMap::new();
+    for item in self {
+      *counts.entry(key(item)).or_default() += 1;
+    }
+    counts
+  }
+
+  /// Like `take_while`, but also yields the first item that fails the predicate.
+  fn take_while_with_last<P>(self, predicate: P) -> TakeWhileWithLast<Self, P>
+  where
+    P: FnMut(&Self::Item) -> bool,
+  {
+    TakeWhileWithLast {
+      inner: self,
+      predicate,
+      done: false,
+    }
+  }
+
+  /// The smallest and largest items, in a single pass. Ties resolve like `min` and `max`.
+  fn min_max(mut self) -> Option<(Self::Item, Self::Item)>
+  where
+    Self::Item: Ord + Clone,
+  {
+    let first = self.next()?;
+    let init = (first.clone(), first);
+    Some(self.fold(init, |(min, max), item| {
+      if item < min {
+        (item, max)
+      } else if item >= max {
+        (min, item)
+      } else {
+        (min, max)
+      }
+    }))
+  }
+
+  /// The largest item and its position. Unlike `max`, ties resolve to the first item.
+  fn max_with_position(self) -> Option<(Self::Item, usize)>
+  where
+    Self::Item: Ord,
+  {
+    self
+      .enumerate()
+      .reduce(|best, next| if next.1 > best.1 { next } else { best })
+      .map(|(i, item)| (item, i))
+  }
+
+  /// The smallest item and its position. Ties resolve to the first item.
+  fn min_with_position(self) -> Option<(Self::Item, usize)>
+  where
+    Self::Item: Ord,
+  {
+    self
+      .enumerate()
+      .reduce(|best, next| if next.1 < best.1 { next } else { best })
+      .map(|(i, item)| (item, i))
+  }
+
+  /// Collects all successes, or if there are any errors, returns every error with its index.
+  fn try_collect_all<C>(self) -> Result<C, IndexedErrors<<Self::Item as ResultItem>::Error>>
+  where
+    Self::Item: ResultItem,
+    C: FromIterator<<Self::Item as ResultItem>::Ok>,
+  {
+    let mut errors = vec![];
+    let collected: C = self
+      .enumerate()
+      .filter_map(|(i, item)| item.into_result().map_err(|e| errors.push((i, e))).ok())
+      .collect();
+    if errors.is_empty() {
+      Ok(collected)
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// Run-length encodes the iterator into `(item, count)` for each run of equal items.
+  fn runs(self) -> impl Iterator<Item = (Self::Item, usize)>
+  where
+    Self::Item: PartialEq + Clone,
+  {
+    self
+      .chunk_by_key(Self::Item::clone)
+      .map(|(item, run)| (item, run.len()))
+  }
+
+  /// The inverse of `runs`, repeating each item `count` times.
+  fn expand_runs<T>(self) -> impl Iterator<Item = T>
+  where
+    Self: Iterator<Item = (T, usize)>,
+    T: Clone,
+  {
+    self.flat_map(|(item, count)| std::iter::repeat_n(item, count))
+  }
+
+  /// Summary statistics of the items, or None if there are none. Panics on NaN.
+  fn stats(self) -> Option<Stats<Self::Item>>
+  where
+    Self::Item: Copy + PartialOrd + Zero + ToPrimitive,
+  {
+    let mut items: Vec<_> = self.collect();
+    let min = *items.iter().min_by(compare)?;
+    let max = *items.iter().max_by(compare)?;
+    let sum = items.iter().fold(Self::Item::zero(), |acc, x| acc + *x);
+    let count = items.len();
+    let as_f64 = |x: Self::Item| x.to_f64().expect("item cannot be represented as f64");
+
+    let mid = count / 2;
+    let (below, upper, _) = items.select_nth_unstable_by(mid, compare);
+    let median = if count % 2 == 1 {
+      as_f64(*upper)
+    } else {
+      let lower = *below.iter().max_by(compare)?;
+      (as_f64(lower) + as_f64(*upper)) / 2.0
+    };
+
+    Some(Stats {
+      count,
+      sum,
+      mean: as_f64(sum) / count as f64,
+      min,
+      max,
+      median,
+    })
+  }
+
+  /// Finds where the keys of the items start repeating, as `(start, length)` of the cycle,
+  /// or None if the iterator ends first. Never returns if the keys neither repeat nor end.
+  fn find_cycle_by<F, K>(self, mut key: F) -> Option<(usize, usize)>
+  where
+    Self: Clone,
+    F: FnMut(&Self::Item) -> K,
+    K: PartialEq,
+  {
+    // Brent's algorithm: the tortoise jumps to the hare at every power of two
+    let mut hare = self.clone();
+    let mut tortoise = key(&hare.next()?);
+    let mut power = 1;
+    let mut length = 1;
+    loop {
+      let next = key(&hare.next()?);
+      if next == tortoise {
+        break;
+      }
+      if power == length {
+        tortoise = next;
+        power *= 2;
+        length = 0;
+      }
+      length += 1;
+    }
+
+    // start a hare `length` items ahead, they meet at the start of the cycle
+    let mut tortoise = self.clone();
+    let mut hare = self.skip(length);
+    let mut start = 0;
+    while key(&tortoise.next()?) != key(&hare.next()?) {
+      start += 1;
+    }
+    Some((start, length))
+  }
+
+  /// The `n`th item, skipping ahead with `find_cycle_by` if the keys repeat.
+  fn state_after<F, K>(mut self, n: usize, key: F) -> Option<Self::Item>
+  where
+    Self: Clone,
+    F: FnMut(&Self::Item) -> K,
+    K: PartialEq,
+  {
+    match self.clone().find_cycle_by(key) {
+      Some((start, length)) if n >= start => self.nth(start + (n - start) % length),
+      _ => self.nth(n),
+    }
+  }
 }
 
 impl<T: Iterator> IteratorExt for T {}
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats<T> {
+  pub count: usize,
+  pub sum: T,
+  pub mean: f64,
+  pub min: T,
+  pub max: T,
+  /// The middle item, or the mean of the two middle items if the count is even.
+  pub median: f64,
+}
+
+fn compare<T: PartialOrd>(a: &T, b: &T) -> std::cmp::Ordering {
+  a.partial_cmp(b).expect("items cannot be compared")
+}
+
+/// Errors paired with the index of the item that caused them.
+pub type IndexedErrors<E> = Vec<(usize, E)>;
+
+/// Lets `try_collect_all` name the success and error types of `Result` items.
+pub trait ResultItem {
+  type Ok;
+  type Error;
+
+  fn into_result(self) -> Result<Self::Ok, Self::Error>;
+}
+
+impl<T, E> ResultItem for Result<T, E> {
+  type Ok = T;
+  type Error = E;
+
+  fn into_result(self) -> Result<T, E> {
+    self
+  }
+}
+
 pub struct RepeatEachIterator<T>
 where
   T: Iterator,
@@ -117,6 +413,184 @@ where
   }
 }
 
+pub struct ChunkByKey<I: Iterator, F, K> {
+  inner: I,
+  key: F,
+  /// The first item of the next group, which was pulled to end the previous one.
+  pending: Option<(K, I::Item)>,
+}
+
+impl<I, F, K> Iterator for ChunkByKey<I, F, K>
+where
+  I: Iterator,
+  F: FnMut(&I::Item) -> K,
+  K: PartialEq,
+{
+  type Item = (K, Vec<I::Item>);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (key, first) = match self.pending.take() {
+      Some(pending) => pending,
+      None => {
+        let item = self.inner.next()?;
+        ((self.key)(&item), item)
+      }
+    };
+    let mut group = vec![first];
+    for item in self.inner.by_ref() {
+      let next_key = (self.key)(&item);
+      if next_key != key {
+        self.pending = Some((next_key, item));
+        break;
+      }
+      group.push(item);
+    }
+    Some((key, group))
+  }
+}
+
+pub struct ArrayWindows<I: Iterator, const N: usize> {
+  inner: I,
+  window: VecDeque<I::Item>,
+}
+
+impl<I, const N: usize> Iterator for ArrayWindows<I, N>
+where
+  I: Iterator,
+  I::Item: Clone,
+{
+  type Item = [I::Item; N];
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.window.len() == N {
+      self.window.pop_front();
+    }
+    while self.window.len() < N {
+      self.window.push_back(self.inner.next()?);
+    }
+    Some(std::array::from_fn(|i| self.window[i].clone()))
+  }
+}
+
+pub struct WindowExtrema<I: Iterator> {
+  inner: I,
+  k: usize,
+  /// How many items have been pulled from `inner`.
+  seen: usize,
+  /// The items that can still be the extremum of a window, with their positions.
+  /// From front to back, they get later and less extreme.
+  candidates: VecDeque<(usize, I::Item)>,
+  min: bool,
+}
+
+impl<I: Iterator> WindowExtrema<I>
+where
+  I::Item: Clone + Ord,
+{
+  fn new(inner: I, k: usize, min: bool) -> Self {
+    assert!(k != 0, "window size cannot be 0");
+    Self {
+      inner,
+      k,
+      seen: 0,
+      candidates: VecDeque::new(),
+      min,
+    }
+  }
+
+  /// Whether `a` wins over the earlier `b`.
+  fn beats(&self, a: &I::Item, b: &I::Item) -> bool {
+    if self.min { a < b } else { a > b }
+  }
+}
+
+impl<I> Iterator for WindowExtrema<I>
+where
+  I: Iterator,
+  I::Item: Clone + Ord,
+{
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let item = self.inner.next()?;
+      while let Some((_, last)) = self.candidates.back()
+        && self.beats(&item, last)
+      {
+        self.candidates.pop_back();
+      }
+      self.candidates.push_back((self.seen, item));
+      self.seen += 1;
+      if self.seen >= self.k {
+        break;
+      }
+    }
+    // drop the front if it has left the window
+    if let Some((index, _)) = self.candidates.front()
+      && *index + self.k < self.seen
+    {
+      self.candidates.pop_front();
+    }
+    self.candidates.front().map(|(_, item)| item.clone())
+  }
+}
+
+pub struct Pairs<I: Iterator> {
+  rest: I,
+  /// The first item of the pairs being produced, and the items left to pair it with.
+  current: Option<(I::Item, I)>,
+  with_replacement: bool,
+}
+
+impl<I> Iterator for Pairs<I>
+where
+  I: Iterator + Clone,
+  I::Item: Clone,
+{
+  type Item = (I::Item, I::Item);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some((first, others)) = &mut self.current
+        && let Some(second) = others.next()
+      {
+        return Some((first.clone(), second));
+      }
+      let from_first = self.rest.clone();
+      let first = self.rest.next()?;
+      let others = if self.with_replacement {
+        from_first
+      } else {
+        self.rest.clone()
+      };
+      self.current = Some((first, others));
+    }
+  }
+}
+
+pub struct TakeWhileWithLast<I, P> {
+  inner: I,
+  predicate: P,
+  done: bool,
+}
+
+impl<I, P> Iterator for TakeWhileWithLast<I, P>
+where
+  I: Iterator,
+  P: FnMut(&I::Item) -> bool,
+{
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+    let item = self.inner.next()?;
+    self.done = !(self.predicate)(&item);
+    Some(item)
+  }
+}
+
 pub struct UniqueIterator<I: Iterator, F: FnMut(&I::Item) -> M, M: Hash + Eq> {
   inner: I,
   mapper: F,
@@ -146,7 +620,7 @@ where
 
 #[cfg(test)]
 mod tests {
-  use super::IteratorExt;
+  use super::{IteratorExt, Stats};
 
   #[test]
   fn test_unique() {
@@ -176,6 +650,184 @@ mod tests {
   fn test_repeat_each_empty() {
     let vals: Vec<usize> = vec![];
     let repeated: Vec<_> = vals.into_iter().repeat_each(3).collect();
-    assert_eq!(repeated, vec![]);
+    assert!(repeated.is_empty());
+  }
+
+  #[test]
+  fn test_chunk_by_key() {
+    let chunks: Vec<_> = "aabccca".chars().chunk_by_key(|c| *c).collect();
+    assert_eq!(
+      chunks,
+      [
+        ('a', vec!['a', 'a']),
+        ('b', vec!['b']),
+        ('c', vec!['c', 'c', 'c']),
+        ('a', vec!['a'])
+      ]
+    );
+
+    let runs: Vec<_> = [1, 3, 5, 2, 4, 7]
+      .into_iter()
+      .chunk_by_key(|n| n % 2 == 0)
+      .map(|(even, ns)| (even, ns.len()))
+      .collect();
+    assert_eq!(runs, [(false, 3), (true, 2), (false, 1)]);
+    assert_eq!(std::iter::empty::<u8>().chunk_by_key(|n| *n).count(), 0);
+  }
+
+  #[test]
+  fn test_array_windows() {
+    let windows: Vec<_> = (1..=5).array_windows::<3>().collect();
+    assert_eq!(windows, [[1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+
+    let increasing = [3, 1, 2, 5, 4]
+      .into_iter()
+      .array_windows()
+      .filter(|[a, b]| a < b)
+      .count();
+    assert_eq!(increasing, 2);
+    assert_eq!((1..3).array_windows::<3>().count(), 0);
+  }
+
+  #[test]
+  fn test_window_extrema() {
+    let values = [4, 2, 12, 3, 8, 8, 1, 5, 7];
+    let mins: Vec<_> = values.into_iter().window_min(3).collect();
+    assert_eq!(mins, [2, 2, 3, 3, 1, 1, 1]);
+    let maxes: Vec<_> = values.into_iter().window_max(3).collect();
+    assert_eq!(maxes, [12, 12, 12, 8, 8, 8, 7]);
+    assert_eq!(values.into_iter().window_max(1).collect::<Vec<_>>(), values);
+    assert_eq!(values.into_iter().window_min(10).count(), 0);
+
+    let brute: Vec<_> = values
+      .windows(4)
+      .map(|w| *w.iter().max().unwrap())
+      .collect();
+    assert_eq!(values.into_iter().window_max(4).collect::<Vec<_>>(), brute);
+  }
+
+  #[test]
+  fn test_pairs() {
+    let pairs: Vec<_> = (1..=4).unordered_pairs().collect();
+    assert_eq!(pairs, [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]);
+
+    let pairs: Vec<_> = "ab".chars().pairs_with_replacement().collect();
+    assert_eq!(pairs, [('a', 'a'), ('a', 'b'), ('b', 'b')]);
+    assert_eq!((0..1).unordered_pairs().count(), 0);
+    assert_eq!((0..100).unordered_pairs().count(), 4950);
+  }
+
+  #[test]
+  fn test_histogram() {
+    let counts = "abracadabra".chars().histogram();
+    assert_eq!(counts[&'a'], 5);
+    assert_eq!(counts[&'r'], 2);
+    assert_eq!(counts.len(), 5);
+
+    let parity = (1..=7).histogram_by(|n| n % 2);
+    assert_eq!(parity[&0], 3);
+    assert_eq!(parity[&1], 4);
+  }
+
+  #[test]
+  fn test_take_while_with_last() {
+    let taken: Vec<_> = [1, 2, 5, 3, 6]
+      .into_iter()
+      .take_while_with_last(|n| *n < 4)
+      .collect();
+    assert_eq!(taken, [1, 2, 5]);
+
+    let collatz: Vec<_> = std::iter::successors(Some(6u32), |n| {
+      Some(if n % 2 == 0 { n / 2 } else { 3 * n + 1 })
+    })
+    .take_while_with_last(|n| *n != 1)
+    .collect();
+    assert_eq!(collatz, [6, 3, 10, 5, 16, 8, 4, 2, 1]);
+  }
+
+  #[test]
+  fn test_extrema() {
+    assert_eq!([3, 1, 4, 1, 5].into_iter().min_max(), Some((1, 5)));
+    assert_eq!([7].into_iter().min_max(), Some((7, 7)));
+    assert_eq!(std::iter::empty::<u8>().min_max(), None);
+
+    assert_eq!(
+      [3, 9, 4, 9, 1].into_iter().max_with_position(),
+      Some((9, 1))
+    );
+    assert_eq!(
+      [3, 1, 4, 1, 5].into_iter().min_with_position(),
+      Some((1, 1))
+    );
+    assert_eq!(std::iter::empty::<u8>().max_with_position(), None);
+  }
+
+  #[test]
+  fn test_try_collect_all() {
+    let nums: Result<Vec<u8>, _> = "1 2 3".split(' ').map(str::parse).try_collect_all();
+    assert_eq!(nums.unwrap(), [1, 2, 3]);
+
+    let errors = "1 x 3 300"
+      .split(' ')
+      .map(str::parse::<u8>)
+      .try_collect_all::<Vec<_>>()
+      .unwrap_err();
+    assert_eq!(errors.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [1, 3]);
+  }
+
+  #[test]
+  fn test_runs() {
+    let runs: Vec<_> = "1112211".chars().runs().collect();
+    assert_eq!(runs, [('1', 3), ('2', 2), ('1', 2)]);
+    assert_eq!(
+      runs.into_iter().expand_runs().collect::<String>(),
+      "1112211"
+    );
+
+    // look-and-say
+    let next: String = "1211"
+      .chars()
+      .runs()
+      .map(|(digit, count)| format!("{count}{digit}"))
+      .collect();
+    assert_eq!(next, "111221");
+  }
+
+  #[test]
+  fn test_stats() {
+    let stats = [5u32, 1, 4, 2].into_iter().stats().unwrap();
+    assert_eq!(
+      stats,
+      Stats {
+        count: 4,
+        sum: 12,
+        mean: 3.0,
+        min: 1,
+        max: 5,
+        median: 3.0
+      }
+    );
+
+    let stats = [2.5, -1.0, 7.0].into_iter().stats().unwrap();
+    assert_eq!((stats.min, stats.max, stats.median), (-1.0, 7.0, 2.5));
+    assert_eq!(std::iter::empty::<i32>().stats(), None);
+  }
+
+  #[test]
+  fn test_find_cycle() {
+    // 0, 1, 2, 3, 4, 2, 3, 4, ...
+    let states = std::iter::successors(Some(0), |x| Some(if *x < 4 { x + 1 } else { 2 }));
+    assert_eq!(states.clone().find_cycle_by(|x| *x), Some((2, 3)));
+    assert_eq!(states.clone().state_after(1, |x| *x), Some(1));
+    assert_eq!(states.clone().state_after(1_000_000_000, |x| *x), Some(4));
+
+    let lcg = std::iter::successors(Some(7u64), |x| Some((x * x + 1) % 1000));
+    let (start, length) = lcg.clone().find_cycle_by(|x| *x).unwrap();
+    let items: Vec<_> = lcg.take(start + 2 * length).collect();
+    assert_eq!(items[start], items[start + length]);
+    assert!(items[..start + length].iter().unique().count() == start + length);
+
+    assert_eq!((0..10).find_cycle_by(|x| *x), None);
+    assert_eq!((0..10).state_after(5, |x| *x), Some(5));
   }
 }
@@ -1,4 +1,5 @@
 pub mod iterator;
 pub mod numbers;
+pub mod string;
 
 pub mod duration;
@@ -1,5 +1,7 @@
 use std::cmp::Ordering;
 
+use crate::prime;
+
 pub trait UnsignedExt {
   fn ratio(self, denominator: Self) -> f64;
 
@@ -16,38 +18,306 @@ pub trait UnsignedExt {
   ///
   /// Computing this number involves numbers much larger than the result (although not as large as by the naive factorial formula). For large inputs, this algorithm may result in overflow, even if the theoretical result would fit in the type.
   fn choose(self, num: Self) -> Self;
+
+  /// Computes `self^exp mod modulus` by repeated squaring, see `prime::power_mod`.
+  /// Panics if `modulus` is 0.
+  fn modpow(self, exp: Self, modulus: Self) -> Self;
+
+  /// The number of decimal digits, where 0 has a single digit.
+  fn num_digits(self) -> u32;
+
+  /// The decimal digits, most significant first.
+  fn digits(self) -> impl Iterator<Item = u8>;
+
+  /// The decimal digits, least significant first.
+  fn digits_rev(self) -> impl Iterator<Item = u8>;
+
+  /// Builds a number from decimal digits, most significant first. Overflows on too many digits.
+  fn from_digits(digits: impl IntoIterator<Item = u8>) -> Self;
+
+  /// Whether the number is the square of an integer, using `isqrt` from std.
+  #[allow(clippy::wrong_self_convention)]
+  fn is_perfect_square(self) -> bool;
+
+  /// The integer cube root, rounded down.
+  fn icbrt(self) -> Self;
+
+  /// Whether the number is the cube of an integer.
+  #[allow(clippy::wrong_self_convention)]
+  fn is_perfect_cube(self) -> bool;
+}
+
+macro_rules! impl_unsigned {
+  ($type:ty) => {
+    impl UnsignedExt for $type {
+      fn choose(self, k: Self) -> Self {
+        let (num, den) = (0..k)
+          .map(|i| (self - i, i + 1))
+          .fold((1, 1), |(num, den), (n, d)| {
+            let num = num * n;
+            let den = den * d;
+            let gcd = num.gcd(den);
+            (num / gcd, den / gcd)
+          });
+        num / den
+      }
+
+      fn ratio(self, denominator: Self) -> f64 {
+        self as f64 / denominator as f64
+      }
+
+      fn gcd(self, rhs: Self) -> Self {
+        // Euclid's algorithm: https://en.wikipedia.org/wiki/Euclidean_algorithm
+        let mut a = self;
+        let mut b = rhs;
+        while b != 0 {
+          (a, b) = (b, a % b);
+        }
+        a
+      }
+
+      fn lcm(self, rhs: Self) -> Self {
+        self * (rhs / self.gcd(rhs))
+      }
+
+      fn modpow(self, exp: Self, modulus: Self) -> Self {
+        prime::power_mod(self, exp, modulus)
+      }
+
+      fn num_digits(self) -> u32 {
+        self.checked_ilog10().unwrap_or(0) + 1
+      }
+
+      fn digits(self) -> impl Iterator<Item = u8> {
+        (0..self.num_digits())
+          .rev()
+          .map(move |i| (self / (10 as $type).pow(i) % 10) as u8)
+      }
+
+      fn digits_rev(self) -> impl Iterator<Item = u8> {
+        let mut rest = Some(self);
+        std::iter::from_fn(move || {
+          let n = rest?;
+          rest = (n >= 10).then_some(n / 10);
+          Some((n % 10) as u8)
+        })
+      }
+
+      fn from_digits(digits: impl IntoIterator<Item = u8>) -> Self {
+        digits.into_iter().fold(0, |acc, d| acc * 10 + d as $type)
+      }
+
+      fn is_perfect_square(self) -> bool {
+        self.isqrt().pow(2) == self
+      }
+
+      fn icbrt(self) -> Self {
+        // start from the float estimate, then fix any rounding error
+        let mut root = (self as f64).cbrt() as $type;
+        while root.checked_pow(3).is_none_or(|cube| cube > self) {
+          root -= 1;
+        }
+        while (root + 1).checked_pow(3).is_some_and(|cube| cube <= self) {
+          root += 1;
+        }
+        root
+      }
+
+      fn is_perfect_cube(self) -> bool {
+        self.icbrt().pow(3) == self
+      }
+    }
+  };
+}
+
+impl_unsigned!(u8);
+impl_unsigned!(u16);
+impl_unsigned!(u32);
+impl_unsigned!(u64);
+impl_unsigned!(u128);
+impl_unsigned!(usize);
+
+pub trait SignedExt: Sized {
+  /// Computes the greatest common divisor of `Self` and `rhs`, which is never negative.
+  fn gcd(self, rhs: Self) -> Self;
+
+  /// Computes the lowest common multiple of `Self` and `rhs`, which is never negative.
+  /// Panics if both numbers are 0.
+  fn lcm(self, rhs: Self) -> Self;
+
+  /// The extended Euclidean algorithm, giving `(g, x, y)` such that `self * x + rhs * y == g`,
+  /// where `g` is the greatest common divisor.
+  fn egcd(self, rhs: Self) -> (Self, Self, Self);
+
+  /// Computes `self^exp mod modulus`, in the range `0..modulus` even for negative `self`.
+  /// Panics if `exp` is negative or `modulus` is not positive.
+  fn modpow(self, exp: Self, modulus: Self) -> Self;
+
+  /// The `x` in `0..modulus` such that `self * x` is 1 mod `modulus`,
+  /// or None if `self` and `modulus` are not coprime.
+  fn mod_inverse(self, modulus: Self) -> Option<Self>;
 }
 
-impl UnsignedExt for u64 {
-  fn choose(self, k: Self) -> Self {
-    let (num, den) = (0..k)
-      .map(|i| (self - i, i + 1))
-      .fold((1, 1), |(num, den), (n, d)| {
-        let num = num * n;
-        let den = den * d;
-        let gcd = num.gcd(den);
-        (num / gcd, den / gcd)
-      });
-    num / den
+macro_rules! impl_signed {
+  ($type:ty) => {
+    impl SignedExt for $type {
+      fn gcd(self, rhs: Self) -> Self {
+        self.egcd(rhs).0
+      }
+
+      fn lcm(self, rhs: Self) -> Self {
+        (self / self.gcd(rhs) * rhs).abs()
+      }
+
+      fn egcd(self, rhs: Self) -> (Self, Self, Self) {
+        // https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+        let (mut r0, mut r1) = (self, rhs);
+        let (mut x0, mut x1) = (1, 0);
+        let (mut y0, mut y1) = (0, 1);
+        while r1 != 0 {
+          let q = r0 / r1;
+          (r0, r1) = (r1, r0 - q * r1);
+          (x0, x1) = (x1, x0 - q * x1);
+          (y0, y1) = (y1, y0 - q * y1);
+        }
+        if r0 < 0 {
+          (-r0, -x0, -y0)
+        } else {
+          (r0, x0, y0)
+        }
+      }
+
+      fn modpow(self, exp: Self, modulus: Self) -> Self {
+        assert!(exp >= 0, "negative exponent");
+        assert!(modulus > 0, "modulus must be positive");
+        let base = self.rem_euclid(modulus) as u128;
+        base.modpow(exp as u128, modulus as u128) as $type
+      }
+
+      fn mod_inverse(self, modulus: Self) -> Option<Self> {
+        let (g, x, _) = self.egcd(modulus);
+        (g == 1).then(|| x.rem_euclid(modulus))
+      }
+    }
+  };
+}
+
+impl_signed!(i32);
+impl_signed!(i64);
+impl_signed!(i128);
+
+pub trait PrimeExt {
+  /// Deterministic for every `u64`, see `prime::is_prime`.
+  #[allow(clippy::wrong_self_convention)]
+  fn is_prime(self) -> bool;
+
+  /// The prime factorization as pairs of prime and exponent, in increasing order.
+  /// Uses trial division, so numbers with two large prime factors are slow.
+  fn prime_factors(self) -> Vec<(u64, u32)>;
+}
+
+impl PrimeExt for u64 {
+  fn is_prime(self) -> bool {
+    match self {
+      0 | 1 => false,
+      2 => true,
+      n if n.is_multiple_of(2) => false,
+      n => prime::is_prime(n),
+    }
   }
 
-  fn ratio(self, denominator: Self) -> f64 {
-    self as f64 / denominator as f64
+  fn prime_factors(self) -> Vec<(u64, u32)> {
+    let mut factors = vec![];
+    let mut n = self;
+    // stop early once the rest is prime, only checking when it changes
+    let mut rest_is_prime = n.is_prime();
+    let mut divisor = 2;
+    while !rest_is_prime && divisor * divisor <= n {
+      let mut exp = 0;
+      while n.is_multiple_of(divisor) {
+        n /= divisor;
+        exp += 1;
+      }
+      if exp > 0 {
+        factors.push((divisor, exp));
+        rest_is_prime = n.is_prime();
+      }
+      divisor += if divisor == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+      factors.push((n, 1));
+    }
+    factors
   }
+}
 
-  fn gcd(self, rhs: Self) -> Self {
-    // Euclid's algorithm: https://en.wikipedia.org/wiki/Euclidean_algorithm
-    let mut a = self;
-    let mut b = rhs;
-    while b != 0 {
-      (a, b) = (b, a % b);
+/// All primes up to and including `n`, using the sieve of Eratosthenes.
+pub fn primes_up_to(n: u64) -> Vec<u64> {
+  let n = n as usize;
+  let mut composite = vec![false; n + 1];
+  let mut primes = vec![];
+  for i in 2..=n {
+    if composite[i] {
+      continue;
+    }
+    primes.push(i as u64);
+    for multiple in (i * i..=n).step_by(i) {
+      composite[multiple] = true;
     }
-    a
   }
+  primes
+}
 
-  fn lcm(self, rhs: Self) -> Self {
-    self * (rhs / self.gcd(rhs))
+/// Solves `a * x + b * y = c` over the integers.
+/// Returns a solution `(x, y)` together with a step `(dx, dy)`,
+/// so that every solution is `(x + k * dx, y - k * dy)` for some integer `k`,
+/// or None if there are no solutions. Panics if `a` and `b` are both 0.
+pub fn linear_diophantine(a: i64, b: i64, c: i64) -> Option<((i64, i64), (i64, i64))> {
+  assert!(a != 0 || b != 0, "a and b cannot both be 0");
+  let (g, x, y) = (a as i128).egcd(b as i128);
+  let c = c as i128;
+  if c % g != 0 {
+    return None;
   }
+  let (x, y) = (x * (c / g), y * (c / g));
+  let (dx, dy) = (b as i128 / g, a as i128 / g);
+  // pick the solution with the smallest non-negative x, to keep the numbers small
+  let (x, y) = match dx {
+    0 => (x, y),
+    dx => {
+      let k = x.div_euclid(dx.abs()) * dx.signum();
+      (x - k * dx, y + k * dy)
+    }
+  };
+  Some((
+    (x.try_into().ok()?, y.try_into().ok()?),
+    (dx as i64, dy as i64),
+  ))
+}
+
+/// Solves a system of congruences `x = residue (mod modulus)` with the Chinese remainder theorem.
+/// Returns `(x, m)`, where every solution is `x` plus a multiple of `m`, the lcm of the moduli,
+/// or None if the congruences contradict each other, a modulus is 0, or the lcm overflows.
+/// The moduli do not have to be coprime.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+  // https://en.wikipedia.org/wiki/Chinese_remainder_theorem#Generalization_to_non-coprime_moduli
+  let (r, m) = congruences
+    .iter()
+    .try_fold((0i128, 1i128), |(r1, m1), &(r2, m2)| {
+      let (r2, m2) = (r2 as i128, m2 as i128);
+      if m2 == 0 {
+        return None;
+      }
+      let g = m1.gcd(m2);
+      if (r2 - r1) % g != 0 {
+        return None;
+      }
+      let step = m2 / g;
+      let k = ((r2 - r1) / g).rem_euclid(step) * (m1 / g).mod_inverse(step)? % step;
+      let m = m1.checked_mul(step)?;
+      Some((m1.checked_mul(k)?.checked_add(r1)?.rem_euclid(m), m))
+    })?;
+  Some((r.try_into().ok()?, m.try_into().ok()?))
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -99,28 +369,146 @@ mod tests {
 
   #[test]
   fn test_choose() {
-    assert_eq!(8.choose(4), 70);
-    assert_eq!(5.choose(1), 5);
-    assert_eq!(1.choose(1), 1);
-    assert_eq!(100.choose(15), 253338471349988640);
+    assert_eq!(8u64.choose(4), 70);
+    assert_eq!(5u64.choose(1), 5);
+    assert_eq!(1u64.choose(1), 1);
+    assert_eq!(100u64.choose(15), 253338471349988640);
+    assert_eq!(10u16.choose(3), 120);
   }
 
   #[test]
   fn test_gcd() {
-    assert_eq!(6.gcd(9), 3);
-    assert_eq!(1071.gcd(462), 21);
+    assert_eq!(6u64.gcd(9), 3);
+    assert_eq!(1071u64.gcd(462), 21);
+    assert_eq!(1071u16.gcd(462), 21);
+    assert_eq!(1071usize.gcd(462), 21);
   }
 
   #[test]
   fn test_lcm() {
-    assert_eq!(6.lcm(9), 18);
-    assert_eq!(4.lcm(20), 20);
-    assert_eq!(21.lcm(6), 42);
+    assert_eq!(6u64.lcm(9), 18);
+    assert_eq!(4u64.lcm(20), 20);
+    assert_eq!(21u64.lcm(6), 42);
+    assert_eq!(21u32.lcm(6), 42);
+    assert_eq!(21u128.lcm(6), 42);
+  }
+
+  #[test]
+  fn test_signed() {
+    assert_eq!((-6i64).gcd(9), 3);
+    assert_eq!(6i32.gcd(-9), 3);
+    assert_eq!(0i64.gcd(-4), 4);
+    assert_eq!((-6i128).lcm(9), 18);
+    assert_eq!(4i64.lcm(-6), 12);
+
+    for (a, b) in [(240i64, 46), (-240, 46), (17, -5), (0, 7)] {
+      let (g, x, y) = a.egcd(b);
+      assert_eq!(g, a.gcd(b));
+      assert_eq!(a * x + b * y, g);
+    }
+  }
+
+  #[test]
+  fn test_modpow() {
+    assert_eq!(4u64.modpow(13, 497), 445);
+    assert_eq!(7u32.modpow(0, 13), 1);
+    assert_eq!(5u8.modpow(3, 1), 0);
+    // the handshake from AoC 2020 day 25
+    assert_eq!(7u64.modpow(8, 20201227), 5764801);
+    assert_eq!(17807724u64.modpow(8, 20201227), 14897079);
+    assert_eq!(u64::MAX.modpow(u64::MAX, u64::MAX - 1), 1);
+    assert_eq!((u128::MAX - 1).modpow(2, u128::MAX), 1);
+    // Fermat's little theorem for the prime 2^127 - 1
+    let p = (1u128 << 127) - 1;
+    assert_eq!(3u128.modpow(p - 1, p), 1);
+    assert_eq!((-2i64).modpow(3, 5), 2);
+  }
+
+  #[test]
+  fn test_mod_inverse() {
+    assert_eq!(3i64.mod_inverse(11), Some(4));
+    assert_eq!((-3i64).mod_inverse(11), Some(7));
+    assert_eq!(6i32.mod_inverse(9), None);
+  }
+
+  #[test]
+  fn test_crt() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+    assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    assert_eq!(crt(&[]), Some((0, 1)));
+    assert_eq!(crt(&[(1, 4), (0, 0)]), None);
+    // the lcm of these does not fit in an i128
+    let moduli = [i64::MAX, i64::MAX - 1, i64::MAX - 2];
+    assert_eq!(crt(&moduli.map(|m| (1, m))), None);
+    // the bus schedule from AoC 2020 day 13: 7,13,x,x,59,x,31,19
+    let buses = [(0, 7), (1, 13), (4, 59), (6, 31), (7, 19)];
+    let congruences: Vec<_> = buses.iter().map(|&(i, m)| (-i, m)).collect();
+    assert_eq!(crt(&congruences).map(|(x, _)| x), Some(1068781));
+  }
+
+  #[test]
+  fn test_primes() {
+    assert_eq!(primes_up_to(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    assert!(primes_up_to(1).is_empty());
+    let primes = primes_up_to(1000);
+    assert!((0..=1000u64).all(|n| n.is_prime() == primes.contains(&n)));
+  }
+
+  #[test]
+  fn test_prime_factors() {
+    assert_eq!(360u64.prime_factors(), [(2, 3), (3, 2), (5, 1)]);
+    assert_eq!(97u64.prime_factors(), [(97, 1)]);
+    assert!(1u64.prime_factors().is_empty());
+    assert_eq!(
+      (2u64.pow(40) * 1_000_003).prime_factors(),
+      [(2, 40), (1_000_003, 1)]
+    );
+  }
+
+  #[test]
+  fn test_linear_diophantine() {
+    let ((x, y), (dx, dy)) = linear_diophantine(94, 22, 8400).unwrap();
+    for k in -3..3 {
+      assert_eq!(94 * (x + k * dx) + 22 * (y - k * dy), 8400);
+    }
+    assert!((0..dx.abs()).contains(&x));
+    assert_eq!(linear_diophantine(4, 6, 5), None);
+    assert_eq!(linear_diophantine(-3, 0, 9), Some(((-3, 0), (0, -1))));
+  }
+
+  #[test]
+  fn test_digits() {
+    assert_eq!(1234u32.digits().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert_eq!(1234u32.digits_rev().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    assert_eq!(0u8.digits().collect::<Vec<_>>(), [0]);
+    assert_eq!(0u8.digits_rev().collect::<Vec<_>>(), [0]);
+    assert_eq!(u64::MAX.num_digits(), 20);
+    assert_eq!(9u64.num_digits(), 1);
+    assert_eq!(10u64.num_digits(), 2);
+    assert_eq!(u64::from_digits([1, 0, 2, 4]), 1024);
+    assert_eq!(u128::from_digits(u128::MAX.digits()), u128::MAX);
+  }
+
+  #[test]
+  fn test_roots() {
+    assert!(49u64.is_perfect_square());
+    assert!(0u8.is_perfect_square());
+    assert!(!50u64.is_perfect_square());
+    assert!((u32::MAX as u64).pow(2).is_perfect_square());
+
+    assert_eq!(26u32.icbrt(), 2);
+    assert_eq!(27u32.icbrt(), 3);
+    assert_eq!(u8::MAX.icbrt(), 6);
+    assert_eq!(u64::MAX.icbrt(), 2642245);
+    assert_eq!(u128::MAX.icbrt(), 6981463658331);
+    assert!(1_000_000_000u64.is_perfect_cube());
+    assert!(!999_999_999u64.is_perfect_cube());
   }
 
   #[test]
   fn test_ratio() {
-    assert_eq!(1.ratio(2), 0.5);
-    assert_eq!(2.ratio(5), 0.4);
+    assert_eq!(1u64.ratio(2), 0.5);
+    assert_eq!(2u8.ratio(5), 0.4);
   }
 }
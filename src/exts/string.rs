@@ -0,0 +1,166 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{error::AocError, parse::parse_at};
+
+pub trait StrExt {
+  /// Every integer in the string, e.g. `"x=-3, y=12"` gives `[-3, 12]`.
+  /// A `-` is only a sign when it does not follow a letter or digit, so `"3-5"` gives `[3, 5]`.
+  /// Fails with the line of the first number that does not fit in `T`.
+  fn ints<T>(&self) -> Result<Vec<T>, AocError>
+  where
+    T: FromStr,
+    T::Err: Display;
+
+  /// Every run of digits in the string, ignoring signs.
+  fn uints<T>(&self) -> Result<Vec<T>, AocError>
+  where
+    T: FromStr,
+    T::Err: Display;
+
+  /// Every number in the string, with an optional sign and fraction.
+  fn floats<T>(&self) -> Result<Vec<T>, AocError>
+  where
+    T: FromStr,
+    T::Err: Display;
+
+  /// The chunks of the string that are separated by blank lines, without their trailing newline.
+  fn blocks(&self) -> impl Iterator<Item = &str>;
+
+  /// Parses every block, stopping at the first error.
+  fn parse_blocks<T: FromStr>(&self) -> Result<Vec<T>, T::Err> {
+    self.blocks().map(str::parse).collect()
+  }
+}
+
+impl StrExt for str {
+  fn ints<T>(&self) -> Result<Vec<T>, AocError>
+  where
+    T: FromStr,
+    T::Err: Display,
+  {
+    parse_all(self, scan_numbers(self, true, false))
+  }
+
+  fn uints<T>(&self) -> Result<Vec<T>, AocError>
+  where
+    T: FromStr,
+    T::Err: Display,
+  {
+    parse_all(self, scan_numbers(self, false, false))
+  }
+
+  fn floats<T>(&self) -> Result<Vec<T>, AocError>
+  where
+    T: FromStr,
+    T::Err: Display,
+  {
+    parse_all(self, scan_numbers(self, true, true))
+  }
+
+  fn blocks(&self) -> impl Iterator<Item = &str> {
+    let mut lines = self.split_inclusive('\n');
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+      // skip blank lines before the block
+      let mut start = pos;
+      let mut end = pos;
+      for line in lines.by_ref() {
+        pos += line.len();
+        if line.trim().is_empty() {
+          if start == end {
+            start = pos;
+            end = pos;
+            continue;
+          }
+          break;
+        }
+        end = pos;
+      }
+      (start != end).then(|| self[start..end].trim_end_matches(['\n', '\r']))
+    })
+  }
+}
+
+/// Parses `numbers`, which are slices of `s`.
+fn parse_all<'a, T>(s: &str, numbers: impl Iterator<Item = &'a str>) -> Result<Vec<T>, AocError>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  numbers.map(|n| parse_at(s, n)).collect()
+}
+
+/// Finds the numbers in `s`, as slices of it.
+fn scan_numbers(s: &str, signed: bool, fractional: bool) -> impl Iterator<Item = &str> {
+  let bytes = s.as_bytes();
+  let digits_from = |mut i: usize| {
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+      i += 1;
+    }
+    i
+  };
+  let mut pos = 0;
+  std::iter::from_fn(move || {
+    let start = (pos..bytes.len()).find(|&i| bytes[i].is_ascii_digit())?;
+    let sign = signed
+      && start > 0
+      && bytes[start - 1] == b'-'
+      && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric());
+    let mut end = digits_from(start);
+    if fractional
+      && bytes.get(end) == Some(&b'.')
+      && bytes.get(end + 1).is_some_and(u8::is_ascii_digit)
+    {
+      end = digits_from(end + 1);
+    }
+    pos = end;
+    Some(&s[start - sign as usize..end])
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_ints() {
+    assert_eq!("p=0,4 v=-3,-12".ints::<i32>().unwrap(), [0, 4, -3, -12]);
+    assert_eq!("Game 12: 3-5, x-7".ints::<i64>().unwrap(), [12, 3, 5, 7]);
+    assert_eq!("-1 - -2".uints::<u8>().unwrap(), [1, 2]);
+    assert!("no numbers".ints::<i32>().unwrap().is_empty());
+  }
+
+  #[test]
+  fn test_floats() {
+    assert_eq!(
+      "at -1.5, 2 and 3.25.".floats::<f64>().unwrap(),
+      [-1.5, 2.0, 3.25]
+    );
+  }
+
+  #[test]
+  fn test_blocks() {
+    let text = "\na\nb\n\n\nc\r\n\r\nd\n";
+    assert_eq!(text.blocks().collect::<Vec<_>>(), ["a\nb", "c", "d"]);
+    assert_eq!("".blocks().count(), 0);
+
+    let sums: Vec<u32> = "1 2\n\n3"
+      .blocks()
+      .map(|block| block.uints::<u32>().unwrap().iter().sum())
+      .collect();
+    assert_eq!(sums, [3, 3]);
+    assert_eq!("1\n\n2".parse_blocks::<u8>().unwrap(), [1, 2]);
+    assert!("1\n\nx".parse_blocks::<u8>().is_err());
+  }
+
+  #[test]
+  fn test_overflow() {
+    assert_eq!(
+      "1\n2 300".uints::<u8>(),
+      Err(AocError::parse(
+        2,
+        "number too large to fit in target type: \"300\""
+      ))
+    );
+  }
+}
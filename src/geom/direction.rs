@@ -0,0 +1,75 @@
+use crate::vex::Vex;
+
+/// One of the four directions on a grid, where y grows downwards like rows of text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+  Up,
+  Right,
+  Down,
+  Left,
+}
+
+impl Direction {
+  /// In clockwise order, starting up.
+  pub const ALL: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+  ];
+
+  /// The change in position of a step in this direction.
+  pub fn delta(self) -> Vex<i64, 2> {
+    match self {
+      Direction::Up => Vex([0, -1]),
+      Direction::Right => Vex([1, 0]),
+      Direction::Down => Vex([0, 1]),
+      Direction::Left => Vex([-1, 0]),
+    }
+  }
+
+  pub fn turn_right(self) -> Self {
+    Self::ALL[(self as usize + 1) % 4]
+  }
+
+  pub fn turn_left(self) -> Self {
+    Self::ALL[(self as usize + 3) % 4]
+  }
+
+  pub fn reverse(self) -> Self {
+    Self::ALL[(self as usize + 2) % 4]
+  }
+}
+
+impl TryFrom<char> for Direction {
+  type Error = anyhow::Error;
+
+  /// Parses `^>v<` or `URDL`.
+  fn try_from(c: char) -> Result<Self, Self::Error> {
+    Ok(match c {
+      '^' | 'U' => Direction::Up,
+      '>' | 'R' => Direction::Right,
+      'v' | 'D' => Direction::Down,
+      '<' | 'L' => Direction::Left,
+      _ => anyhow::bail!("not a direction: {c:?}"),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_turns() {
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    assert_eq!(Direction::Left.reverse(), Direction::Right);
+    for dir in Direction::ALL {
+      assert_eq!(dir.turn_left().turn_right(), dir);
+      assert_eq!(dir.delta() + dir.reverse().delta(), Vex([0, 0]));
+    }
+    assert_eq!(Direction::try_from('v').unwrap(), Direction::Down);
+    assert!(Direction::try_from('x').is_err());
+  }
+}
@@ -0,0 +1,10 @@
+pub mod direction;
+pub mod polygon;
+pub mod rect;
+pub mod segment;
+pub mod walker;
+
+pub use direction::Direction;
+pub use rect::Rect;
+pub use segment::{Intersection, segment_intersection};
+pub use walker::Walker;
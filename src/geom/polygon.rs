@@ -0,0 +1,230 @@
+use crate::{exts::numbers::SignedExt, geom::rect::Rect, vex::Vex};
+
+/// Where a point is relative to a polygon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+  Inside,
+  Boundary,
+  Outside,
+}
+
+/// A simple polygon with integer vertices, in order around it and closed back to the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polygon {
+  vertices: Vec<Vex<i64, 2>>,
+}
+
+fn cross(a: Vex<i64, 2>, b: Vex<i64, 2>) -> i64 {
+  a.x() * b.y() - a.y() * b.x()
+}
+
+impl Polygon {
+  pub fn new(vertices: Vec<Vex<i64, 2>>) -> Self {
+    Self { vertices }
+  }
+
+  pub fn vertices(&self) -> &[Vex<i64, 2>] {
+    &self.vertices
+  }
+
+  /// Each edge as its two ends, including the one closing the polygon.
+  pub fn edges(&self) -> impl Iterator<Item = (Vex<i64, 2>, Vex<i64, 2>)> {
+    let next = self.vertices.iter().cycle().skip(1);
+    self.vertices.iter().copied().zip(next.copied())
+  }
+
+  /// True if every edge is horizontal or vertical.
+  pub fn is_rectilinear(&self) -> bool {
+    self.edges().all(|(a, b)| a.x() == b.x() || a.y() == b.y())
+  }
+
+  /// Twice the area, which is always an integer, by the shoelace formula.
+  /// https://en.wikipedia.org/wiki/Shoelace_formula
+  pub fn doubled_area(&self) -> u64 {
+    self
+      .edges()
+      .map(|(a, b)| cross(a, b))
+      .sum::<i64>()
+      .unsigned_abs()
+  }
+
+  pub fn area(&self) -> f64 {
+    self.doubled_area() as f64 / 2.0
+  }
+
+  /// The number of integer points on the edges.
+  pub fn boundary_points(&self) -> u64 {
+    self
+      .edges()
+      .map(|(a, b)| (b.x() - a.x()).gcd(b.y() - a.y()).unsigned_abs())
+      .sum()
+  }
+
+  /// The number of integer points strictly inside, by Pick's theorem `A = i + b/2 - 1`.
+  /// https://en.wikipedia.org/wiki/Pick%27s_theorem
+  pub fn interior_points(&self) -> u64 {
+    (self.doubled_area() + 2 - self.boundary_points()) / 2
+  }
+
+  /// The number of integer points inside or on the edges, which is the number of cells
+  /// covered when the vertices are the centers of cells.
+  pub fn lattice_points(&self) -> u64 {
+    self.interior_points() + self.boundary_points()
+  }
+
+  pub fn locate(&self, point: Vex<i64, 2>) -> Location {
+    self.locate_doubled(point * 2)
+  }
+
+  /// True if the point is inside or on the edges.
+  pub fn contains(&self, point: Vex<i64, 2>) -> bool {
+    self.locate(point) != Location::Outside
+  }
+
+  /// Locates a point given in half units, so that midpoints can be tested exactly.
+  fn locate_doubled(&self, point: Vex<i64, 2>) -> Location {
+    let mut inside = false;
+    for (a, b) in self.edges() {
+      let (a, b) = (a * 2, b * 2);
+      let side = cross(b - a, point - a);
+      let Rect { min, max } = Rect::new(a, b);
+      if side == 0
+        && min.x() <= point.x()
+        && point.x() <= max.x()
+        && min.y() <= point.y()
+        && point.y() <= max.y()
+      {
+        return Location::Boundary;
+      }
+      // cast a ray to the right, counting the edges it crosses
+      // https://en.wikipedia.org/wiki/Point_in_polygon#Ray_casting_algorithm
+      if (a.y() > point.y()) != (b.y() > point.y()) && (side > 0) == (b.y() > a.y()) {
+        inside = !inside;
+      }
+    }
+    if inside {
+      Location::Inside
+    } else {
+      Location::Outside
+    }
+  }
+
+  /// True if the axis-aligned rectangle with opposite corners `a` and `b` is inside or on the
+  /// edges. The polygon must be rectilinear.
+  pub fn contains_rect(&self, a: Vex<i64, 2>, b: Vex<i64, 2>) -> bool {
+    debug_assert!(self.is_rectilinear(), "polygon is not rectilinear");
+    let Rect { min, max } = Rect::new(a, b);
+    if min.x() == max.x() || min.y() == max.y() {
+      return self.contains_segment(min, max);
+    }
+    // with no edge through the inside of the rectangle, it is all inside or all outside
+    let crosses = self.edges().any(|(p, q)| {
+      let Rect { min: lo, max: hi } = Rect::new(p, q);
+      if lo.x() == hi.x() {
+        min.x() < lo.x() && lo.x() < max.x() && lo.y().max(min.y()) < hi.y().min(max.y())
+      } else {
+        min.y() < lo.y() && lo.y() < max.y() && lo.x().max(min.x()) < hi.x().min(max.x())
+      }
+    });
+    !crosses && self.locate_doubled(min + max) != Location::Outside
+  }
+
+  /// True if the horizontal or vertical segment from `min` to `max` is inside or on the edges.
+  fn contains_segment(&self, min: Vex<i64, 2>, max: Vex<i64, 2>) -> bool {
+    // along the segment, the location can only change at the coordinates of vertices
+    let mut stops: Vec<i64> = if min.y() == max.y() {
+      self.vertices.iter().map(|v| v.x()).collect()
+    } else {
+      self.vertices.iter().map(|v| v.y()).collect()
+    };
+    let (start, end) = if min.y() == max.y() {
+      (min.x(), max.x())
+    } else {
+      (min.y(), max.y())
+    };
+    stops.retain(|s| start < *s && *s < end);
+    stops.extend([start, end]);
+    stops.sort_unstable();
+    stops.dedup();
+    let at = |doubled: i64| {
+      if min.y() == max.y() {
+        Vex([doubled, min.y() * 2])
+      } else {
+        Vex([min.x() * 2, doubled])
+      }
+    };
+    let stops_inside = stops
+      .iter()
+      .all(|s| self.locate_doubled(at(s * 2)) != Location::Outside);
+    stops_inside
+      && stops
+        .windows(2)
+        .all(|w| self.locate_doubled(at(w[0] + w[1])) != Location::Outside)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn polygon(points: &[(i64, i64)]) -> Polygon {
+    Polygon::new(points.iter().map(|&(x, y)| Vex([x, y])).collect())
+  }
+
+  fn v(x: i64, y: i64) -> Vex<i64, 2> {
+    Vex([x, y])
+  }
+
+  #[test]
+  fn test_area() {
+    let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+    assert_eq!(square.doubled_area(), 32);
+    assert_eq!(square.boundary_points(), 16);
+    assert_eq!(square.interior_points(), 9);
+    assert_eq!(square.lattice_points(), 25);
+
+    // the winding direction does not matter
+    let triangle = polygon(&[(0, 0), (0, 3), (3, 0)]);
+    assert_eq!(triangle.area(), 4.5);
+    assert_eq!(triangle.boundary_points(), 9);
+    assert_eq!(triangle.interior_points(), 1);
+    assert!(!triangle.is_rectilinear());
+  }
+
+  #[test]
+  fn test_locate() {
+    let triangle = polygon(&[(0, 0), (4, 0), (0, 4)]);
+    assert_eq!(triangle.locate(v(1, 1)), Location::Inside);
+    assert_eq!(triangle.locate(v(2, 2)), Location::Boundary);
+    assert_eq!(triangle.locate(v(0, 3)), Location::Boundary);
+    assert_eq!(triangle.locate(v(3, 3)), Location::Outside);
+    assert_eq!(triangle.locate(v(-1, 0)), Location::Outside);
+    // a ray through a vertex is only counted once
+    assert_eq!(triangle.locate(v(-1, 4)), Location::Outside);
+  }
+
+  #[test]
+  fn test_contains_rect() {
+    // a U shape, open at the top
+    let u = polygon(&[
+      (0, 0),
+      (6, 0),
+      (6, 4),
+      (4, 4),
+      (4, 2),
+      (2, 2),
+      (2, 4),
+      (0, 4),
+    ]);
+    assert!(u.is_rectilinear());
+    assert!(u.contains_rect(v(0, 0), v(6, 2)));
+    assert!(u.contains_rect(v(4, 4), v(6, 0)));
+    assert!(!u.contains_rect(v(0, 0), v(6, 3)));
+    assert!(!u.contains_rect(v(2, 2), v(4, 4)));
+    // segments along the edges and across the gap
+    assert!(u.contains_rect(v(2, 2), v(4, 2)));
+    assert!(u.contains_rect(v(0, 4), v(0, 0)));
+    assert!(!u.contains_rect(v(0, 3), v(6, 3)));
+    assert!(!u.contains_rect(v(2, 4), v(4, 4)));
+  }
+}
@@ -0,0 +1,115 @@
+use num_traits::Num;
+
+use crate::vex::Vex;
+
+/// An axis-aligned rectangle, covering `min.x..max.x` and `min.y..max.y`.
+/// With integers, these are the cells from `min` up to but not including `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect<T> {
+  pub min: Vex<T, 2>,
+  pub max: Vex<T, 2>,
+}
+
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+  if b < a { b } else { a }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+  if b > a { b } else { a }
+}
+
+impl<T: Copy + Num + PartialOrd> Rect<T> {
+  /// The rectangle between two opposite corners, in any order.
+  pub fn new(a: Vex<T, 2>, b: Vex<T, 2>) -> Self {
+    Self {
+      min: Vex([min(a.x(), b.x()), min(a.y(), b.y())]),
+      max: Vex([max(a.x(), b.x()), max(a.y(), b.y())]),
+    }
+  }
+
+  /// The rectangle starting at `min` and extending `width` right and `height` down.
+  pub fn from_size(min: Vex<T, 2>, width: T, height: T) -> Self {
+    Self::new(min, Vex([min.x() + width, min.y() + height]))
+  }
+
+  pub fn width(&self) -> T {
+    self.max.x() - self.min.x()
+  }
+
+  pub fn height(&self) -> T {
+    self.max.y() - self.min.y()
+  }
+
+  pub fn area(&self) -> T {
+    self.width() * self.height()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.width() == T::zero() || self.height() == T::zero()
+  }
+
+  pub fn contains(&self, point: Vex<T, 2>) -> bool {
+    self.min.x() <= point.x()
+      && point.x() < self.max.x()
+      && self.min.y() <= point.y()
+      && point.y() < self.max.y()
+  }
+
+  /// True if all of `other` is inside this rectangle.
+  pub fn contains_rect(&self, other: &Self) -> bool {
+    self.min.x() <= other.min.x()
+      && other.max.x() <= self.max.x()
+      && self.min.y() <= other.min.y()
+      && other.max.y() <= self.max.y()
+  }
+
+  /// The rectangle covered by both, or None if they do not overlap.
+  /// Rectangles that only share an edge do not overlap.
+  pub fn overlap(&self, other: &Self) -> Option<Self> {
+    let lo = Vex([
+      max(self.min.x(), other.min.x()),
+      max(self.min.y(), other.min.y()),
+    ]);
+    let hi = Vex([
+      min(self.max.x(), other.max.x()),
+      min(self.max.y(), other.max.y()),
+    ]);
+    (lo.x() < hi.x() && lo.y() < hi.y()).then_some(Self { min: lo, max: hi })
+  }
+
+  pub fn overlaps(&self, other: &Self) -> bool {
+    self.overlap(other).is_some()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_claims() {
+    // fabric claims: #1 @ 1,3: 4x4, #2 @ 3,1: 4x4, #3 @ 5,5: 2x2
+    let one = Rect::from_size(Vex([1, 3]), 4, 4);
+    let two = Rect::from_size(Vex([3, 1]), 4, 4);
+    let three = Rect::from_size(Vex([5, 5]), 2, 2);
+    assert_eq!(one.area(), 16);
+    assert_eq!(one.overlap(&two), Some(Rect::new(Vex([3, 3]), Vex([5, 5]))));
+    assert_eq!(one.overlap(&two).unwrap().area(), 4);
+    // sharing an edge is not overlapping
+    assert!(!one.overlaps(&three));
+    assert!(!two.overlaps(&three));
+
+    assert!(one.contains(Vex([1, 3])));
+    assert!(!one.contains(Vex([5, 3])));
+    assert!(one.contains_rect(&Rect::new(Vex([2, 4]), Vex([5, 7]))));
+    assert!(!one.contains_rect(&two));
+  }
+
+  #[test]
+  fn test_float() {
+    let a = Rect::new(Vex([1.0, 1.0]), Vex([0.0, 0.0]));
+    let b = Rect::new(Vex([0.5, 0.5]), Vex([2.0, 2.0]));
+    assert_eq!(a.overlap(&b).unwrap().area(), 0.25);
+    assert!(Rect::new(Vex([0.0, 1.0]), Vex([3.0, 1.0])).is_empty());
+  }
+}
@@ -0,0 +1,126 @@
+use num_traits::{Signed, ToPrimitive};
+
+use crate::vex::Vex;
+
+/// Where two segments meet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Intersection<T> {
+  /// At a single point, which may be between integer coordinates.
+  Point(Vex<f64, 2>),
+  /// Along the segment between these points, as the segments are on the same line.
+  Overlap(Vex<T, 2>, Vex<T, 2>),
+}
+
+fn cross<T: Copy + Signed>(a: Vex<T, 2>, b: Vex<T, 2>) -> T {
+  a.x() * b.y() - a.y() * b.x()
+}
+
+fn to_f64<T: ToPrimitive>(point: Vex<T, 2>) -> Vex<f64, 2> {
+  Vex(point.0.map(|c| c.to_f64().unwrap()))
+}
+
+/// Where the segments from `a.0` to `a.1` and from `b.0` to `b.1` meet, including their ends,
+/// or None if they do not. Whether they meet is decided exactly for integers.
+pub fn segment_intersection<T>(
+  a: (Vex<T, 2>, Vex<T, 2>),
+  b: (Vex<T, 2>, Vex<T, 2>),
+) -> Option<Intersection<T>>
+where
+  T: Copy + Signed + PartialOrd + ToPrimitive,
+{
+  let r = a.1 - a.0;
+  let s = b.1 - b.0;
+  let between = b.0 - a.0;
+  let mut denom = cross(r, s);
+  if denom.is_zero() {
+    if !cross(between, r).is_zero() || !cross(between, s).is_zero() {
+      // parallel, on different lines
+      return None;
+    }
+    // on the same line, where ordering by x then y is ordering along the line
+    let key = |p: &Vex<T, 2>| (p.x(), p.y());
+    let ordered = |(p, q): (Vex<T, 2>, Vex<T, 2>)| if key(&q) < key(&p) { (q, p) } else { (p, q) };
+    let ((a_lo, a_hi), (b_lo, b_hi)) = (ordered(a), ordered(b));
+    let lo = if key(&b_lo) > key(&a_lo) { b_lo } else { a_lo };
+    let hi = if key(&b_hi) < key(&a_hi) { b_hi } else { a_hi };
+    return if key(&lo) > key(&hi) {
+      None
+    } else if key(&lo) == key(&hi) {
+      Some(Intersection::Point(to_f64(lo)))
+    } else {
+      Some(Intersection::Overlap(lo, hi))
+    };
+  }
+  // a.0 + t * r == b.0 + u * s, with t and u scaled by denom to stay exact
+  let mut t = cross(between, s);
+  let mut u = cross(between, r);
+  if denom.is_negative() {
+    (denom, t, u) = (-denom, -t, -u);
+  }
+  let zero = T::zero();
+  if t < zero || t > denom || u < zero || u > denom {
+    return None;
+  }
+  let t = t.to_f64().unwrap() / denom.to_f64().unwrap();
+  Some(Intersection::Point(to_f64(a.0) + to_f64(r) * t))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn seg(x0: i64, y0: i64, x1: i64, y1: i64) -> (Vex<i64, 2>, Vex<i64, 2>) {
+    (Vex([x0, y0]), Vex([x1, y1]))
+  }
+
+  #[test]
+  fn test_crossing() {
+    assert_eq!(
+      segment_intersection(seg(0, 0, 4, 4), seg(0, 4, 4, 0)),
+      Some(Intersection::Point(Vex([2.0, 2.0])))
+    );
+    // between integer coordinates
+    assert_eq!(
+      segment_intersection(seg(0, 0, 1, 1), seg(0, 1, 1, 0)),
+      Some(Intersection::Point(Vex([0.5, 0.5])))
+    );
+    // touching at an end
+    assert_eq!(
+      segment_intersection(seg(0, 0, 2, 0), seg(2, 0, 2, 5)),
+      Some(Intersection::Point(Vex([2.0, 0.0])))
+    );
+    assert_eq!(
+      segment_intersection(seg(0, 0, 2, 0), seg(3, -1, 3, 5)),
+      None
+    );
+    assert_eq!(segment_intersection(seg(0, 0, 4, 4), seg(0, 1, 4, 5)), None);
+  }
+
+  #[test]
+  fn test_collinear() {
+    assert_eq!(
+      segment_intersection(seg(0, 0, 4, 0), seg(6, 0, 2, 0)),
+      Some(Intersection::Overlap(Vex([2, 0]), Vex([4, 0])))
+    );
+    assert_eq!(
+      segment_intersection(seg(0, 0, 0, 4), seg(0, 4, 0, 8)),
+      Some(Intersection::Point(Vex([0.0, 4.0])))
+    );
+    assert_eq!(segment_intersection(seg(0, 0, 1, 1), seg(2, 2, 3, 3)), None);
+    // a single point on a segment
+    assert_eq!(
+      segment_intersection(seg(1, 1, 1, 1), seg(0, 0, 2, 2)),
+      Some(Intersection::Point(Vex([1.0, 1.0])))
+    );
+  }
+
+  #[test]
+  fn test_float() {
+    let a = (Vex([0.0, 0.0]), Vex([1.0, 2.0]));
+    let b = (Vex([0.0, 2.0]), Vex([1.0, 0.0]));
+    assert_eq!(
+      segment_intersection(a, b),
+      Some(Intersection::Point(Vex([0.5, 1.0])))
+    );
+  }
+}
@@ -0,0 +1,106 @@
+use crate::{geom::direction::Direction, vex::Vex};
+
+/// A position and the direction it faces, for patrols and turtle graphics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Walker {
+  position: Vex<i64, 2>,
+  direction: Direction,
+  /// Every position visited, in order, if it is being recorded.
+  trail: Option<Vec<Vex<i64, 2>>>,
+}
+
+impl Walker {
+  pub fn new(position: Vex<i64, 2>, direction: Direction) -> Self {
+    Self {
+      position,
+      direction,
+      trail: None,
+    }
+  }
+
+  /// Starts recording the positions visited, beginning with the current one.
+  pub fn with_trail(mut self) -> Self {
+    self.trail = Some(vec![self.position]);
+    self
+  }
+
+  pub fn position(&self) -> Vex<i64, 2> {
+    self.position
+  }
+
+  /// The position as grid coordinates, or None if it is off the top or left.
+  pub fn grid_position(&self) -> Option<(usize, usize)> {
+    Some((
+      self.position.x().try_into().ok()?,
+      self.position.y().try_into().ok()?,
+    ))
+  }
+
+  pub fn direction(&self) -> Direction {
+    self.direction
+  }
+
+  /// The position one step ahead, without moving.
+  pub fn ahead(&self) -> Vex<i64, 2> {
+    self.position + self.direction.delta()
+  }
+
+  /// Moves one step forward.
+  pub fn step(&mut self) {
+    self.forward(1);
+  }
+
+  /// Moves `n` steps forward, recording each of them in the trail.
+  pub fn forward(&mut self, n: u64) {
+    for _ in 0..n {
+      self.position += self.direction.delta();
+      if let Some(trail) = &mut self.trail {
+        trail.push(self.position);
+      }
+    }
+  }
+
+  pub fn turn_left(&mut self) {
+    self.direction = self.direction.turn_left();
+  }
+
+  pub fn turn_right(&mut self) {
+    self.direction = self.direction.turn_right();
+  }
+
+  pub fn turn_around(&mut self) {
+    self.direction = self.direction.reverse();
+  }
+
+  /// The positions visited since `with_trail`, which is empty if it was never called.
+  pub fn trail(&self) -> &[Vex<i64, 2>] {
+    self.trail.as_deref().unwrap_or_default()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_walk() {
+    let mut walker = Walker::new(Vex([0, 0]), Direction::Up).with_trail();
+    walker.step();
+    walker.turn_right();
+    walker.forward(2);
+    assert_eq!(walker.position(), Vex([2, -1]));
+    assert_eq!(walker.direction(), Direction::Right);
+    assert_eq!(walker.ahead(), Vex([3, -1]));
+    assert_eq!(walker.grid_position(), None);
+    assert_eq!(
+      walker.trail(),
+      [Vex([0, 0]), Vex([0, -1]), Vex([1, -1]), Vex([2, -1])]
+    );
+
+    walker.turn_around();
+    walker.turn_left();
+    walker.forward(3);
+    assert_eq!(walker.grid_position(), Some((2, 2)));
+    assert!(Walker::new(Vex([0, 0]), Direction::Up).trail().is_empty());
+  }
+}
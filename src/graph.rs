@@ -1,6 +1,9 @@
-use std::collections::BTreeMap;
+use std::{
+  collections::{BTreeMap, HashMap},
+  hash::Hash,
+};
 
-use crate::unionfind::UnionFind;
+use crate::{error::AocError, unionfind::UnionFind};
 
 /// Graph where nodes are associated with values of N, and edges are associated with values of E.
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
@@ -159,6 +162,37 @@ impl<N, E, ET: Edge> Graph<N, E, ET> {
   }
 }
 
+impl<N: Hash + Eq + Clone, E, ET: Edge> Graph<N, E, ET> {
+  /// Constructs a graph from lines that can be read one at a time, like `parse::stream_lines`.
+  /// `edge` gives the two nodes and the value of the edge on a line, or None if it has none,
+  /// and nodes are added the first time they are named. Blank lines are skipped.
+  pub fn from_edge_stream<S: AsRef<str>>(
+    lines: impl IntoIterator<Item = Result<S, AocError>>,
+    mut edge: impl FnMut(&str) -> Option<(N, N, E)>,
+  ) -> Result<Self, AocError> {
+    let mut graph = Self::new();
+    let mut indices: HashMap<N, usize> = HashMap::new();
+    let mut index = |graph: &mut Self, node: N| {
+      *indices
+        .entry(node)
+        .or_insert_with_key(|node| graph.add_node(node.clone()))
+    };
+    for (i, line) in lines.into_iter().enumerate() {
+      let line = line?;
+      let line = line.as_ref();
+      if line.is_empty() {
+        continue;
+      }
+      let (from, to, value) =
+        edge(line).ok_or_else(|| AocError::parse(i + 1, format!("expected an edge: {line:?}")))?;
+      let from = index(&mut graph, from);
+      let to = index(&mut graph, to);
+      graph.add_edge(from, to, value);
+    }
+    Ok(graph)
+  }
+}
+
 impl<V, E> Graph<V, E, Undirected> {
   /// Constructs a minimum spanning tree for a given graph, with each edge weight value mapped to a weight function.
   pub fn minimum_spanning_tree_by<'g, T>(
@@ -203,6 +237,24 @@ impl<V, E> Graph<V, E, Undirected> {
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_from_edge_stream() {
+    let lines = ["a-b 3", "b-c 4", "", "c-a 5"].map(Ok);
+    let edge = |line: &str| {
+      let (nodes, cost) = line.split_once(' ')?;
+      let (from, to) = nodes.split_once('-')?;
+      Some((from.to_string(), to.to_string(), cost.parse::<u32>().ok()?))
+    };
+    let graph: Graph<String, u32> = Graph::from_edge_stream(lines, edge).unwrap();
+    assert_eq!(graph.num_nodes(), 3);
+    assert_eq!(graph.num_edges(), 3);
+    assert_eq!(graph.get_node(2).map(String::as_str), Some("c"));
+    assert_eq!(graph.get_edge(0, 2), Some(&5));
+
+    let error = Graph::<String, u32>::from_edge_stream(["a-b 1", "a b"].map(Ok), edge).unwrap_err();
+    assert_eq!(error, AocError::parse(2, "expected an edge: \"a b\""));
+  }
+
   #[test]
   fn test_node_retrieval() {
     let mut g: Graph<u32, ()> = Graph::new();
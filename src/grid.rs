@@ -8,6 +8,8 @@ use anyhow::ensure;
 use itertools::Itertools;
 use num_traits::Euclid;
 
+use crate::error::AocError;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -273,6 +275,69 @@ impl<T> Grid<T> {
   }
 }
 
+impl Grid<u8> {
+  /// Constructs a grid from rows of `0-9`, with each digit becoming its value.
+  /// Errors name the line and column of any other character.
+  pub fn from_digits(text: &str) -> Result<Self, AocError> {
+    let data = text
+      .trim_end()
+      .lines()
+      .enumerate()
+      .map(|(y, line)| {
+        line
+          .chars()
+          .enumerate()
+          .map(|(x, c)| {
+            c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+              AocError::parse(
+                y + 1,
+                format!("expected a digit in column {}: {c:?}", x + 1),
+              )
+            })
+          })
+          .collect()
+      })
+      .collect::<Result<_, _>>()?;
+    Self::from_rows(data).map_err(AocError::bad_input)
+  }
+}
+
+impl<T: TryFrom<char>> Grid<T> {
+  /// Constructs a grid from lines that can be read one at a time, like `parse::stream_lines`,
+  /// mapping each character to `T`. Trailing blank lines are ignored.
+  pub fn from_line_stream<S: AsRef<str>>(
+    lines: impl IntoIterator<Item = Result<S, AocError>>,
+  ) -> Result<Self, AocError> {
+    let mut data = vec![];
+    let mut width = None;
+    let mut blank = 0;
+    for (y, line) in lines.into_iter().enumerate() {
+      let line = line?;
+      let line = line.as_ref();
+      if line.is_empty() {
+        blank += 1;
+        continue;
+      }
+      if blank > 0 {
+        return Err(AocError::parse(y + 1 - blank, "blank line inside the grid"));
+      }
+      let start = data.len();
+      for (x, c) in line.chars().enumerate() {
+        let cell = c
+          .try_into()
+          .map_err(|_| AocError::parse(y + 1, format!("unexpected {c:?} in column {}", x + 1)))?;
+        data.push(cell);
+      }
+      let row = data.len() - start;
+      if *width.get_or_insert(row) != row {
+        return Err(AocError::parse(y + 1, "rows of unequal length"));
+      }
+    }
+    let width = width.ok_or_else(|| AocError::bad_input("no rows"))?;
+    Self::from_data(data, width).map_err(AocError::bad_input)
+  }
+}
+
 impl<T: TryFrom<char>> FromStr for Grid<T> {
   type Err = anyhow::Error;
 
@@ -340,6 +405,35 @@ mod tests {
   use super::*;
   use core::assert_matches;
 
+  #[test]
+  fn test_from_digits() {
+    let grid = Grid::from_digits("0129\n3456\n").unwrap();
+    assert_eq!(grid.dimensions(), (2, 4));
+    assert_eq!(grid.row(0).copied().collect::<Vec<_>>(), [0, 1, 2, 9]);
+    assert_eq!(grid[(1, 1)], 4);
+
+    let error = Grid::from_digits("012\n3a5").unwrap_err().to_string();
+    assert_eq!(error, "line 2: expected a digit in column 2: 'a'");
+    assert!(Grid::from_digits("012\n34").is_err());
+    assert!(Grid::from_digits("").is_err());
+  }
+
+  #[test]
+  fn test_from_line_stream() {
+    let lines = ["#.#", ".#.", ""].map(Ok);
+    let grid: Grid<char> = Grid::from_line_stream(lines).unwrap();
+    assert_eq!(grid.dimensions(), (2, 3));
+    assert_eq!(grid[(1, 1)], '#');
+
+    let error = Grid::<char>::from_line_stream(["#.#", "##"].map(Ok)).unwrap_err();
+    assert_matches!(error, AocError::Parse { line: 2, .. });
+    let error = Grid::<char>::from_line_stream(["#", "", "#"].map(Ok)).unwrap_err();
+    assert_matches!(error, AocError::Parse { line: 2, .. });
+    let read_error = Err(AocError::parse(1, "cannot read"));
+    assert!(Grid::<char>::from_line_stream([read_error as Result<&str, _>]).is_err());
+    assert!(Grid::<char>::from_line_stream(Vec::<Result<&str, _>>::new()).is_err());
+  }
+
   #[test]
   fn test_construction() {
     let mut g = Grid::new(4, 4, 0.0);
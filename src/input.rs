@@ -0,0 +1,362 @@
+use std::{
+  fmt::Display,
+  fs::{self, OpenOptions},
+  io::Write,
+  path::{Path, PathBuf},
+  process::{Command, Stdio},
+};
+
+use crate::error::AocError;
+
+pub const YEAR: u32 = 2025;
+
+/// The default directory of the inputs, unless `AOC_DATA_DIR` is set.
+pub const DATA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/data");
+
+/// Where inputs are read from and cached, `AOC_DATA_DIR` if set or else `DATA_DIR`.
+pub fn data_dir() -> PathBuf {
+  std::env::var_os("AOC_DATA_DIR").map_or_else(|| PathBuf::from(DATA_DIR), PathBuf::from)
+}
+
+/// Reads the input of a day from `data_dir()`, downloading it first if it is missing or blank.
+pub fn load(day: u32) -> anyhow::Result<String> {
+  let dir = data_dir();
+  cached(&dir, day, || {
+    download(YEAR, day).map_err(|e| {
+      e.context(format!(
+        "no input for day {day} at {} and it cannot be downloaded, set AOC_DATA_DIR to where your inputs are",
+        cache_path(&dir, day).display()
+      ))
+    })
+  })
+}
+
+/// Like `load`, but reading from `dir` and never downloading.
+pub fn load_from(dir: &Path, day: u32) -> anyhow::Result<String> {
+  let path = cache_path(dir, day);
+  if !path.exists() {
+    anyhow::bail!("no input for day {day} at {}", path.display());
+  }
+  let input = fs::read_to_string(&path)
+    .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", path.display()))?;
+  if input.trim().is_empty() {
+    return Err(
+      AocError::bad_input(format!(
+        "the input for day {day} at {} is empty",
+        path.display()
+      ))
+      .into(),
+    );
+  }
+  Ok(input)
+}
+
+/// The session cookie, from `AOC_SESSION` or else `~/.config/aoc/session`.
+pub fn session_token() -> anyhow::Result<String> {
+  if let Ok(token) = std::env::var("AOC_SESSION") {
+    return Ok(token.trim().to_string());
+  }
+  let home = std::env::var("HOME")?;
+  let path = Path::new(&home).join(".config/aoc/session");
+  let token = fs::read_to_string(&path)
+    .map_err(|e| anyhow::anyhow!("no AOC_SESSION and cannot read {}: {e}", path.display()))?;
+  Ok(token.trim().to_string())
+}
+
+pub fn cache_path(dir: &Path, day: u32) -> PathBuf {
+  dir.join(format!("{day:02}.txt"))
+}
+
+/// The input of a day, downloaded once and cached in `data_dir()`.
+pub fn get(year: u32, day: u32) -> anyhow::Result<String> {
+  get_cached(&data_dir(), year, day)
+}
+
+/// Like `get`, but caching in `dir`.
+pub fn get_cached(dir: &Path, year: u32, day: u32) -> anyhow::Result<String> {
+  cached(dir, day, || download(year, day))
+}
+
+/// The input of a day cached in `dir`, or else the one from `fetch`, which is then cached.
+/// A blank file counts as missing, such as the one `aoc new` creates to paste an input into.
+pub(crate) fn cached(
+  dir: &Path,
+  day: u32,
+  fetch: impl FnOnce() -> anyhow::Result<String>,
+) -> anyhow::Result<String> {
+  let path = cache_path(dir, day);
+  if path.exists() {
+    let input = fs::read_to_string(&path)?;
+    if !input.trim().is_empty() {
+      return Ok(input);
+    }
+  }
+  let input = fetch()?;
+  fs::create_dir_all(dir)?;
+  fs::write(path, &input)?;
+  Ok(input)
+}
+
+/// Downloads an input with curl, which saves pulling in an HTTP client.
+fn download(year: u32, day: u32) -> anyhow::Result<String> {
+  anyhow::ensure!((1..=25).contains(&day), "there is no day {day}");
+  request(
+    &format!("https://adventofcode.com/{year}/day/{day}/input"),
+    &[],
+  )
+}
+
+/// Sends a request with the session cookie, as a form POST if there are `fields`.
+fn request(url: &str, fields: &[(&str, &str)]) -> anyhow::Result<String> {
+  let config = format!("cookie = \"session={}\"\n", session_token()?);
+  let mut curl = Command::new("curl");
+  curl
+    .args(["--silent", "--show-error", "--fail"])
+    .args(["--user-agent", "github.com/Actimia/aoc25"])
+    // the cookie is passed as config on stdin, as arguments are visible to other users
+    .args(["--config", "-"]);
+  for (name, value) in fields {
+    curl.args(["--data-urlencode", &format!("{name}={value}")]);
+  }
+  let mut child = curl
+    .arg(url)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|e| anyhow::anyhow!("could not run curl: {e}"))?;
+  // take stdin so that it is closed once written
+  child
+    .stdin
+    .take()
+    .expect("stdin is piped")
+    .write_all(config.as_bytes())?;
+  let output = child.wait_with_output()?;
+  anyhow::ensure!(
+    output.status.success(),
+    "request to {url} failed: {}",
+    String::from_utf8_lossy(&output.stderr).trim()
+  );
+  Ok(String::from_utf8(output.stdout)?)
+}
+
+/// The response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+  Correct,
+  TooHigh,
+  TooLow,
+  Wrong,
+  /// Answered too recently, with the wait if given, e.g. `"37s"`.
+  RateLimited(Option<String>),
+  /// The part is already solved, or not unlocked yet.
+  WrongLevel,
+  Unknown,
+}
+
+impl Verdict {
+  /// Parses the message of a response page, ignoring everything outside its `<article>`.
+  pub fn parse(response: &str) -> Self {
+    let response = &message(response);
+    if response.contains("That's the right answer") {
+      Verdict::Correct
+    } else if response.contains("too high") {
+      Verdict::TooHigh
+    } else if response.contains("too low") {
+      Verdict::TooLow
+    } else if response.contains("That's not the right answer") {
+      Verdict::Wrong
+    } else if response.contains("You gave an answer too recently") {
+      let wait = response
+        .split_once("You have ")
+        .and_then(|(_, rest)| rest.split_once(" left to wait"))
+        .map(|(wait, _)| wait.to_string());
+      Verdict::RateLimited(wait)
+    } else if response.contains("You don't seem to be solving the right level") {
+      Verdict::WrongLevel
+    } else {
+      Verdict::Unknown
+    }
+  }
+
+  /// Whether the answer itself was judged, so that it is worth remembering.
+  pub fn is_final(&self) -> bool {
+    matches!(
+      self,
+      Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+    )
+  }
+}
+
+/// The text of the `<article>` of a page, or of the whole page if it has none, without tags.
+fn message(page: &str) -> String {
+  let (article, mut in_tag) = match page
+    .split_once("<article")
+    .and_then(|(_, rest)| rest.split_once("</article>"))
+  {
+    // the rest of the opening tag is still to come
+    Some((article, _)) => (article, true),
+    None => (page, false),
+  };
+  let mut text = String::new();
+  for c in article.chars() {
+    match c {
+      '<' => in_tag = true,
+      '>' => in_tag = false,
+      c if !in_tag => text.push(c),
+      _ => {}
+    }
+  }
+  text
+}
+
+impl Display for Verdict {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Verdict::Correct => write!(f, "correct"),
+      Verdict::TooHigh => write!(f, "too high"),
+      Verdict::TooLow => write!(f, "too low"),
+      Verdict::Wrong => write!(f, "wrong"),
+      Verdict::RateLimited(Some(wait)) => write!(f, "rate limited, wait {wait}"),
+      Verdict::RateLimited(None) => write!(f, "rate limited"),
+      Verdict::WrongLevel => write!(f, "wrong level"),
+      Verdict::Unknown => write!(f, "unknown response"),
+    }
+  }
+}
+
+/// Where verdicts are recorded, one `day<TAB>part<TAB>answer<TAB>verdict` line each.
+pub fn verdicts_path(dir: &Path) -> PathBuf {
+  dir.join("verdicts.txt")
+}
+
+/// The verdict recorded for an earlier submission of the same answer.
+pub fn previous_verdict(dir: &Path, day: u32, part: u8, answer: &str) -> Option<String> {
+  let verdicts = fs::read_to_string(verdicts_path(dir)).ok()?;
+  let key = format!("{day}\t{part}\t{answer}\t");
+  verdicts
+    .lines()
+    .find_map(|line| line.strip_prefix(&key))
+    .map(str::to_string)
+}
+
+pub fn record_verdict(
+  dir: &Path,
+  day: u32,
+  part: u8,
+  answer: &str,
+  verdict: &Verdict,
+) -> anyhow::Result<()> {
+  let mut file = OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(verdicts_path(dir))?;
+  writeln!(file, "{day}\t{part}\t{answer}\t{verdict}")?;
+  Ok(())
+}
+
+/// Submits an answer, unless the same answer was already judged.
+/// Judged answers are recorded in `data_dir()`.
+pub fn submit(year: u32, day: u32, part: u8, answer: &str) -> anyhow::Result<Verdict> {
+  let dir = data_dir();
+  if let Some(previous) = previous_verdict(&dir, day, part, answer) {
+    anyhow::bail!("{answer} was already submitted for day {day} part {part}: {previous}");
+  }
+  let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
+  let level = part.to_string();
+  let response = request(&url, &[("level", &level), ("answer", answer)])?;
+  let verdict = Verdict::parse(&response);
+  if verdict.is_final() {
+    record_verdict(&dir, day, part, answer, &verdict)?;
+  }
+  Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_cached() {
+    let dir = std::env::temp_dir().join("aoc25_test_input");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(cache_path(&dir, 3), "cached").unwrap();
+    assert_eq!(get_cached(&dir, 2025, 3).unwrap(), "cached");
+    // a blank file is replaced by the fetched input
+    fs::write(cache_path(&dir, 3), "\n").unwrap();
+    assert_eq!(cached(&dir, 3, || Ok("fetched".into())).unwrap(), "fetched");
+    assert_eq!(get_cached(&dir, 2025, 3).unwrap(), "fetched");
+    assert!(cache_path(&dir, 3).ends_with("03.txt"));
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_load() {
+    let dir = std::env::temp_dir().join("aoc25_test_load");
+    fs::create_dir_all(&dir).unwrap();
+    let missing = load_from(&dir, 4).unwrap_err().to_string();
+    assert!(missing.contains("no input for day 4") && missing.contains("04.txt"));
+
+    fs::write(cache_path(&dir, 4), "\n").unwrap();
+    assert!(
+      load_from(&dir, 4)
+        .unwrap_err()
+        .to_string()
+        .contains("is empty")
+    );
+    fs::write(cache_path(&dir, 4), "1 2\n").unwrap();
+    assert_eq!(load_from(&dir, 4).unwrap(), "1 2\n");
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_verdict() {
+    let parse = |s| Verdict::parse(s);
+    assert_eq!(
+      parse("<p>That's the right answer! You are one gold star closer"),
+      Verdict::Correct
+    );
+    assert_eq!(
+      parse("That's not the right answer; your answer is too low."),
+      Verdict::TooLow
+    );
+    assert_eq!(parse("That's not the right answer."), Verdict::Wrong);
+    assert_eq!(
+      parse("You gave an answer too recently. You have 37s left to wait."),
+      Verdict::RateLimited(Some("37s".into()))
+    );
+    assert_eq!(parse("<html></html>"), Verdict::Unknown);
+    assert_eq!(
+      parse(
+        "<title>too low</title><main><article><p>That's the <em>right answer</em>!</p></article></main>"
+      ),
+      Verdict::Correct
+    );
+    assert_eq!(
+      parse(
+        "<p>too high</p><article><p>You don't seem to be solving the right level.</p></article>"
+      ),
+      Verdict::WrongLevel
+    );
+  }
+
+  #[test]
+  fn test_record_verdict() {
+    let dir = std::env::temp_dir().join("aoc25_test_verdicts");
+    fs::create_dir_all(&dir).unwrap();
+    let _ = fs::remove_file(verdicts_path(&dir));
+    assert_eq!(previous_verdict(&dir, 9, 1, "12"), None);
+    record_verdict(&dir, 9, 1, "12", &Verdict::TooHigh).unwrap();
+    assert_eq!(
+      previous_verdict(&dir, 9, 1, "12").as_deref(),
+      Some("too high")
+    );
+    assert_eq!(previous_verdict(&dir, 9, 1, "1"), None);
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_bad_day() {
+    assert!(download(2025, 26).is_err());
+  }
+}
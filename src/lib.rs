@@ -1,23 +1,44 @@
+#[cfg(feature = "memory")]
+pub mod alloc;
+pub mod answers;
 pub mod assert;
 pub mod avg;
+pub mod bitset;
 pub mod bloomfilter;
 pub mod cards;
+pub mod collections;
+pub mod counter;
 pub mod elo;
 pub mod eqish;
+pub mod error;
 pub mod events;
+pub mod exact_cover;
 pub mod exts;
 pub mod fuzzy;
+pub mod geom;
 pub mod graph;
 pub mod graph_algo;
 pub mod grid;
+pub mod input;
 pub mod kmeans;
 pub mod lcs;
+pub mod linalg;
+pub mod memo;
+pub mod opt;
+pub mod parse;
 pub mod prime;
 pub mod priority;
+pub mod ranges;
+pub mod scaffold;
+pub mod segment_tree;
 pub mod seq;
 pub mod seq3;
+pub mod solution;
+pub mod sparse_table;
+pub mod spatial;
 pub mod strings;
 pub mod time;
 pub mod unionfind;
 pub mod vex;
+pub mod viz;
 pub mod truthy;
@@ -0,0 +1,235 @@
+use crate::bitset::BitSet;
+
+/// A matrix over GF(2), where adding is xor, stored as one `BitSet` per row.
+/// Toggle puzzles like lights out are linear systems over GF(2): pressing a button twice
+/// does nothing, so each button is pressed 0 or 1 times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix {
+  rows: Vec<BitSet>,
+  cols: usize,
+}
+
+impl Matrix {
+  /// Creates a matrix of zeros.
+  pub fn new(rows: usize, cols: usize) -> Self {
+    Self {
+      rows: vec![BitSet::new(cols); rows],
+      cols,
+    }
+  }
+
+  /// Panics if the rows have different lengths.
+  pub fn from_rows(rows: Vec<BitSet>) -> Self {
+    let cols = rows.first().map_or(0, BitSet::len);
+    assert!(
+      rows.iter().all(|row| row.len() == cols),
+      "rows have different lengths"
+    );
+    Self { rows, cols }
+  }
+
+  pub fn rows(&self) -> usize {
+    self.rows.len()
+  }
+
+  pub fn cols(&self) -> usize {
+    self.cols
+  }
+
+  pub fn get(&self, row: usize, col: usize) -> bool {
+    self.rows[row].get(col)
+  }
+
+  pub fn set(&mut self, row: usize, col: usize, value: bool) {
+    self.rows[row].set(col, value);
+  }
+
+  pub fn row(&self, row: usize) -> &BitSet {
+    &self.rows[row]
+  }
+
+  /// The product with the column vector `x`. Panics if its length is not `cols`.
+  pub fn mul(&self, x: &BitSet) -> BitSet {
+    assert_eq!(x.len(), self.cols, "wrong vector length");
+    let mut product = BitSet::new(self.rows.len());
+    for (i, row) in self.rows.iter().enumerate() {
+      let ones = row.iter_ones().filter(|col| x.get(*col)).count();
+      product.set(i, ones % 2 == 1);
+    }
+    product
+  }
+
+  pub fn rank(&self) -> usize {
+    Echelon::new(self, None).pivots.len()
+  }
+}
+
+/// A matrix in reduced row echelon form, with the right-hand side reduced alongside.
+struct Echelon {
+  rows: Vec<BitSet>,
+  rhs: Vec<bool>,
+  /// The pivot column of each of the first rows, in increasing order.
+  pivots: Vec<usize>,
+  cols: usize,
+}
+
+impl Echelon {
+  fn new(a: &Matrix, b: Option<&BitSet>) -> Self {
+    let mut rows = a.rows.clone();
+    let mut rhs: Vec<bool> = match b {
+      Some(b) => {
+        assert_eq!(b.len(), a.rows(), "wrong vector length");
+        (0..b.len()).map(|i| b.get(i)).collect()
+      }
+      None => vec![false; a.rows()],
+    };
+    let mut pivots = vec![];
+    for col in 0..a.cols {
+      let top = pivots.len();
+      let Some(found) = (top..rows.len()).find(|r| rows[*r].get(col)) else {
+        continue;
+      };
+      rows.swap(top, found);
+      rhs.swap(top, found);
+      let pivot = rows[top].clone();
+      for r in 0..rows.len() {
+        if r != top && rows[r].get(col) {
+          rows[r].symmetric_difference(&pivot);
+          rhs[r] ^= rhs[top];
+        }
+      }
+      pivots.push(col);
+    }
+    Self {
+      rows,
+      rhs,
+      pivots,
+      cols: a.cols,
+    }
+  }
+
+  /// The columns without a pivot, which can be chosen freely.
+  fn free(&self) -> impl Iterator<Item = usize> {
+    (0..self.cols).filter(|col| self.pivots.binary_search(col).is_err())
+  }
+}
+
+/// A solution of `a * x = b`, with every free variable 0, or None if there is none.
+/// All the solutions are this plus any combination of the `nullspace`.
+pub fn solve(a: &Matrix, b: &BitSet) -> Option<BitSet> {
+  let echelon = Echelon::new(a, Some(b));
+  // a zero row with a 1 on the right is 0 = 1
+  if echelon.rhs[echelon.pivots.len()..].iter().any(|bit| *bit) {
+    return None;
+  }
+  let mut x = BitSet::new(a.cols);
+  for (i, pivot) in echelon.pivots.iter().enumerate() {
+    x.set(*pivot, echelon.rhs[i]);
+  }
+  Some(x)
+}
+
+/// A basis of the solutions of `a * x = 0`, one vector per free variable.
+pub fn nullspace(a: &Matrix) -> Vec<BitSet> {
+  let echelon = Echelon::new(a, None);
+  echelon
+    .free()
+    .map(|free| {
+      let mut x = BitSet::new(a.cols);
+      x.set(free, true);
+      for (i, pivot) in echelon.pivots.iter().enumerate() {
+        if echelon.rows[i].get(free) {
+          x.set(*pivot, true);
+        }
+      }
+      x
+    })
+    .collect()
+}
+
+/// The largest nullspace `fewest_ones` searches, as it takes `2^n` steps.
+pub const MAX_FREE_VARIABLES: usize = 30;
+
+/// The solution of `a * x = b` with the fewest ones, or None if there is none.
+/// Tries every combination of the nullspace, so this is exponential in its size,
+/// and fails if it has more than `MAX_FREE_VARIABLES` vectors.
+pub fn fewest_ones(a: &Matrix, b: &BitSet) -> anyhow::Result<Option<BitSet>> {
+  let Some(mut x) = solve(a, b) else {
+    return Ok(None);
+  };
+  let basis = nullspace(a);
+  anyhow::ensure!(
+    basis.len() <= MAX_FREE_VARIABLES,
+    "too many free variables to search: {} > {MAX_FREE_VARIABLES}",
+    basis.len()
+  );
+  let mut best = x.clone();
+  // visit the combinations in gray code order, changing one basis vector per step
+  for step in 1..1usize << basis.len() {
+    x.symmetric_difference(&basis[step.trailing_zeros() as usize]);
+    if x.count_ones() < best.count_ones() {
+      best = x.clone();
+    }
+  }
+  Ok(Some(best))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn bits(bits: &str) -> BitSet {
+    let mut set = BitSet::new(bits.len());
+    for (i, c) in bits.chars().enumerate() {
+      set.set(i, c == '1');
+    }
+    set
+  }
+
+  fn matrix(rows: &[&str]) -> Matrix {
+    Matrix::from_rows(rows.iter().map(|row| bits(row)).collect())
+  }
+
+  #[test]
+  fn test_solve() {
+    let a = matrix(&["110", "011", "101"]);
+    assert_eq!(a.rank(), 2);
+    // the rows sum to zero, so b must too
+    assert_eq!(solve(&a, &bits("111")), None);
+    let x = solve(&a, &bits("101")).unwrap();
+    assert_eq!(a.mul(&x), bits("101"));
+
+    let null = nullspace(&a);
+    assert_eq!(null, [bits("111")]);
+    assert!(a.mul(&null[0]).is_clear());
+  }
+
+  #[test]
+  fn test_full_rank() {
+    let a = matrix(&["100", "110", "111"]);
+    assert_eq!(a.rank(), 3);
+    assert!(nullspace(&a).is_empty());
+    assert_eq!(solve(&a, &bits("011")), Some(bits("010")));
+  }
+
+  #[test]
+  fn test_fewest_ones() {
+    // the first machine of day 10: the fewest presses are the last two buttons
+    let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+    let mut a = Matrix::new(4, buttons.len());
+    for (button, lights) in buttons.iter().enumerate() {
+      for light in *lights {
+        a.set(*light, button, true);
+      }
+    }
+    let x = fewest_ones(&a, &bits("0110")).unwrap().unwrap();
+    assert_eq!(x.count_ones(), 2);
+    assert_eq!(a.mul(&x), bits("0110"));
+
+    let a = Matrix::new(1, 70);
+    assert!(fewest_ones(&a, &bits("0")).is_err());
+    let a = Matrix::new(1, MAX_FREE_VARIABLES + 2);
+    assert!(fewest_ones(&a, &bits("0")).is_err());
+    assert_eq!(fewest_ones(&a, &bits("1")).unwrap(), None);
+  }
+}
@@ -0,0 +1,168 @@
+use std::ops::{Index, IndexMut, Mul};
+
+use num_traits::{One, Zero};
+
+/// A dense matrix, indexed by `(row, col)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix<T> {
+  data: Vec<T>,
+  rows: usize,
+  cols: usize,
+}
+
+impl<T: Copy + Zero> Matrix<T> {
+  /// Creates a matrix of zeros.
+  pub fn new(rows: usize, cols: usize) -> Self {
+    Self {
+      data: vec![T::zero(); rows * cols],
+      rows,
+      cols,
+    }
+  }
+}
+
+impl<T: Copy + Zero + One> Matrix<T> {
+  pub fn identity(n: usize) -> Self {
+    let mut identity = Self::new(n, n);
+    for i in 0..n {
+      identity[(i, i)] = T::one();
+    }
+    identity
+  }
+
+  /// The matrix multiplied by itself `exp` times, by repeated squaring.
+  /// Panics if the matrix is not square.
+  pub fn pow(&self, mut exp: u64) -> Self {
+    assert_eq!(self.rows, self.cols, "only square matrices have powers");
+    let mut result = Self::identity(self.rows);
+    let mut base = self.clone();
+    while exp > 0 {
+      if exp & 1 == 1 {
+        result = &result * &base;
+      }
+      base = &base * &base;
+      exp >>= 1;
+    }
+    result
+  }
+
+  /// The product with the column vector `x`. Panics if its length is not `cols`.
+  pub fn mul_vec(&self, x: &[T]) -> Vec<T> {
+    assert_eq!(x.len(), self.cols, "wrong vector length");
+    (0..self.rows)
+      .map(|r| {
+        self
+          .row(r)
+          .iter()
+          .zip(x)
+          .fold(T::zero(), |sum, (a, b)| sum + *a * *b)
+      })
+      .collect()
+  }
+}
+
+impl<T> Matrix<T> {
+  /// Panics if the rows have different lengths.
+  pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+    let cols = rows.first().map_or(0, Vec::len);
+    assert!(
+      rows.iter().all(|row| row.len() == cols),
+      "rows have different lengths"
+    );
+    Self {
+      rows: rows.len(),
+      cols,
+      data: rows.into_iter().flatten().collect(),
+    }
+  }
+
+  pub fn rows(&self) -> usize {
+    self.rows
+  }
+
+  pub fn cols(&self) -> usize {
+    self.cols
+  }
+
+  pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+    (row < self.rows && col < self.cols).then(|| &self.data[row * self.cols + col])
+  }
+
+  pub fn row(&self, row: usize) -> &[T] {
+    &self.data[row * self.cols..(row + 1) * self.cols]
+  }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+  type Output = T;
+
+  fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+    assert!(row < self.rows && col < self.cols, "index out of bounds");
+    &self.data[row * self.cols + col]
+  }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+  fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+    assert!(row < self.rows && col < self.cols, "index out of bounds");
+    &mut self.data[row * self.cols + col]
+  }
+}
+
+/// Panics if the number of columns of `self` differs from the rows of `rhs`.
+impl<T: Copy + Zero + Mul<Output = T>> Mul for &Matrix<T> {
+  type Output = Matrix<T>;
+
+  fn mul(self, rhs: Self) -> Self::Output {
+    assert_eq!(self.cols, rhs.rows, "cannot multiply these shapes");
+    let mut product = Matrix::new(self.rows, rhs.cols);
+    for r in 0..self.rows {
+      for k in 0..self.cols {
+        let a = self[(r, k)];
+        if a.is_zero() {
+          continue;
+        }
+        for c in 0..rhs.cols {
+          product[(r, c)] = product[(r, c)] + a * rhs[(k, c)];
+        }
+      }
+    }
+    product
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linalg::ModInt;
+
+  #[test]
+  fn test_mul() {
+    let a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    let b = Matrix::from_rows(vec![vec![7, 8], vec![9, 10], vec![11, 12]]);
+    assert_eq!(
+      &a * &b,
+      Matrix::from_rows(vec![vec![58, 64], vec![139, 154]])
+    );
+    assert_eq!(a.mul_vec(&[1, 0, -1]), [-2, -2]);
+    assert_eq!(&a * &Matrix::identity(3), a);
+    assert_eq!(a.get(1, 2), Some(&6));
+    assert_eq!(a.get(2, 0), None);
+  }
+
+  #[test]
+  fn test_fibonacci() {
+    // (F(n+1), F(n)) = [[1, 1], [1, 0]]^n (1, 0)
+    let step = Matrix::from_rows(vec![vec![1u64, 1], vec![1, 0]]);
+    assert_eq!(step.pow(0), Matrix::identity(2));
+    assert_eq!(step.pow(40)[(0, 1)], 102334155);
+
+    let one = ModInt::<1_000_000_007>::new(1);
+    let step = Matrix::from_rows(vec![vec![one, one], vec![one, ModInt::new(0)]]);
+    assert_eq!(step.pow(100)[(0, 1)].value(), 687995182);
+    // the last digits of the fibonacci numbers repeat every 60 steps
+    let one = ModInt::<10>::new(1);
+    let step = Matrix::from_rows(vec![vec![one, one], vec![one, ModInt::new(0)]]);
+    assert_eq!(step.pow(1_000_000_000_000), step.pow(40));
+  }
+}
@@ -0,0 +1,6 @@
+pub mod gf2;
+pub mod matrix;
+pub mod modular;
+
+pub use matrix::Matrix;
+pub use modular::ModInt;
@@ -0,0 +1,102 @@
+use std::{
+  fmt::Display,
+  ops::{Add, AddAssign, Mul, MulAssign, Sub},
+};
+
+use num_traits::{One, Zero};
+
+/// An integer modulo `M`, for counting answers that are only wanted mod some prime.
+/// `M` must fit in 63 bits so that sums do not overflow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+  pub fn new(value: u64) -> Self {
+    Self(value % M)
+  }
+
+  pub fn value(self) -> u64 {
+    self.0
+  }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+  fn from(value: u64) -> Self {
+    Self::new(value)
+  }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+  type Output = Self;
+
+  fn add(self, rhs: Self) -> Self::Output {
+    Self((self.0 + rhs.0) % M)
+  }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+  fn add_assign(&mut self, rhs: Self) {
+    *self = *self + rhs;
+  }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+  type Output = Self;
+
+  fn sub(self, rhs: Self) -> Self::Output {
+    Self((self.0 + M - rhs.0) % M)
+  }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+  type Output = Self;
+
+  fn mul(self, rhs: Self) -> Self::Output {
+    Self((self.0 as u128 * rhs.0 as u128 % M as u128) as u64)
+  }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+  fn mul_assign(&mut self, rhs: Self) {
+    *self = *self * rhs;
+  }
+}
+
+impl<const M: u64> Zero for ModInt<M> {
+  fn zero() -> Self {
+    Self(0)
+  }
+
+  fn is_zero(&self) -> bool {
+    self.0 == 0
+  }
+}
+
+impl<const M: u64> One for ModInt<M> {
+  fn one() -> Self {
+    Self(1 % M)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  type Mod7 = ModInt<7>;
+
+  #[test]
+  fn test_arithmetic() {
+    assert_eq!(Mod7::new(10).value(), 3);
+    assert_eq!(Mod7::new(5) + Mod7::new(4), Mod7::new(2));
+    assert_eq!(Mod7::new(2) - Mod7::new(5), Mod7::new(4));
+    assert_eq!(Mod7::new(3) * Mod7::new(5), Mod7::new(1));
+    let big = ModInt::<{ u64::MAX / 2 }>::new(u64::MAX / 2 - 1);
+    assert_eq!((big * big).value(), 1);
+  }
+}
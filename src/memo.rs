@@ -0,0 +1,153 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  hash::Hash,
+};
+
+/// A cache of the results of a function, for recursive searches with overlapping subproblems.
+///
+/// Inside hand-written recursion, check `get` first and `insert` the result before returning:
+///
+/// ```
+/// # use aoc25::memo::Memo;
+/// fn ways(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+///   if n <= 1 {
+///     return 1;
+///   }
+///   if let Some(ways) = memo.get(&n) {
+///     return *ways;
+///   }
+///   let result = ways(n - 1, memo) + ways(n - 2, memo);
+///   memo.insert(n, result)
+/// }
+/// assert_eq!(ways(80, &mut Memo::new()), 37889062373143906);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+  cache: HashMap<K, V>,
+  /// The keys in the order they were inserted, when the size is bounded.
+  order: VecDeque<K>,
+  capacity: Option<usize>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+  pub fn new() -> Self {
+    Self {
+      cache: HashMap::new(),
+      order: VecDeque::new(),
+      capacity: None,
+    }
+  }
+
+  /// A cache of at most `capacity` results, forgetting the oldest first when it is full.
+  /// Panics if `capacity` is 0.
+  pub fn bounded(capacity: usize) -> Self {
+    assert!(capacity > 0, "capacity cannot be 0");
+    Self {
+      capacity: Some(capacity),
+      ..Self::new()
+    }
+  }
+
+  pub fn get(&self, key: &K) -> Option<&V> {
+    self.cache.get(key)
+  }
+
+  /// Caches the result for `key`, and returns it.
+  pub fn insert(&mut self, key: K, value: V) -> V {
+    if let Some(capacity) = self.capacity
+      && !self.cache.contains_key(&key)
+    {
+      if self.cache.len() == capacity
+        && let Some(oldest) = self.order.pop_front()
+      {
+        self.cache.remove(&oldest);
+      }
+      self.order.push_back(key.clone());
+    }
+    self.cache.insert(key, value.clone());
+    value
+  }
+
+  /// Calls `f` for `key` unless its result is cached. `f` is given a function to recurse with,
+  /// which uses the same cache.
+  pub fn call<F>(&mut self, f: &F, key: K) -> V
+  where
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+  {
+    if let Some(value) = self.get(&key) {
+      return value.clone();
+    }
+    let value = f(&mut |key| self.call(f, key), key.clone());
+    self.insert(key, value)
+  }
+
+  pub fn len(&self) -> usize {
+    self.cache.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cache.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    self.cache.clear();
+    self.order.clear();
+  }
+}
+
+/// Memoizes a recursive function, which is given a function to recurse with as its first argument.
+///
+/// ```
+/// # use aoc25::memo::memoize;
+/// let mut fib = memoize(|fib, n: u64| if n <= 1 { n } else { fib(n - 1) + fib(n - 2) });
+/// assert_eq!(fib(90), 2880067194370816120);
+/// ```
+pub fn memoize<K, V, F>(f: F) -> impl FnMut(K) -> V
+where
+  K: Hash + Eq + Clone,
+  V: Clone,
+  F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+  let mut memo = Memo::new();
+  move |key| memo.call(&f, key)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::Cell;
+
+  use super::*;
+
+  #[test]
+  fn test_memoize_calls_once() {
+    let calls = Cell::new(0);
+    let mut fib = memoize(|fib, n: u64| {
+      calls.set(calls.get() + 1);
+      if n <= 1 { n } else { fib(n - 1) + fib(n - 2) }
+    });
+    assert_eq!(fib(30), 832040);
+    assert_eq!(calls.get(), 31);
+    assert_eq!(fib(30), 832040);
+    assert_eq!(calls.get(), 31);
+  }
+
+  #[test]
+  fn test_bounded() {
+    let mut memo = Memo::bounded(2);
+    memo.insert(1, "one");
+    memo.insert(2, "two");
+    memo.insert(1, "uno");
+    assert_eq!(memo.len(), 2);
+    memo.insert(3, "three");
+    assert_eq!(memo.len(), 2);
+    assert_eq!(memo.get(&1), None);
+    assert_eq!(memo.get(&2), Some(&"two"));
+    assert_eq!(memo.get(&3), Some(&"three"));
+  }
+}
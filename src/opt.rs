@@ -0,0 +1,147 @@
+use std::ops::RangeInclusive;
+
+use microlp::{ComparisonOp, LinearExpr, OptimizationDirection, Problem, Variable};
+
+/// A variable of an `Ilp`, used to build constraints and read its value from the solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Var(Variable);
+
+/// An integer (or mixed) linear program, built up one variable and constraint at a time.
+///
+/// ```
+/// # use aoc25::opt::Ilp;
+/// // the fewest coins of 3 and 5 making 14
+/// let mut ilp = Ilp::minimize();
+/// let threes = ilp.int_var(1.0, 0..=10);
+/// let fives = ilp.int_var(1.0, 0..=10);
+/// ilp.eq([(threes, 3.0), (fives, 5.0)], 14.0);
+/// let solution = ilp.solve().unwrap();
+/// assert_eq!(solution.objective_int(), 4);
+/// assert_eq!(solution.int(fives), 1);
+/// ```
+pub struct Ilp {
+  problem: Problem,
+}
+
+impl Ilp {
+  pub fn minimize() -> Self {
+    Self {
+      problem: Problem::new(OptimizationDirection::Minimize),
+    }
+  }
+
+  pub fn maximize() -> Self {
+    Self {
+      problem: Problem::new(OptimizationDirection::Maximize),
+    }
+  }
+
+  /// An integer variable in `range`, adding `cost` times its value to the objective.
+  pub fn int_var(&mut self, cost: f64, range: RangeInclusive<i32>) -> Var {
+    Var(
+      self
+        .problem
+        .add_integer_var(cost, (*range.start(), *range.end())),
+    )
+  }
+
+  /// A variable that is 0 or 1.
+  pub fn bool_var(&mut self, cost: f64) -> Var {
+    Var(self.problem.add_binary_var(cost))
+  }
+
+  /// A real valued variable in `min..=max`, which may be infinite.
+  pub fn real_var(&mut self, cost: f64, min: f64, max: f64) -> Var {
+    Var(self.problem.add_var(cost, (min, max)))
+  }
+
+  fn constrain(&mut self, terms: impl IntoIterator<Item = (Var, f64)>, op: ComparisonOp, rhs: f64) {
+    let expr: LinearExpr = terms
+      .into_iter()
+      .map(|(var, coeff)| (var.0, coeff))
+      .collect();
+    self.problem.add_constraint(expr, op, rhs);
+  }
+
+  /// Requires the sum of each variable times its coefficient to equal `rhs`.
+  pub fn eq(&mut self, terms: impl IntoIterator<Item = (Var, f64)>, rhs: f64) {
+    self.constrain(terms, ComparisonOp::Eq, rhs);
+  }
+
+  /// Requires the sum of each variable times its coefficient to be at most `rhs`.
+  pub fn le(&mut self, terms: impl IntoIterator<Item = (Var, f64)>, rhs: f64) {
+    self.constrain(terms, ComparisonOp::Le, rhs);
+  }
+
+  /// Requires the sum of each variable times its coefficient to be at least `rhs`.
+  pub fn ge(&mut self, terms: impl IntoIterator<Item = (Var, f64)>, rhs: f64) {
+    self.constrain(terms, ComparisonOp::Ge, rhs);
+  }
+
+  /// The best solution, or an error if there is none or the objective is unbounded.
+  pub fn solve(&self) -> anyhow::Result<IlpSolution> {
+    let solution = self
+      .problem
+      .solve()
+      .map_err(|e| anyhow::anyhow!("cannot solve the linear program: {e}"))?;
+    Ok(IlpSolution(solution))
+  }
+}
+
+pub struct IlpSolution(microlp::Solution);
+
+impl IlpSolution {
+  pub fn objective(&self) -> f64 {
+    self.0.objective()
+  }
+
+  /// The objective rounded to an integer, for when every variable and cost is an integer.
+  pub fn objective_int(&self) -> i64 {
+    self.0.objective().round() as i64
+  }
+
+  pub fn value(&self, var: Var) -> f64 {
+    *self.0.var_value(var.0)
+  }
+
+  /// The value of an integer variable, without the solver's floating point error.
+  pub fn int(&self, var: Var) -> i64 {
+    self.value(var).round() as i64
+  }
+
+  pub fn bool(&self, var: Var) -> bool {
+    self.int(var) != 0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_maximize() {
+    // the classic: at most 4 of x + y, 3x + y at most 6, maximizing x + 2y
+    let mut ilp = Ilp::maximize();
+    let x = ilp.int_var(1.0, 0..=10);
+    let y = ilp.int_var(2.0, 0..=10);
+    ilp.le([(x, 1.0), (y, 1.0)], 4.0);
+    ilp.le([(x, 3.0), (y, 1.0)], 6.0);
+    let solution = ilp.solve().unwrap();
+    assert_eq!(solution.objective_int(), 8);
+    assert_eq!((solution.int(x), solution.int(y)), (0, 4));
+  }
+
+  #[test]
+  fn test_bool_and_infeasible() {
+    let mut ilp = Ilp::minimize();
+    let a = ilp.bool_var(1.0);
+    let b = ilp.bool_var(1.0);
+    ilp.ge([(a, 1.0), (b, 1.0)], 1.0);
+    ilp.eq([(a, 1.0)], 0.0);
+    let solution = ilp.solve().unwrap();
+    assert!(!solution.bool(a) && solution.bool(b));
+
+    ilp.ge([(b, 1.0)], 2.0);
+    assert!(ilp.solve().is_err());
+  }
+}
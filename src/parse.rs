@@ -0,0 +1,159 @@
+use std::{
+  fmt::Display,
+  fs::File,
+  io::{BufRead, BufReader},
+  path::Path,
+  str::FromStr,
+};
+
+use crate::{error::AocError, exts::string::StrExt, grid::Grid};
+
+/// The line number of `part`, which must be a slice of `input`.
+fn line_of(input: &str, part: &str) -> usize {
+  let offset = part.as_ptr() as usize - input.as_ptr() as usize;
+  input[..offset].matches('\n').count() + 1
+}
+
+/// Parses `part` of `input`, naming its line in the error.
+pub(crate) fn parse_at<T>(input: &str, part: &str) -> Result<T, AocError>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  part
+    .parse()
+    .map_err(|e| AocError::parse(line_of(input, part), format!("{e}: {part:?}")))
+}
+
+/// Parses every line. Trailing blank lines are ignored, other blank lines are parsed.
+pub fn lines_of<T>(input: &str) -> Result<Vec<T>, AocError>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  input
+    .trim_end()
+    .lines()
+    .map(|line| parse_at(input, line))
+    .collect()
+}
+
+/// Parses every block of lines separated by blank lines. Errors name the first line of the block.
+pub fn blocks_of<T>(input: &str) -> Result<Vec<T>, AocError>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  input.blocks().map(|block| parse_at(input, block)).collect()
+}
+
+/// Parses the items of a list like `1,2, 3`, which may span several lines.
+pub fn comma_separated<T>(input: &str) -> Result<Vec<T>, AocError>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  let items = input.trim();
+  if items.is_empty() {
+    return Ok(vec![]);
+  }
+  // lines are counted in the untrimmed input, so leading blank lines are not skipped
+  items
+    .split(',')
+    .map(|item| parse_at(input, item.trim()))
+    .collect()
+}
+
+/// Parses a block of `0-9` into a grid of their values, see `Grid::from_digits`.
+pub fn grid_of_digits(input: &str) -> Result<Grid<u8>, AocError> {
+  Grid::from_digits(input)
+}
+
+/// Reads the lines of a file one at a time, so huge inputs never have to fit in memory.
+/// Errors name the line that could not be read. See `Grid::from_line_stream` and
+/// `Graph::from_edge_stream` for building from the lines.
+pub fn stream_lines(
+  path: impl AsRef<Path>,
+) -> Result<impl Iterator<Item = Result<String, AocError>>, AocError> {
+  let path = path.as_ref();
+  let file = File::open(path)
+    .map_err(|e| AocError::bad_input(format!("cannot open {}: {e}", path.display())))?;
+  Ok(
+    BufReader::new(file)
+      .lines()
+      .enumerate()
+      .map(|(i, line)| line.map_err(|e| AocError::parse(i + 1, format!("cannot read: {e}")))),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use core::assert_matches;
+
+  use super::*;
+
+  #[test]
+  fn test_lines_of() {
+    assert_eq!(lines_of::<i32>("1\n-2\n3\n\n").unwrap(), [1, -2, 3]);
+    assert!(lines_of::<i32>("").unwrap().is_empty());
+    let error = lines_of::<i32>("1\n2\nx\n").unwrap_err();
+    assert_matches!(error, AocError::Parse { line: 3, .. });
+    let error = error.to_string();
+    assert!(error.starts_with("line 3:"), "{error}");
+    assert!(error.ends_with("\"x\""), "{error}");
+  }
+
+  #[test]
+  fn test_blocks_of() {
+    #[derive(Debug, PartialEq)]
+    struct Sum(u32);
+
+    impl FromStr for Sum {
+      type Err = std::num::ParseIntError;
+
+      fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+          .map(str::parse::<u32>)
+          .sum::<Result<_, _>>()
+          .map(Sum)
+      }
+    }
+
+    assert_eq!(blocks_of::<Sum>("1\n2\n\n3\n").unwrap(), [Sum(3), Sum(3)]);
+    let error = blocks_of::<Sum>("1\n\n\n2\nx\n").unwrap_err().to_string();
+    assert!(error.starts_with("line 4:"), "{error}");
+  }
+
+  #[test]
+  fn test_comma_separated() {
+    assert_eq!(comma_separated::<u8>("1,2, 3\n").unwrap(), [1, 2, 3]);
+    assert!(comma_separated::<u8>("\n").unwrap().is_empty());
+    let error = comma_separated::<u8>("1,2,\n300").unwrap_err().to_string();
+    assert!(error.starts_with("line 2:"), "{error}");
+    let error = comma_separated::<u8>("\n\n1,x").unwrap_err().to_string();
+    assert!(error.starts_with("line 3:"), "{error}");
+  }
+
+  #[test]
+  fn test_stream_lines() {
+    let path = std::env::temp_dir().join("aoc25_test_stream_lines.txt");
+    std::fs::write(&path, "1\n22\n\n333").unwrap();
+    let lines: Vec<String> = stream_lines(&path).unwrap().map(Result::unwrap).collect();
+    assert_eq!(lines, ["1", "22", "", "333"]);
+    std::fs::remove_file(&path).unwrap();
+    assert_matches!(stream_lines(&path).err(), Some(AocError::BadInput(_)));
+  }
+
+  #[test]
+  fn test_grid_of_digits() {
+    let grid = grid_of_digits("123\n456\n").unwrap();
+    assert_eq!(grid.dimensions(), (2, 3));
+    assert_eq!(grid[(2, 1)], 6);
+    let error = grid_of_digits("123\n4x6").unwrap_err().to_string();
+    assert!(
+      error.starts_with("line 2: expected a digit in column 2"),
+      "{error}"
+    );
+    assert!(grid_of_digits("123\n45").is_err());
+  }
+}
@@ -69,7 +69,7 @@ pub fn is_prime(num: u64) -> bool {
   true
 }
 
-/// Fast modular exponentiation.
+/// Fast modular exponentiation, for any modulus that fits in `A`. Panics if `m` is 0.
 pub fn power_mod<A>(a: A, e: A, m: A) -> A
 where
   A: Copy + PartialOrd + Num + Bounded,
@@ -78,11 +78,9 @@ where
   let zero: A = Zero::zero();
   let one: A = One::one();
   let two: A = one + one;
-  let max: A = Bounded::max_value();
   assert!(m != zero);
-  assert!((m - one) < (max / (m - one)));
 
-  let mut result = one;
+  let mut result = one % m;
   let mut base = a % m;
   let mut exponent = e;
 
@@ -91,15 +89,43 @@ where
       break;
     }
     if exponent % two == one {
-      result = (result * base) % m;
+      result = mul_mod(result, base, m);
     }
     exponent = exponent >> one;
-    base = (base * base) % m;
+    base = mul_mod(base, base, m);
   }
 
   result
 }
 
+/// Computes `a * b % m` for `a` and `b` below `m`, even if the product does not fit in `A`.
+fn mul_mod<A>(a: A, b: A, m: A) -> A
+where
+  A: Copy + PartialOrd + Num + Bounded,
+  A: Shr<A, Output = A>,
+{
+  let zero: A = Zero::zero();
+  let one: A = One::one();
+  let two: A = one + one;
+  if a == zero || b <= A::max_value() / a {
+    return a * b % m;
+  }
+
+  // double and add, keeping every sum below m
+  let add = |x: A, y: A| if x >= m - y { x - (m - y) } else { x + y };
+  let mut result = zero;
+  let mut a = a;
+  let mut b = b;
+  while b > zero {
+    if b % two == one {
+      result = add(result, a);
+    }
+    a = add(a, a);
+    b = b >> one;
+  }
+  result
+}
+
 #[cfg(test)]
 mod tests {
   use crate::prime::{highest_power, is_prime, is_probably_prime};
@@ -0,0 +1,199 @@
+use std::ops::RangeInclusive;
+
+use num_traits::PrimInt;
+
+/// A set of integers, stored as sorted inclusive ranges that neither overlap nor touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+  ranges: Vec<(T, T)>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+  pub fn new() -> Self {
+    Self { ranges: vec![] }
+  }
+
+  /// The number of separate ranges.
+  pub fn len(&self) -> usize {
+    self.ranges.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  /// The ranges in increasing order.
+  pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> {
+    self.ranges.iter().map(|&(start, end)| start..=end)
+  }
+
+  /// Adds every number in `range`, merging it with the ranges it overlaps or touches.
+  pub fn insert(&mut self, range: RangeInclusive<T>) {
+    let (start, end) = range.into_inner();
+    if start > end {
+      return;
+    }
+    // ranges in first..last overlap or touch the new one
+    let first = self
+      .ranges
+      .partition_point(|(_, e)| e.saturating_add(T::one()) < start);
+    let last = self
+      .ranges
+      .partition_point(|(s, _)| *s <= end.saturating_add(T::one()));
+    let merged = if first < last {
+      (
+        start.min(self.ranges[first].0),
+        end.max(self.ranges[last - 1].1),
+      )
+    } else {
+      (start, end)
+    };
+    self.ranges.splice(first..last, [merged]);
+  }
+
+  /// Removes every number in `range`, splitting the ranges it cuts through.
+  pub fn remove(&mut self, range: RangeInclusive<T>) {
+    let (start, end) = range.into_inner();
+    if start > end {
+      return;
+    }
+    let first = self.ranges.partition_point(|(_, e)| *e < start);
+    let last = self.ranges.partition_point(|(s, _)| *s <= end);
+    if first == last {
+      return;
+    }
+    let mut kept = vec![];
+    if self.ranges[first].0 < start {
+      kept.push((self.ranges[first].0, start - T::one()));
+    }
+    if self.ranges[last - 1].1 > end {
+      kept.push((end + T::one(), self.ranges[last - 1].1));
+    }
+    self.ranges.splice(first..last, kept);
+  }
+
+  pub fn contains(&self, point: T) -> bool {
+    let i = self.ranges.partition_point(|(_, e)| *e < point);
+    self.ranges.get(i).is_some_and(|(s, _)| *s <= point)
+  }
+
+  /// The numbers in `self` but not in `other`.
+  pub fn subtract(&self, other: &Self) -> Self {
+    let mut result = self.clone();
+    for range in other.iter() {
+      result.remove(range);
+    }
+    result
+  }
+
+  /// The numbers in both `self` and `other`.
+  pub fn intersect(&self, other: &Self) -> Self {
+    let mut ranges = vec![];
+    let (mut i, mut j) = (0, 0);
+    while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+      let (start, end) = (a.0.max(b.0), a.1.min(b.1));
+      if start <= end {
+        ranges.push((start, end));
+      }
+      // the range ending first cannot overlap anything else
+      if a.1 < b.1 {
+        i += 1;
+      } else {
+        j += 1;
+      }
+    }
+    Self { ranges }
+  }
+
+  /// How many numbers are in the set. This is a u128 so that even the full range of a u64 fits.
+  pub fn total_len(&self) -> u128 {
+    self
+      .ranges
+      .iter()
+      .map(|(start, end)| {
+        let len = match end.to_i128().zip(start.to_i128()) {
+          // the difference always fits in a u128, even if it does not fit in an i128
+          Some((e, s)) => (e as u128).wrapping_sub(s as u128),
+          // only u128 does not fit in an i128
+          None => end.to_u128().unwrap() - start.to_u128().unwrap(),
+        };
+        len + 1
+      })
+      .sum()
+  }
+
+  /// The ranges between the ranges of the set, in increasing order.
+  pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> {
+    self
+      .ranges
+      .windows(2)
+      .map(|w| (w[0].1 + T::one())..=(w[1].0 - T::one()))
+  }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+  fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+    let mut set = Self::new();
+    for range in iter {
+      set.insert(range);
+    }
+    set
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ranges<T: PrimInt>(set: &IntervalSet<T>) -> Vec<RangeInclusive<T>> {
+    set.iter().collect()
+  }
+
+  #[test]
+  fn test_insert() {
+    let mut set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+    assert_eq!(ranges(&set), [3..=5, 10..=20]);
+    assert_eq!(set.total_len(), 14);
+
+    // touching ranges are merged, empty ranges ignored
+    set.insert(6..=9);
+    let (start, end) = (30, 29);
+    set.insert(start..=end);
+    assert_eq!(ranges(&set), [3..=20]);
+    set.insert(0..=0);
+    set.insert(u64::MAX..=u64::MAX);
+    assert_eq!(ranges(&set), [0..=0, 3..=20, u64::MAX..=u64::MAX]);
+    assert!(set.contains(0) && set.contains(20) && set.contains(u64::MAX));
+    assert!(!set.contains(2) && !set.contains(21));
+  }
+
+  #[test]
+  fn test_remove() {
+    let mut set: IntervalSet<i32> = [-10..=10, 20..=30].into_iter().collect();
+    set.remove(0..=0);
+    assert_eq!(ranges(&set), [-10..=-1, 1..=10, 20..=30]);
+    set.remove(5..=25);
+    assert_eq!(ranges(&set), [-10..=-1, 1..=4, 26..=30]);
+    set.remove(-100..=100);
+    assert!(set.is_empty());
+  }
+
+  #[test]
+  fn test_set_operations() {
+    let a: IntervalSet<u8> = [0..=10, 20..=30].into_iter().collect();
+    let b: IntervalSet<u8> = [5..=25].into_iter().collect();
+    assert_eq!(ranges(&a.intersect(&b)), [5..=10, 20..=25]);
+    assert_eq!(ranges(&a.subtract(&b)), [0..=4, 26..=30]);
+    assert_eq!(ranges(&b.subtract(&a)), [11..=19]);
+    assert_eq!(a.gaps().collect::<Vec<_>>(), [11..=19]);
+    assert_eq!(IntervalSet::<u8>::from_iter([0..=255]).total_len(), 256);
+    assert_eq!(
+      IntervalSet::<u128>::from_iter([0..=u128::MAX - 1]).total_len(),
+      u128::MAX
+    );
+    assert_eq!(
+      IntervalSet::<i128>::from_iter([i128::MIN..=i128::MAX - 1]).total_len(),
+      u128::MAX
+    );
+  }
+}
@@ -0,0 +1,198 @@
+use std::{fs, path::Path};
+
+use crate::input;
+
+const TEMPLATE: &str = r#"use std::fmt::Display;
+
+use aoc25::solution::{Sample, Solution, run_day};
+
+const SAMPLE_INPUT: &str = "";
+
+fn parse(input: &str) -> anyhow::Result<Vec<String>> {
+  Ok(input.lines().map(str::to_string).collect())
+}
+
+fn part_one(lines: &[String]) -> usize {
+  lines.len()
+}
+
+fn part_two(_lines: &[String]) -> usize {
+  0
+}
+
+pub struct DayDD;
+
+impl Solution for DayDD {
+  const DAY: u32 = DAY_NUMBER;
+  const TITLE: &'static str = "TODO";
+
+  type Parsed = Vec<String>;
+
+  const SAMPLES: &'static [Sample] = &[Sample {
+    input: SAMPLE_INPUT,
+    part1: None,
+    part2: None,
+  }];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+    parse(input)
+  }
+
+  fn part1(parsed: &Self::Parsed) -> impl Display {
+    part_one(parsed)
+  }
+
+  fn part2(parsed: &Self::Parsed) -> impl Display {
+    part_two(parsed)
+  }
+}
+
+fn main() -> anyhow::Result<()> {
+  run_day::<DayDD>()
+}
+
+#[cfg(test)]
+mod tests {
+  use aoc25::aoc_test;
+
+  use super::*;
+
+  aoc_test!(DayDD, part1, SAMPLE_INPUT, 0);
+  aoc_test!(DayDD, part2, SAMPLE_INPUT, 0);
+}
+"#;
+
+/// The source of a new day's binary, with placeholder parsing and answers.
+pub fn template(day: u32) -> String {
+  TEMPLATE
+    .replace("DAY_NUMBER", &day.to_string())
+    .replace("DD", &format!("{day:02}"))
+}
+
+/// Adds a day to the source of the runner, as a module and an entry in `days!`.
+/// The day is placed after the last registered one, and the list may need formatting after.
+pub fn register(runner: &str, day: u32) -> anyhow::Result<String> {
+  let name = format!("aoc{day:02}");
+  anyhow::ensure!(
+    !runner.contains(&format!("mod {name};")),
+    "day {day} is already registered"
+  );
+  let Some(start) = runner.find("days![").map(|i| i + "days![".len()) else {
+    anyhow::bail!("runner has no days! list");
+  };
+  let Some(end) = runner[start..].find(']').map(|i| start + i) else {
+    anyhow::bail!("runner has an unclosed days! list");
+  };
+  let entries = runner[start..end].trim().trim_end_matches(',');
+  let entry = format!("{name}::Day{day:02}");
+  let list = if entries.is_empty() {
+    entry
+  } else {
+    format!("{entries}, {entry}")
+  };
+  let runner = format!("{}{list}{}", &runner[..start], &runner[end..]);
+
+  let mut lines: Vec<String> = runner.lines().map(str::to_string).collect();
+  let Some(last_mod) = lines.iter().rposition(|l| l.starts_with("mod aoc")) else {
+    anyhow::bail!("runner has no day modules");
+  };
+  let module = [
+    "#[allow(dead_code)]".to_string(),
+    format!("#[path = \"{name}.rs\"]"),
+    format!("mod {name};"),
+  ];
+  lines.splice(last_mod + 1..last_mod + 1, module);
+  Ok(lines.join("\n") + "\n")
+}
+
+/// Creates the binary and an empty input for `day` in `bin_dir`, and registers it in `aoc.rs`.
+/// Existing files are never overwritten.
+pub fn create(bin_dir: &Path, day: u32) -> anyhow::Result<()> {
+  anyhow::ensure!((1..=25).contains(&day), "there is no day {day}");
+  let source = bin_dir.join(format!("aoc{day:02}.rs"));
+  anyhow::ensure!(!source.exists(), "{} already exists", source.display());
+
+  let runner_path = bin_dir.join("aoc.rs");
+  let runner = register(&fs::read_to_string(&runner_path)?, day)?;
+
+  let data = input::cache_path(&bin_dir.join("data"), day);
+  if !data.exists() {
+    fs::create_dir_all(bin_dir.join("data"))?;
+    fs::write(&data, "")?;
+  }
+  fs::write(&source, template(day))?;
+  fs::write(&runner_path, runner)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RUNNER: &str = "#[allow(dead_code)]
+#[path = \"aoc07.rs\"]
+mod aoc07;
+
+fn main() {
+  let days = days![aoc07::Day07];
+}
+";
+
+  #[test]
+  fn test_template() {
+    let source = template(8);
+    assert!(source.contains("pub struct Day08;"));
+    assert!(source.contains("const DAY: u32 = 8;"));
+    assert!(source.contains("run_day::<Day08>()"));
+  }
+
+  #[test]
+  fn test_register() {
+    let runner = register(RUNNER, 8).unwrap();
+    assert_eq!(
+      runner,
+      "#[allow(dead_code)]
+#[path = \"aoc07.rs\"]
+mod aoc07;
+#[allow(dead_code)]
+#[path = \"aoc08.rs\"]
+mod aoc08;
+
+fn main() {
+  let days = days![aoc07::Day07, aoc08::Day08];
+}
+"
+    );
+    assert!(register(&runner, 8).is_err());
+  }
+
+  #[test]
+  fn test_create() {
+    let dir = std::env::temp_dir().join("aoc25_test_scaffold");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("aoc.rs"), RUNNER).unwrap();
+
+    create(&dir, 8).unwrap();
+    assert_eq!(
+      fs::read_to_string(dir.join("aoc08.rs")).unwrap(),
+      template(8)
+    );
+    assert_eq!(fs::read_to_string(dir.join("data/08.txt")).unwrap(), "");
+    // the empty input does not count as cached, so it is still downloaded
+    let fetched = input::cached(&dir.join("data"), 8, || Ok("1 2\n".into())).unwrap();
+    assert_eq!(fetched, "1 2\n");
+    assert_eq!(
+      fs::read_to_string(dir.join("data/08.txt")).unwrap(),
+      "1 2\n"
+    );
+    assert!(
+      fs::read_to_string(dir.join("aoc.rs"))
+        .unwrap()
+        .contains("mod aoc08;")
+    );
+    assert!(create(&dir, 8).is_err());
+    assert!(create(&dir, 26).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -0,0 +1,197 @@
+use std::{
+  marker::PhantomData,
+  ops::{Bound, RangeBounds},
+};
+
+use num_traits::{Bounded, Zero};
+
+/// How a segment tree combines values. `combine` must be associative,
+/// with `identity` combining to leave the other value unchanged.
+pub trait Op<T> {
+  fn identity() -> T;
+  fn combine(a: &T, b: &T) -> T;
+}
+
+pub struct Sum;
+
+impl<T: Copy + Zero> Op<T> for Sum {
+  fn identity() -> T {
+    T::zero()
+  }
+
+  fn combine(a: &T, b: &T) -> T {
+    *a + *b
+  }
+}
+
+pub struct Min;
+
+impl<T: Copy + Ord + Bounded> Op<T> for Min {
+  fn identity() -> T {
+    T::max_value()
+  }
+
+  fn combine(a: &T, b: &T) -> T {
+    *a.min(b)
+  }
+}
+
+pub struct Max;
+
+impl<T: Copy + Ord + Bounded> Op<T> for Max {
+  fn identity() -> T {
+    T::min_value()
+  }
+
+  fn combine(a: &T, b: &T) -> T {
+    *a.max(b)
+  }
+}
+
+/// An `Op` where combining a value with itself changes nothing, so overlapping ranges
+/// can be combined without counting anything twice.
+pub trait Idempotent<T>: Op<T> {}
+
+impl<T: Copy + Ord + Bounded> Idempotent<T> for Min {}
+
+impl<T: Copy + Ord + Bounded> Idempotent<T> for Max {}
+
+/// Combines any range of values in O(log n), while allowing single values to be changed.
+pub struct SegmentTree<T, O: Op<T>> {
+  /// The leaves are in `len..2 * len`, and each node `i` above them combines `2i` and `2i + 1`.
+  nodes: Vec<T>,
+  len: usize,
+  _op: PhantomData<O>,
+}
+
+impl<T: Clone, O: Op<T>> SegmentTree<T, O> {
+  pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+    let values: Vec<T> = values.into_iter().collect();
+    let len = values.len();
+    let mut nodes = vec![O::identity(); len];
+    nodes.extend(values);
+    for i in (1..len).rev() {
+      nodes[i] = O::combine(&nodes[2 * i], &nodes[2 * i + 1]);
+    }
+    Self {
+      nodes,
+      len,
+      _op: PhantomData,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Panics if `index` is out of bounds.
+  pub fn get(&self, index: usize) -> &T {
+    assert!(index < self.len, "index out of bounds: {index}");
+    &self.nodes[self.len + index]
+  }
+
+  /// Replaces the value at `index`. Panics if it is out of bounds.
+  pub fn set(&mut self, index: usize, value: T) {
+    assert!(index < self.len, "index out of bounds: {index}");
+    let mut i = self.len + index;
+    self.nodes[i] = value;
+    while i > 1 {
+      i /= 2;
+      self.nodes[i] = O::combine(&self.nodes[2 * i], &self.nodes[2 * i + 1]);
+    }
+  }
+
+  /// The values in `range` combined in order, or the identity if it is empty.
+  /// Panics if the range goes past the end.
+  pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+    let start = match range.start_bound() {
+      Bound::Included(&s) => s,
+      Bound::Excluded(&s) => s + 1,
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(&e) => e + 1,
+      Bound::Excluded(&e) => e,
+      Bound::Unbounded => self.len,
+    };
+    assert!(end <= self.len, "range out of bounds: {start}..{end}");
+    // combine from both ends towards the middle, keeping the order for non-commutative ops
+    let (mut left, mut right) = (O::identity(), O::identity());
+    let (mut lo, mut hi) = (start + self.len, end + self.len);
+    while lo < hi {
+      if lo % 2 == 1 {
+        left = O::combine(&left, &self.nodes[lo]);
+        lo += 1;
+      }
+      if hi % 2 == 1 {
+        hi -= 1;
+        right = O::combine(&self.nodes[hi], &right);
+      }
+      lo /= 2;
+      hi /= 2;
+    }
+    O::combine(&left, &right)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_queries() {
+    let values = [5, 3, 8, 1, 9, 2, 7];
+    let mut sums: SegmentTree<i64, Sum> = SegmentTree::new(values);
+    let mut mins: SegmentTree<i64, Min> = SegmentTree::new(values);
+    let maxes: SegmentTree<i64, Max> = SegmentTree::new(values);
+    assert_eq!(sums.query(..), 35);
+    assert_eq!(sums.query(1..4), 12);
+    assert_eq!(sums.query(2..=2), 8);
+    assert_eq!(sums.query(3..3), 0);
+    assert_eq!(mins.query(0..3), 3);
+    assert_eq!(maxes.query(3..), 9);
+
+    sums.set(4, -1);
+    mins.set(4, -1);
+    assert_eq!(*sums.get(4), -1);
+    assert_eq!(sums.query(..), 25);
+    assert_eq!(mins.query(2..), -1);
+    assert_eq!(mins.query(..4), 1);
+  }
+
+  #[test]
+  fn test_matches_brute_force() {
+    let values: Vec<i32> = (0..37).map(|i| (i * 7919) % 101 - 50).collect();
+    let tree: SegmentTree<i32, Min> = SegmentTree::new(values.iter().copied());
+    for start in 0..values.len() {
+      for end in start + 1..=values.len() {
+        assert_eq!(
+          tree.query(start..end),
+          *values[start..end].iter().min().unwrap()
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_non_commutative() {
+    struct Concat;
+
+    impl Op<String> for Concat {
+      fn identity() -> String {
+        String::new()
+      }
+
+      fn combine(a: &String, b: &String) -> String {
+        format!("{a}{b}")
+      }
+    }
+
+    let tree: SegmentTree<String, Concat> = SegmentTree::new("abcdefg".chars().map(String::from));
+    assert_eq!(tree.query(1..6), "bcdef");
+  }
+}
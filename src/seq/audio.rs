@@ -0,0 +1,172 @@
+use std::{
+  f64::consts::TAU,
+  fs::File,
+  io::{BufWriter, Write},
+  path::Path,
+};
+
+use super::event::NoteEvent;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Waveform {
+  Sine,
+  Square,
+  Saw,
+}
+
+impl Waveform {
+  /// Samples the waveform at `phase`, given in cycles, in the range `-1.0..=1.0`.
+  fn sample(&self, phase: f64) -> f64 {
+    let phase = phase.fract();
+    match self {
+      Waveform::Sine => (phase * TAU).sin(),
+      Waveform::Square => {
+        if phase < 0.5 {
+          1.0
+        } else {
+          -1.0
+        }
+      }
+      Waveform::Saw => 2.0 * phase - 1.0,
+    }
+  }
+}
+
+/// A single oscillator with a linear attack/release envelope.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Synth {
+  pub waveform: Waveform,
+  /// Steps per minute.
+  pub tempo: f64,
+  pub sample_rate: u32,
+  /// Frequency of note 0, in Hz. Defaults to middle C.
+  pub root: f64,
+  /// Attack time, in seconds.
+  pub attack: f64,
+  /// Release time, in seconds.
+  pub release: f64,
+}
+
+impl Default for Synth {
+  fn default() -> Self {
+    Self {
+      waveform: Waveform::Sine,
+      tempo: 240.0,
+      sample_rate: 44100,
+      root: 261.63,
+      attack: 0.005,
+      release: 0.05,
+    }
+  }
+}
+
+impl Synth {
+  /// The frequency of `note` in equal temperament, in semitones relative to `root`.
+  pub fn frequency(&self, note: i8) -> f64 {
+    self.root * 2f64.powf(note as f64 / 12.0)
+  }
+
+  /// Renders `events` to mono samples in the range `-1.0..=1.0`.
+  pub fn render(&self, events: impl IntoIterator<Item = NoteEvent>) -> Vec<f32> {
+    let rate = self.sample_rate as f64;
+    let mut samples = vec![];
+    for event in events {
+      let seconds = event.length * 60.0 / self.tempo;
+      let len = (seconds * rate).round() as usize;
+      let Some(pitch) = event.sounding() else {
+        samples.extend(std::iter::repeat_n(0.0, len));
+        continue;
+      };
+      let freq = self.frequency(pitch);
+      let amplitude = event.velocity as f64 / 127.0;
+      samples.extend((0..len).map(|i| {
+        let t = i as f64 / rate;
+        let envelope = (t / self.attack)
+          .min((seconds - t) / self.release)
+          .clamp(0.0, 1.0);
+        (self.waveform.sample(t * freq) * envelope * amplitude * 0.5) as f32
+      }));
+    }
+    samples
+  }
+
+  /// Renders `events` into a 16 bit mono WAV file at `path`.
+  pub fn write_wav(
+    &self,
+    events: impl IntoIterator<Item = NoteEvent>,
+    path: impl AsRef<Path>,
+  ) -> anyhow::Result<()> {
+    let samples = self.render(events);
+    let mut file = BufWriter::new(File::create(path)?);
+    write_wav(&samples, self.sample_rate, &mut file)?;
+    file.flush()?;
+    Ok(())
+  }
+}
+
+/// Writes `samples` as 16 bit mono PCM in the WAV format.
+pub fn write_wav(samples: &[f32], sample_rate: u32, out: &mut impl Write) -> std::io::Result<()> {
+  // http://soundfile.sapp.org/doc/WaveFormat/
+  let data_len = (samples.len() * 2) as u32;
+  out.write_all(b"RIFF")?;
+  out.write_all(&(36 + data_len).to_le_bytes())?;
+  out.write_all(b"WAVE")?;
+  out.write_all(b"fmt ")?;
+  out.write_all(&16u32.to_le_bytes())?; // chunk size
+  out.write_all(&1u16.to_le_bytes())?; // PCM
+  out.write_all(&1u16.to_le_bytes())?; // channels
+  out.write_all(&sample_rate.to_le_bytes())?;
+  out.write_all(&(sample_rate * 2).to_le_bytes())?; // byte rate
+  out.write_all(&2u16.to_le_bytes())?; // block align
+  out.write_all(&16u16.to_le_bytes())?; // bits per sample
+  out.write_all(b"data")?;
+  out.write_all(&data_len.to_le_bytes())?;
+  for sample in samples {
+    let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+    out.write_all(&value.to_le_bytes())?;
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    assert_approx_eq,
+    seq::{Seq, Sequence, timing::TimedSequence},
+  };
+
+  use super::*;
+
+  #[test]
+  fn test_frequency() {
+    let synth = Synth::default();
+    assert_approx_eq!(synth.frequency(9), 440.0);
+    assert_approx_eq!(synth.frequency(-3), 220.0);
+    assert_approx_eq!(synth.frequency(0), 261.63);
+  }
+
+  #[test]
+  fn test_render() {
+    let synth = Synth {
+      waveform: Waveform::Square,
+      tempo: 60.0,
+      sample_rate: 100,
+      ..Default::default()
+    };
+    let events = Seq::try_from("0 .").unwrap().timed().with_velocity(127);
+    let samples = synth.render(events.take(2));
+    assert_eq!(samples.len(), 200);
+    assert!(samples[..100].iter().any(|s| *s > 0.4));
+    assert!(samples[100..].iter().all(|s| *s == 0.0));
+  }
+
+  #[test]
+  fn test_write_wav() {
+    let mut out = vec![];
+    write_wav(&[0.0, 1.0, -1.0], 8000, &mut out).unwrap();
+    assert_eq!(out.len(), 44 + 6);
+    assert_eq!(&out[0..4], b"RIFF");
+    assert_eq!(&out[8..12], b"WAVE");
+    assert_eq!(&out[44..], &[0, 0, 0xff, 0x7f, 0x01, 0x80]);
+  }
+}
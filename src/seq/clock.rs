@@ -0,0 +1,251 @@
+use std::{
+  cmp::Ordering,
+  thread,
+  time::{Duration, Instant},
+};
+
+struct Track<T> {
+  sequence: Box<dyn Iterator<Item = T>>,
+  /// Steps per beat.
+  subdivision: u64,
+  played: u64,
+}
+
+impl<T> Track<T> {
+  /// Compares when the next steps of two tracks are due, without going through floats.
+  fn cmp_next(&self, other: &Self) -> Ordering {
+    (self.played * other.subdivision).cmp(&(other.played * self.subdivision))
+  }
+
+  fn next_beat(&self) -> f64 {
+    self.played as f64 / self.subdivision as f64
+  }
+}
+
+/// Drives several sequences at a shared tempo.
+///
+/// Every step is scheduled against an absolute deadline derived from the start of the clock,
+/// so time spent between steps does not accumulate into drift.
+pub struct Clock<T = Option<i8>> {
+  /// The tracks by index, or None once their sequence has finished.
+  tracks: Vec<Option<Track<T>>>,
+  /// Beats per minute.
+  tempo: f64,
+  origin: Instant,
+  /// The beat that was playing at `origin`.
+  origin_beat: f64,
+  /// The beat at which the clock was stopped, if it is not running.
+  stopped_at: Option<f64>,
+}
+
+impl<T> Clock<T> {
+  /// Creates a stopped clock at `tempo` beats per minute.
+  pub fn new(tempo: f64) -> Self {
+    assert!(tempo > 0.0, "tempo must be positive");
+    Self {
+      tracks: vec![],
+      tempo,
+      origin: Instant::now(),
+      origin_beat: 0.0,
+      stopped_at: Some(0.0),
+    }
+  }
+
+  /// Adds a track playing `subdivision` steps of `sequence` per beat, and returns its index.
+  pub fn add_track(
+    &mut self,
+    sequence: impl Iterator<Item = T> + 'static,
+    subdivision: usize,
+  ) -> usize {
+    assert!(subdivision != 0, "subdivision cannot be 0");
+    let beat = self.beat().ceil() as u64;
+    self.tracks.push(Some(Track {
+      sequence: Box::new(sequence),
+      subdivision: subdivision as u64,
+      // new tracks join on the next beat
+      played: beat * subdivision as u64,
+    }));
+    self.tracks.len() - 1
+  }
+
+  pub fn tempo(&self) -> f64 {
+    self.tempo
+  }
+
+  /// Changes the tempo without jumping: the current beat is kept and only the following steps speed up or slow down.
+  pub fn set_tempo(&mut self, tempo: f64) {
+    assert!(tempo > 0.0, "tempo must be positive");
+    self.origin_beat = self.beat();
+    self.origin = Instant::now();
+    self.tempo = tempo;
+  }
+
+  pub fn is_running(&self) -> bool {
+    self.stopped_at.is_none()
+  }
+
+  /// Starts or resumes the clock from where it was stopped.
+  pub fn start(&mut self) {
+    if let Some(beat) = self.stopped_at.take() {
+      self.origin_beat = beat;
+      self.origin = Instant::now();
+    }
+  }
+
+  pub fn stop(&mut self) {
+    if self.is_running() {
+      self.stopped_at = Some(self.beat());
+    }
+  }
+
+  /// The current position of the clock, in beats.
+  pub fn beat(&self) -> f64 {
+    match self.stopped_at {
+      Some(beat) => beat,
+      None => self.origin_beat + self.origin.elapsed().as_secs_f64() * self.tempo / 60.0,
+    }
+  }
+
+  pub fn beat_duration(&self) -> Duration {
+    Duration::from_secs_f64(60.0 / self.tempo)
+  }
+
+  /// The duration of a single step of `track`, or None if it has finished.
+  pub fn step_duration(&self, track: usize) -> Option<Duration> {
+    let track = self.tracks[track].as_ref()?;
+    Some(self.beat_duration() / track.subdivision as u32)
+  }
+
+  /// Whether every track has finished.
+  pub fn is_finished(&self) -> bool {
+    self.tracks.iter().all(Option::is_none)
+  }
+
+  /// The track whose step is due first.
+  fn next_track(&self) -> Option<&Track<T>> {
+    self.tracks.iter().flatten().min_by(|a, b| a.cmp_next(b))
+  }
+
+  /// When the next step is due, or None if the clock is stopped or all tracks have finished.
+  pub fn next_deadline(&self) -> Option<Instant> {
+    if !self.is_running() {
+      return None;
+    }
+    let next = self.next_track()?;
+    let beats = (next.next_beat() - self.origin_beat).max(0.0);
+    Some(self.origin + self.beat_duration().mul_f64(beats))
+  }
+
+  /// Returns the steps that are due, as pairs of track index and value, without waiting.
+  /// Tracks whose sequence has ended are dropped, keeping the indices of the others.
+  /// Returns None if nothing is due yet.
+  pub fn poll(&mut self) -> Option<Vec<(usize, T)>> {
+    loop {
+      if self.next_deadline()? > Instant::now() {
+        return None;
+      }
+      let next = self.next_track()?;
+      let due: Vec<usize> = (0..self.tracks.len())
+        .filter(|i| {
+          self.tracks[*i]
+            .as_ref()
+            .is_some_and(|track| track.cmp_next(next).is_eq())
+        })
+        .collect();
+      let mut steps = vec![];
+      for i in due {
+        let track = self.tracks[i].as_mut().expect("due tracks are playing");
+        track.played += 1;
+        match track.sequence.next() {
+          Some(value) => steps.push((i, value)),
+          None => self.tracks[i] = None,
+        }
+      }
+      // if every due track had ended, look again for the steps after them
+      if !steps.is_empty() {
+        return Some(steps);
+      }
+    }
+  }
+}
+
+impl<T> Iterator for Clock<T> {
+  type Item = Vec<(usize, T)>;
+
+  /// Waits until the next steps are due and returns them.
+  /// Returns None if the clock is stopped or all tracks have finished.
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let deadline = self.next_deadline()?;
+      thread::sleep(deadline.saturating_duration_since(Instant::now()));
+      if let Some(steps) = self.poll() {
+        return Some(steps);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::seq::Seq;
+
+  use super::*;
+
+  #[test]
+  fn test_subdivisions() {
+    let mut clock = Clock::new(6000.0);
+    clock.add_track(Seq::from([1, 2]), 1);
+    clock.add_track(Seq::from([5, 6, 7]), 2);
+    clock.start();
+
+    let start = Instant::now();
+    let ticks: Vec<_> = clock.by_ref().take(5).collect();
+    assert_eq!(
+      ticks,
+      [
+        vec![(0, Some(1)), (1, Some(5))],
+        vec![(1, Some(6))],
+        vec![(0, Some(2)), (1, Some(7))],
+        vec![(1, Some(5))],
+        vec![(0, Some(1)), (1, Some(6))],
+      ]
+    );
+    // the fifth tick is due two beats of 10ms in
+    assert!(start.elapsed() >= Duration::from_millis(20));
+  }
+
+  #[test]
+  fn test_stop() {
+    let mut clock = Clock::new(60.0);
+    clock.add_track(Seq::from(1), 1);
+    assert!(clock.next().is_none());
+
+    clock.start();
+    assert_eq!(clock.next(), Some(vec![(0, Some(1))]));
+    clock.stop();
+    assert!(clock.next_deadline().is_none());
+    assert!(clock.beat() < 1.0);
+  }
+
+  #[test]
+  fn test_finished_tracks() {
+    let mut clock = Clock::new(6000.0);
+    clock.add_track([1, 2, 3].into_iter(), 1);
+    clock.add_track([5].into_iter(), 2);
+    clock.start();
+    let ticks: Vec<_> = clock.by_ref().collect();
+    assert_eq!(ticks, [vec![(0, 1), (1, 5)], vec![(0, 2)], vec![(0, 3)],]);
+    assert!(clock.is_finished());
+    assert_eq!(clock.poll(), None);
+    assert_eq!(clock.step_duration(0), None);
+  }
+
+  #[test]
+  fn test_set_tempo() {
+    let mut clock: Clock = Clock::new(60.0);
+    clock.start();
+    clock.set_tempo(120.0);
+    assert!(clock.beat() < 0.1);
+    assert_eq!(clock.beat_duration(), Duration::from_millis(500));
+  }
+}
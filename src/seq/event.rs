@@ -0,0 +1,57 @@
+/// A note with dynamics, as sent to MIDI or OSC outputs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoteEvent {
+  pub pitch: Option<i8>,
+  pub velocity: u8,
+  pub length: f64,
+}
+
+impl NoteEvent {
+  pub fn new(pitch: impl Into<Option<i8>>, velocity: u8, length: f64) -> Self {
+    Self {
+      pitch: pitch.into(),
+      velocity,
+      length,
+    }
+  }
+
+  /// The pitch to play, or None if this is a rest.
+  pub fn sounding(&self) -> Option<i8> {
+    self.pitch.filter(|_| self.velocity > 0)
+  }
+
+  /// A rest is either a step without a pitch, or a note played with no velocity.
+  pub fn is_rest(&self) -> bool {
+    self.sounding().is_none()
+  }
+}
+
+pub trait EventSequence: Iterator<Item = NoteEvent> {}
+
+impl<T: Iterator<Item = NoteEvent>> EventSequence for T {}
+
+#[cfg(test)]
+mod tests {
+  use crate::seq::{Seq, Sequence, timing::TimedSequence};
+
+  use super::*;
+
+  #[test]
+  fn test_with_velocity() {
+    let seq = Seq::try_from("0 4 . 7").unwrap();
+    let events: Vec<_> = seq.timed().with_velocity([100, -5]).take(4).collect();
+    assert_eq!(
+      events,
+      [
+        NoteEvent::new(0, 100, 1.0),
+        NoteEvent::new(4, 0, 1.0),
+        NoteEvent::new(None, 100, 1.0),
+        NoteEvent::new(7, 0, 1.0),
+      ]
+    );
+    assert_eq!(
+      events.iter().map(NoteEvent::is_rest).collect::<Vec<_>>(),
+      [false, true, true, true]
+    );
+  }
+}
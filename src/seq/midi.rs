@@ -0,0 +1,107 @@
+use midir::{MidiOutput, MidiOutputConnection};
+
+/// MIDI note number of pitch 0 (middle C).
+pub const MIDDLE_C: u8 = 60;
+
+/// Converts a sequence pitch to a MIDI note number, or `None` if it is out of range.
+pub fn note_number(pitch: i8) -> Option<u8> {
+  u8::try_from(pitch as i16 + MIDDLE_C as i16)
+    .ok()
+    .filter(|n| *n < 128)
+}
+
+pub fn note_on(channel: u8, note: u8, velocity: u8) -> [u8; 3] {
+  [0x90 | (channel & 0x0f), note & 0x7f, velocity & 0x7f]
+}
+
+pub fn note_off(channel: u8, note: u8) -> [u8; 3] {
+  [0x80 | (channel & 0x0f), note & 0x7f, 0]
+}
+
+/// Sends the steps of a clock to a MIDI output port, one channel per track.
+pub struct MidiSender {
+  connection: MidiOutputConnection,
+  velocity: u8,
+  sounding: Vec<(u8, u8)>,
+}
+
+impl MidiSender {
+  /// Connects to the first output port whose name contains `port`, or to the port with that index.
+  pub fn connect(port: &str) -> anyhow::Result<Self> {
+    let output = MidiOutput::new("aoc25")?;
+    let ports = output.ports();
+    let found = match port.parse::<usize>() {
+      Ok(index) => ports.get(index).cloned(),
+      Err(_) => ports
+        .into_iter()
+        .find(|p| output.port_name(p).is_ok_and(|name| name.contains(port))),
+    };
+    let Some(found) = found else {
+      anyhow::bail!("no MIDI output port matching {port:?}");
+    };
+    let connection = output
+      .connect(&found, "aoc25")
+      .map_err(|e| anyhow::anyhow!("could not connect to MIDI port: {e}"))?;
+    Ok(Self {
+      connection,
+      velocity: 100,
+      sounding: vec![],
+    })
+  }
+
+  pub fn with_velocity(mut self, velocity: u8) -> Self {
+    self.velocity = velocity.min(127);
+    self
+  }
+
+  /// Ends the notes of the previous step and starts the given ones.
+  /// Rests and pitches outside the MIDI range are skipped.
+  pub fn play_step(&mut self, notes: &[(usize, Option<i8>)]) -> anyhow::Result<()> {
+    self.release()?;
+    for &(track, pitch) in notes {
+      let Some(note) = pitch.and_then(note_number) else {
+        continue;
+      };
+      let channel = (track % 16) as u8;
+      self
+        .connection
+        .send(&note_on(channel, note, self.velocity))?;
+      self.sounding.push((channel, note));
+    }
+    Ok(())
+  }
+
+  /// Ends all notes that are currently sounding.
+  pub fn release(&mut self) -> anyhow::Result<()> {
+    for (channel, note) in self.sounding.drain(..) {
+      self.connection.send(&note_off(channel, note))?;
+    }
+    Ok(())
+  }
+}
+
+impl Drop for MidiSender {
+  fn drop(&mut self) {
+    let _ = self.release();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_note_number() {
+    assert_eq!(note_number(0), Some(60));
+    assert_eq!(note_number(-60), Some(0));
+    assert_eq!(note_number(67), Some(127));
+    assert_eq!(note_number(68), None);
+    assert_eq!(note_number(-61), None);
+  }
+
+  #[test]
+  fn test_messages() {
+    assert_eq!(note_on(1, 60, 100), [0x91, 60, 100]);
+    assert_eq!(note_off(15, 61), [0x8f, 61, 0]);
+  }
+}
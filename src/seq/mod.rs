@@ -1,130 +1,334 @@
-use std::iter::Peekable;
+use std::{fmt::Debug, iter::Peekable, str::FromStr};
 
-use crate::exts::iterator::IteratorExt;
+use num_traits::{PrimInt, WrappingAdd, WrappingSub};
+use rand::{Rng, SeedableRng, distr::uniform::SampleUniform, rngs::StdRng};
+
+use crate::exts::{iterator::IteratorExt, numbers::UnsignedExt};
+
+pub mod audio;
+pub mod clock;
+pub mod event;
+#[cfg(feature = "midi")]
+pub mod midi;
+#[cfg(feature = "osc")]
+pub mod osc;
+pub mod timing;
+
+use timing::{Step, TimedSequence};
 
 // Introduce own trait for infinite sequences?
 // Pros:
-//  - Can have Option<i8> instead of SILENCE sentinel
 //  - Could return Silence/Note(i8)/Sustain enum
 // Cons:
 //  - No iterator interop
 //  - Would have to reimpl a bunch of iterator methods (which might be fun)
 
-pub trait Sequence: Iterator<Item = i8> {}
+/// A sequence of notes, where `None` is a rest.
+pub trait Sequence: Iterator<Item = Option<i8>> {
+  /// Gives every step of the sequence an equal length of 1.
+  fn timed(self) -> impl TimedSequence
+  where
+    Self: Sized,
+  {
+    self.map(|note| Step::new(note, 1.0))
+  }
+
+  /// Rests on every step where `mask` is either 0 or a rest.
+  fn mask(self, mask: impl Into<Seq>) -> impl Sequence
+  where
+    Self: Sized,
+  {
+    self
+      .zip(mask.into())
+      .map(|(note, keep)| note.filter(|_| keep.is_some_and(|k| k != 0)))
+  }
+}
+
+impl<T: Iterator<Item = Option<i8>>> Sequence for T {}
 
-impl<T: Iterator<Item = i8>> Sequence for T {}
+/// Values a sequence can be made of, such as pitches, velocities or CC values.
+pub trait SeqValue: PrimInt + WrappingAdd + WrappingSub + SampleUniform + Debug {}
 
-pub const SILENCE: i8 = i8::MIN;
+impl<T: PrimInt + WrappingAdd + WrappingSub + SampleUniform + Debug> SeqValue for T {}
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-enum SeqToken {
-  Silence,
+enum SeqToken<T> {
+  Rest,
   Repeat,
-  Num(i8),
-  SubSequence(Seq),
-  Random { min: i8, max: i8 },
+  Num(T),
+  SubSequence(Seq<T>),
+  Random { min: T, max: T },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Seq {
-  pattern: Vec<SeqToken>,
+pub struct Seq<T = i8> {
+  pattern: Vec<SeqToken<T>>,
   index: usize,
-  last: i8,
+  last: Option<T>,
+  rng: Option<Box<StdRng>>,
 }
 
-impl Seq {
-  fn new(pattern: Vec<SeqToken>) -> Self {
+impl<T: SeqValue> Seq<T> {
+  fn new(pattern: Vec<SeqToken<T>>) -> Self {
     Self {
       pattern,
       index: 0,
-      last: 0,
+      last: None,
+      rng: None,
+    }
+  }
+
+  /// Makes random tokens reproducible, including those in subsequences.
+  pub fn with_seed(mut self, seed: u64) -> Self {
+    self.seed(&mut StdRng::seed_from_u64(seed));
+    self
+  }
+
+  fn seed(&mut self, seeder: &mut StdRng) {
+    self.rng = Some(Box::new(StdRng::from_rng(seeder)));
+    for token in self.pattern.iter_mut() {
+      if let SeqToken::SubSequence(seq) = token {
+        seq.seed(seeder);
+      }
     }
   }
 
-  pub fn rand(min: i8, max: i8) -> Self {
+  pub fn rand(min: T, max: T) -> Self {
     Self::new(vec![SeqToken::Random {
       min: min.min(max),
       max: min.max(max),
     }])
   }
 
+  /// The number of steps before the sequence repeats itself.
+  /// Random tokens are counted as a single step, even though their values differ between periods.
+  pub fn period(&self) -> usize {
+    // each subsequence only advances once per pass through the pattern
+    let passes = self
+      .pattern
+      .iter()
+      .filter_map(|token| match token {
+        SeqToken::SubSequence(seq) => Some(seq.period()),
+        _ => None,
+      })
+      .fold(1, |acc, p| acc.lcm(p));
+    self.pattern.len() * passes
+  }
+
+  /// Collects a single period of the sequence, starting from its current position.
+  pub fn cycle_vec(&self) -> Vec<Option<T>> {
+    self.clone().take(self.period()).collect()
+  }
+
+  // combining with a rest on either side gives a rest
+
   #[allow(clippy::should_implement_trait)]
-  pub fn add(self, rhs: impl Into<Seq>) -> impl Sequence {
-    self.zip(rhs.into()).map(|(l, r)| l.wrapping_add(r))
+  pub fn add(self, rhs: impl Into<Seq<T>>) -> impl Iterator<Item = Option<T>> {
+    self
+      .zip(rhs.into())
+      .map(|(l, r)| Some(l?.wrapping_add(&r?)))
   }
 
   #[allow(clippy::should_implement_trait)]
-  pub fn sub(self, rhs: impl Into<Seq>) -> impl Sequence {
-    self.zip(rhs.into()).map(|(l, r)| l.wrapping_sub(r))
+  pub fn sub(self, rhs: impl Into<Seq<T>>) -> impl Iterator<Item = Option<T>> {
+    self
+      .zip(rhs.into())
+      .map(|(l, r)| Some(l?.wrapping_sub(&r?)))
   }
 
-  pub fn max(self, rhs: impl Into<Seq>) -> impl Sequence {
-    self.zip(rhs.into()).map(|(l, r)| l.max(r))
+  pub fn max(self, rhs: impl Into<Seq<T>>) -> impl Iterator<Item = Option<T>> {
+    self.zip(rhs.into()).map(|(l, r)| Some(l?.max(r?)))
   }
 
-  pub fn min(self, rhs: impl Into<Seq>) -> impl Sequence {
-    self.zip(rhs.into()).map(|(l, r)| l.min(r))
+  pub fn min(self, rhs: impl Into<Seq<T>>) -> impl Iterator<Item = Option<T>> {
+    self.zip(rhs.into()).map(|(l, r)| Some(l?.min(r?)))
   }
 
-  pub fn clamp(self, min: i8, max: i8) -> impl Sequence {
-    self.map(move |x| x.clamp(min, max))
+  pub fn clamp(self, min: T, max: T) -> impl Iterator<Item = Option<T>> {
+    self.map(move |x| x.map(|x| x.clamp(min, max)))
   }
 
-  pub fn slow(self, x: usize) -> impl Sequence {
+  pub fn slow(self, x: usize) -> impl Iterator<Item = Option<T>> {
     self.repeat_each(x)
   }
 }
 
-impl From<i8> for Seq {
-  fn from(value: i8) -> Self {
-    Self::new(vec![SeqToken::Num(value)])
+/// The number of steps before all `patterns`, played together, line up again.
+pub fn align<T: SeqValue>(patterns: &[Seq<T>]) -> usize {
+  patterns
+    .iter()
+    .map(Seq::period)
+    .fold(1, |acc, p| acc.lcm(p))
+}
+
+/// Plays several voices against each other, yielding one note per voice for every step.
+pub struct ChordSequence<S> {
+  voices: Vec<S>,
+}
+
+/// Stacks `sequences` into a single sequence of chords. Resting voices are yielded as rests,
+/// so the position of each note in a chord identifies its voice.
+pub fn stack<S: Iterator>(sequences: impl IntoIterator<Item = S>) -> ChordSequence<S> {
+  ChordSequence {
+    voices: sequences.into_iter().collect(),
+  }
+}
+
+impl<S: Iterator> Iterator for ChordSequence<S> {
+  type Item = Vec<S::Item>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.voices.iter_mut().map(|voice| voice.next()).collect()
   }
 }
 
-impl From<&[i8]> for Seq {
-  fn from(value: &[i8]) -> Self {
+macro_rules! impl_from_value {
+  ($type:ty) => {
+    impl From<$type> for Seq<$type> {
+      fn from(value: $type) -> Self {
+        Self::new(vec![SeqToken::Num(value)])
+      }
+    }
+  };
+}
+
+impl_from_value!(i8);
+impl_from_value!(u8);
+impl_from_value!(i16);
+impl_from_value!(u16);
+impl_from_value!(i32);
+impl_from_value!(u32);
+impl_from_value!(i64);
+impl_from_value!(u64);
+
+impl<T: SeqValue> From<&[T]> for Seq<T> {
+  fn from(value: &[T]) -> Self {
     Self::new(value.iter().copied().map(SeqToken::Num).collect())
   }
 }
 
-impl From<Vec<i8>> for Seq {
-  fn from(value: Vec<i8>) -> Self {
+impl<T: SeqValue> From<Vec<T>> for Seq<T> {
+  fn from(value: Vec<T>) -> Self {
+    value.as_slice().into()
+  }
+}
+
+impl<T: SeqValue, const L: usize> From<[T; L]> for Seq<T> {
+  fn from(value: [T; L]) -> Self {
     value.as_slice().into()
   }
 }
 
-impl<const L: usize> From<[i8; L]> for Seq {
-  fn from(value: [i8; L]) -> Self {
+/// Builds a sequence from collected steps, such as the output of `cycle_vec`.
+impl<T: SeqValue> From<&[Option<T>]> for Seq<T> {
+  fn from(value: &[Option<T>]) -> Self {
+    Self::new(
+      value
+        .iter()
+        .map(|x| x.map_or(SeqToken::Rest, SeqToken::Num))
+        .collect(),
+    )
+  }
+}
+
+impl<T: SeqValue> From<Vec<Option<T>>> for Seq<T> {
+  fn from(value: Vec<Option<T>>) -> Self {
     value.as_slice().into()
   }
 }
 
+pub(crate) fn read_num<T: PrimInt, I: Iterator<Item = char>>(
+  chars: &mut Peekable<I>,
+) -> anyhow::Result<T> {
+  let ten = T::from(10).ok_or(anyhow::anyhow!("overflow"))?;
+  let mut acc = T::zero();
+  while let Some(c) = chars.peek() {
+    let c = *c;
+    if c.is_numeric() {
+      chars.next();
+      let digit = T::from((c as u8) - b'0').ok_or(anyhow::anyhow!("overflow"))?;
+      acc = acc
+        .checked_mul(&ten)
+        .ok_or(anyhow::anyhow!("overflow"))?
+        .checked_add(&digit)
+        .ok_or(anyhow::anyhow!("overflow"))?
+    } else {
+      break;
+    }
+  }
+  Ok(acc)
+}
+
+/// Reads a negative number, after the leading `-`.
+pub(crate) fn read_neg<T: PrimInt, I: Iterator<Item = char>>(
+  chars: &mut Peekable<I>,
+) -> anyhow::Result<T> {
+  let next = chars.peek().ok_or(anyhow::anyhow!("expected number"))?;
+  if !next.is_numeric() {
+    anyhow::bail!("expected numeric, got: {}", next)
+  }
+  T::zero()
+    .checked_sub(&read_num(chars)?)
+    .ok_or(anyhow::anyhow!("overflow"))
+}
+
+/// Reads a note name such as `c4`, `f#3` or `eb`, as a semitone offset from `c4`.
+/// The octave defaults to 4 when omitted.
+pub(crate) fn read_note<I: Iterator<Item = char>>(chars: &mut Peekable<I>) -> anyhow::Result<i8> {
+  let name = chars.next().ok_or(anyhow::anyhow!("expected note"))?;
+  let semitone: i8 = match name.to_ascii_lowercase() {
+    'c' => 0,
+    'd' => 2,
+    'e' => 4,
+    'f' => 5,
+    'g' => 7,
+    'a' => 9,
+    'b' => 11,
+    other => anyhow::bail!("invalid note name: {}", other),
+  };
+  let accidental: i8 = match chars.peek() {
+    Some('#') => {
+      chars.next();
+      1
+    }
+    Some('b') => {
+      chars.next();
+      -1
+    }
+    _ => 0,
+  };
+  let octave: i8 = match chars.peek() {
+    Some(c) if c.is_numeric() => read_num(chars)?,
+    _ => 4,
+  };
+  (octave - 4)
+    .checked_mul(12)
+    .and_then(|x| x.checked_add(semitone + accidental))
+    .ok_or(anyhow::anyhow!("overflow"))
+}
+
+/// Reads a note name as a value of `T`.
+pub(crate) fn read_note_as<T: PrimInt, I: Iterator<Item = char>>(
+  chars: &mut Peekable<I>,
+) -> anyhow::Result<T> {
+  T::from(read_note(chars)?).ok_or(anyhow::anyhow!("note out of range"))
+}
+
 impl TryFrom<&str> for Seq {
   type Error = anyhow::Error;
 
   fn try_from(text: &str) -> Result<Self, Self::Error> {
-    fn read_num<I: Iterator<Item = char>>(chars: &mut Peekable<I>) -> anyhow::Result<i8> {
-      fn to_num(c: char) -> i8 {
-        ((c as u8) - b'0') as i8
-      }
-      let mut acc: i8 = 0;
-      while let Some(c) = chars.peek() {
-        let c = *c;
-        if c.is_numeric() {
-          chars.next();
-          acc = acc
-            .checked_mul(10)
-            .ok_or(anyhow::anyhow!("overflow"))?
-            .checked_add(to_num(c))
-            .ok_or(anyhow::anyhow!("overflow"))?
-        } else {
-          break;
-        }
-      }
-      Ok(acc)
-    }
+    text.parse()
+  }
+}
+
+impl<T: SeqValue> FromStr for Seq<T> {
+  type Err = anyhow::Error;
 
-    fn parse<I: Iterator<Item = char>>(chars: &mut Peekable<I>) -> anyhow::Result<Vec<SeqToken>> {
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    fn parse<T: SeqValue, I: Iterator<Item = char>>(
+      chars: &mut Peekable<I>,
+    ) -> anyhow::Result<Vec<SeqToken<T>>> {
       let mut res = vec![];
       while let Some(c) = chars.peek() {
         let c = *c;
@@ -142,20 +346,17 @@ impl TryFrom<&str> for Seq {
           }
           '-' => {
             chars.next();
-            let next = chars.peek().ok_or(anyhow::anyhow!("expected number"))?;
-            if !next.is_numeric() {
-              anyhow::bail!("expected numeric, got: {}", next)
-            }
-            res.push(SeqToken::Num(-read_num(chars)?))
+            res.push(SeqToken::Num(read_neg(chars)?))
           }
           '0'..='9' => res.push(SeqToken::Num(read_num(chars)?)),
+          'a'..='g' | 'A'..='G' => res.push(SeqToken::Num(read_note_as(chars)?)),
           '_' => {
             chars.next();
             res.push(SeqToken::Repeat)
           }
           '.' => {
             chars.next();
-            res.push(SeqToken::Silence)
+            res.push(SeqToken::Rest)
           }
           tok => anyhow::bail!("unexpected token: {}", tok),
         }
@@ -170,8 +371,8 @@ impl TryFrom<&str> for Seq {
   }
 }
 
-impl Iterator for Seq {
-  type Item = i8;
+impl<T: SeqValue> Iterator for Seq<T> {
+  type Item = Option<T>;
 
   fn next(&mut self) -> Option<Self::Item> {
     let index = {
@@ -180,11 +381,14 @@ impl Iterator for Seq {
       index
     };
     let res = match self.pattern.get_mut(index).unwrap() {
-      SeqToken::Silence => SILENCE,
+      SeqToken::Rest => None,
       SeqToken::Repeat => self.last,
-      SeqToken::Num(num) => *num,
+      SeqToken::Num(num) => Some(*num),
       SeqToken::SubSequence(pattern_seq) => pattern_seq.next().expect("infinite iterator"),
-      SeqToken::Random { min, max } => rand::random_range(*min..=*max),
+      SeqToken::Random { min, max } => Some(match &mut self.rng {
+        Some(rng) => rng.random_range(*min..=*max),
+        None => rand::random_range(*min..=*max),
+      }),
     };
     self.last = res;
     Some(res)
@@ -199,11 +403,11 @@ mod tests {
   fn test_simple() {
     let seq1 = Seq::from(1);
 
-    assert_eq!(seq1.take(5).collect::<Vec<_>>(), [1, 1, 1, 1, 1]);
+    assert_eq!(seq1.take(5).flatten().collect::<Vec<_>>(), [1, 1, 1, 1, 1]);
 
     let seq2: Seq = Seq::from([1, 2, 3]);
     assert_eq!(
-      seq2.take(10).collect::<Vec<_>>(),
+      seq2.take(10).flatten().collect::<Vec<_>>(),
       vec![1, 2, 3, 1, 2, 3, 1, 2, 3, 1]
     );
   }
@@ -213,12 +417,21 @@ mod tests {
     let seq1 = Seq::from([1, 2, 3]);
 
     assert_eq!(
-      seq1.clone().add([1, -1]).take(10).collect::<Vec<_>>(),
+      seq1
+        .clone()
+        .add([1, -1])
+        .take(10)
+        .flatten()
+        .collect::<Vec<_>>(),
       [2, 1, 4, 0, 3, 2, 2, 1, 4, 0]
     );
 
     assert_eq!(
-      seq1.add(Seq::from([1, -1])).take(10).collect::<Vec<_>>(),
+      seq1
+        .add(Seq::from([1, -1]))
+        .take(10)
+        .flatten()
+        .collect::<Vec<_>>(),
       [2, 1, 4, 0, 3, 2, 2, 1, 4, 0]
     );
   }
@@ -227,7 +440,7 @@ mod tests {
   fn test_transpose() {
     let seq1 = Seq::from([1, 2, 3, -1, 0]);
     assert_eq!(
-      seq1.add(-4).take(10).collect::<Vec<_>>(),
+      seq1.add(-4).take(10).flatten().collect::<Vec<_>>(),
       [-3, -2, -1, -5, -4, -3, -2, -1, -5, -4]
     )
   }
@@ -235,20 +448,20 @@ mod tests {
   #[test]
   fn test_pattern_simple() {
     let seq1 = Seq::try_from("1 2 3").unwrap();
-    assert_eq!(seq1.take(5).collect::<Vec<_>>(), [1, 2, 3, 1, 2])
+    assert_eq!(seq1.take(5).flatten().collect::<Vec<_>>(), [1, 2, 3, 1, 2])
   }
 
   #[test]
   fn test_pattern_repeat() {
     let seq1 = Seq::try_from("1 2 _").unwrap();
     assert_eq!(
-      seq1.take(10).collect::<Vec<_>>(),
+      seq1.take(10).flatten().collect::<Vec<_>>(),
       [1, 2, 2, 1, 2, 2, 1, 2, 2, 1]
     );
 
     let seq2 = Seq::try_from("1 2__ ").unwrap();
     assert_eq!(
-      seq2.take(10).collect::<Vec<_>>(),
+      seq2.take(10).flatten().collect::<Vec<_>>(),
       [1, 2, 2, 2, 1, 2, 2, 2, 1, 2]
     );
   }
@@ -257,13 +470,13 @@ mod tests {
   fn test_pattern_subsequence() {
     let seq1 = Seq::try_from("1 <2 3> 4").unwrap();
     assert_eq!(
-      seq1.take(10).collect::<Vec<_>>(),
+      seq1.take(10).flatten().collect::<Vec<_>>(),
       [1, 2, 4, 1, 3, 4, 1, 2, 4, 1]
     );
 
     let seq2: Seq = Seq::try_from("<2 3>").unwrap();
     assert_eq!(
-      seq2.take(10).collect::<Vec<_>>(),
+      seq2.take(10).flatten().collect::<Vec<_>>(),
       [2, 3, 2, 3, 2, 3, 2, 3, 2, 3]
     );
   }
@@ -272,8 +485,122 @@ mod tests {
   fn test_pattern_subsequence_nested() {
     let seq1 = Seq::try_from("<2 1> <2 <3 5>>").unwrap();
     assert_eq!(
-      seq1.take(10).collect::<Vec<_>>(),
+      seq1.take(10).flatten().collect::<Vec<_>>(),
       [2, 2, 1, 3, 2, 2, 1, 5, 2, 2]
     );
   }
+
+  #[test]
+  fn test_pattern_notes() {
+    let seq1 = Seq::try_from("c4 e4 g4 . a3").unwrap();
+    assert_eq!(
+      seq1.take(5).collect::<Vec<_>>(),
+      [Some(0), Some(4), Some(7), None, Some(-3)]
+    );
+
+    let seq2 = Seq::try_from("c#4 eb bb3 C5").unwrap();
+    assert_eq!(seq2.take(4).flatten().collect::<Vec<_>>(), [1, 3, -2, 12]);
+
+    assert!(Seq::try_from("h4").is_err());
+  }
+
+  #[test]
+  fn test_mask() {
+    let seq = Seq::from([1, 2, 3]).mask(Seq::try_from("1 0 1 .").unwrap());
+    assert_eq!(
+      seq.take(6).collect::<Vec<_>>(),
+      [Some(1), None, Some(3), None, Some(2), None]
+    );
+  }
+
+  #[test]
+  fn test_period() {
+    assert_eq!(Seq::from([1, 2, 3]).period(), 3);
+    assert_eq!(Seq::try_from("1 2 _ .").unwrap().period(), 4);
+    assert_eq!(Seq::try_from("1 <2 3> 4").unwrap().period(), 6);
+    assert_eq!(Seq::try_from("<2 1> <2 <3 5>>").unwrap().period(), 8);
+  }
+
+  #[test]
+  fn test_cycle_vec() {
+    let mut seq = Seq::try_from("1 <2 3> .").unwrap();
+    assert_eq!(
+      seq.cycle_vec(),
+      [Some(1), Some(2), None, Some(1), Some(3), None]
+    );
+    seq.next();
+    assert_eq!(
+      seq.cycle_vec(),
+      [Some(2), None, Some(1), Some(3), None, Some(1)]
+    );
+  }
+
+  #[test]
+  fn test_align() {
+    let patterns = [Seq::from([1, 2, 3]), Seq::from([1, 2]), Seq::from(4)];
+    assert_eq!(align(&patterns), 6);
+    assert_eq!(align::<i8>(&[]), 1);
+  }
+
+  #[test]
+  fn test_stack() {
+    let chords = stack([Seq::from([0, 4]), Seq::try_from("7 . 9").unwrap()]);
+    assert_eq!(
+      chords.take(4).collect::<Vec<_>>(),
+      [
+        vec![Some(0), Some(7)],
+        vec![Some(4), None],
+        vec![Some(0), Some(9)],
+        vec![Some(4), Some(7)]
+      ]
+    );
+  }
+
+  #[test]
+  fn test_generic() {
+    let velocities: Seq<u8> = "100 . 200 _".parse().unwrap();
+    assert_eq!(
+      velocities.take(4).collect::<Vec<_>>(),
+      [Some(100), None, Some(200), Some(200)]
+    );
+    assert!("-1".parse::<Seq<u8>>().is_err());
+    assert!("300".parse::<Seq<u8>>().is_err());
+
+    let bend: Seq<i16> = "-8192 0 c5".parse().unwrap();
+    let bend = Seq::from(bend.add(1000).take(3).collect::<Vec<_>>());
+    assert_eq!(bend.cycle_vec(), [Some(-7192), Some(1000), Some(1012)]);
+  }
+
+  #[test]
+  fn test_rests() {
+    let seq = Seq::try_from("1 . 3 _").unwrap();
+    assert_eq!(
+      seq
+        .clone()
+        .add(vec![Some(1), Some(2), None])
+        .take(4)
+        .collect::<Vec<_>>(),
+      [Some(2), None, None, Some(4)]
+    );
+    assert_eq!(
+      seq.clone().max(2).take(4).collect::<Vec<_>>(),
+      [Some(2), None, Some(3), Some(3)]
+    );
+    assert_eq!(
+      seq.clamp(0, 2).take(4).collect::<Vec<_>>(),
+      [Some(1), None, Some(2), Some(2)]
+    );
+    // a rest is held by a following `_`
+    let held = Seq::try_from("1 . _").unwrap();
+    assert_eq!(held.cycle_vec(), [Some(1), None, None]);
+  }
+
+  #[test]
+  fn test_seed() {
+    let a: Vec<_> = Seq::rand(-50, 50).with_seed(4711).take(20).collect();
+    let b: Vec<_> = Seq::rand(-50, 50).with_seed(4711).take(20).collect();
+    assert_eq!(a, b);
+    let c: Vec<_> = Seq::rand(-50, 50).with_seed(1337).take(20).collect();
+    assert_ne!(a, c);
+  }
 }
@@ -0,0 +1,146 @@
+use std::{
+  io,
+  net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+};
+
+use super::{clock::Clock, event::NoteEvent};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OscArg {
+  Int(i32),
+  Float(f32),
+  Str(String),
+}
+
+/// Encodes a single OSC message.
+/// https://opensoundcontrol.stanford.edu/spec-1_0.html
+pub fn encode_message(address: &str, args: &[OscArg]) -> Vec<u8> {
+  fn push_str(buf: &mut Vec<u8>, text: &str) {
+    buf.extend(text.as_bytes());
+    // strings are null terminated and padded to a multiple of 4 bytes
+    let padding = 4 - text.len() % 4;
+    buf.extend(std::iter::repeat_n(0, padding));
+  }
+
+  let mut buf = vec![];
+  push_str(&mut buf, address);
+  let tags: String = std::iter::once(',')
+    .chain(args.iter().map(|arg| match arg {
+      OscArg::Int(_) => 'i',
+      OscArg::Float(_) => 'f',
+      OscArg::Str(_) => 's',
+    }))
+    .collect();
+  push_str(&mut buf, &tags);
+  for arg in args {
+    match arg {
+      OscArg::Int(i) => buf.extend(i.to_be_bytes()),
+      OscArg::Float(f) => buf.extend(f.to_be_bytes()),
+      OscArg::Str(s) => push_str(&mut buf, s),
+    }
+  }
+  buf
+}
+
+/// Sends notes as OSC messages over UDP, e.g. to SuperCollider.
+pub struct OscSender {
+  socket: UdpSocket,
+  address: String,
+}
+
+impl OscSender {
+  /// Connects to `target`, sending messages to the `/note` address.
+  pub fn connect(target: impl ToSocketAddrs) -> io::Result<Self> {
+    let target = target
+      .to_socket_addrs()?
+      .next()
+      .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no address to connect to"))?;
+    // the local socket must be of the same family as the target
+    let local: SocketAddr = match target {
+      SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+      SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.connect(target)?;
+    Ok(Self {
+      socket,
+      address: "/note".to_string(),
+    })
+  }
+
+  pub fn with_address(mut self, address: impl Into<String>) -> Self {
+    self.address = address.into();
+    self
+  }
+
+  /// Sends `event` as `<address> pitch velocity seconds`. Rests are not sent.
+  pub fn send(&self, event: &NoteEvent, seconds: f64) -> io::Result<()> {
+    let Some(pitch) = event.sounding() else {
+      return Ok(());
+    };
+    let message = encode_message(
+      &self.address,
+      &[
+        OscArg::Int(pitch as i32),
+        OscArg::Int(event.velocity as i32),
+        OscArg::Float(seconds as f32),
+      ],
+    );
+    self.socket.send(&message)?;
+    Ok(())
+  }
+
+  /// Sends the steps of every track of `clock` in real time, until the clock is stopped
+  /// or every track has finished.
+  /// The length of each event is relative to the step length of its track.
+  pub fn play(&self, clock: &mut Clock<NoteEvent>) -> io::Result<()> {
+    while let Some(steps) = clock.next() {
+      for (track, event) in steps {
+        // a track that just played a step is still running
+        let step = clock.step_duration(track).unwrap_or_default();
+        let seconds = step.as_secs_f64() * event.length;
+        self.send(&event, seconds)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_encode() {
+    let msg = encode_message("/note", &[OscArg::Int(7), OscArg::Float(0.5)]);
+    assert_eq!(
+      msg,
+      [
+        b'/', b'n', b'o', b't', b'e', 0, 0, 0, // address
+        b',', b'i', b'f', 0, // type tags
+        0, 0, 0, 7, // int
+        0x3f, 0, 0, 0, // float
+      ]
+    );
+  }
+
+  #[test]
+  fn test_send() {
+    let expected = encode_message(
+      "/note",
+      &[OscArg::Int(3), OscArg::Int(100), OscArg::Float(0.25)],
+    );
+    for local in ["127.0.0.1:0", "[::1]:0"] {
+      // not every machine has IPv6
+      let Ok(receiver) = UdpSocket::bind(local) else {
+        continue;
+      };
+      let sender = OscSender::connect(receiver.local_addr().unwrap()).unwrap();
+      sender.send(&NoteEvent::new(3, 100, 1.0), 0.25).unwrap();
+
+      let mut buf = [0; 64];
+      let len = receiver.recv(&mut buf).unwrap();
+      assert_eq!(&buf[..len], expected.as_slice());
+    }
+  }
+}
@@ -0,0 +1,125 @@
+use super::{
+  Seq,
+  event::{EventSequence, NoteEvent},
+};
+
+/// A single note or rest together with how long it plays, measured in steps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step {
+  pub note: Option<i8>,
+  pub length: f64,
+}
+
+impl Step {
+  pub fn new(note: impl Into<Option<i8>>, length: f64) -> Self {
+    Self {
+      note: note.into(),
+      length,
+    }
+  }
+}
+
+pub trait TimedSequence: Iterator<Item = Step> {
+  /// Shuffles the rhythm by lengthening every first step of a pair by `amount` of its length
+  /// and shortening the following one by `amount` of its own length.
+  /// A pair keeps its total length only if both steps are equally long.
+  /// An `amount` of `1/3` gives a triplet feel.
+  fn swing(self, amount: f64) -> impl TimedSequence
+  where
+    Self: Sized,
+  {
+    assert!((0.0..1.0).contains(&amount), "swing must be in 0..1");
+    self.enumerate().map(move |(index, step)| {
+      let factor = if index % 2 == 0 {
+        1.0 + amount
+      } else {
+        1.0 - amount
+      };
+      Step::new(step.note, step.length * factor)
+    })
+  }
+
+  /// Retriggers every note `n` times within the length of its step.
+  /// Rests are kept as a single step.
+  fn stutter(self, n: usize) -> impl TimedSequence
+  where
+    Self: Sized,
+  {
+    assert!(n != 0, "n cannot be 0");
+    self.flat_map(move |step| {
+      let (count, length) = if step.note.is_none() {
+        (1, step.length)
+      } else {
+        (n, step.length / n as f64)
+      };
+      std::iter::repeat_n(Step::new(step.note, length), count)
+    })
+  }
+
+  /// Pairs every step with a velocity taken from `velocity`, clamped to the MIDI range `0..=127`.
+  /// A rest in `velocity` plays the note with no velocity.
+  fn with_velocity(self, velocity: impl Into<Seq>) -> impl EventSequence
+  where
+    Self: Sized,
+  {
+    self.zip(velocity.into()).map(|(step, vel)| {
+      let vel = vel.map_or(0, |v| v.clamp(0, 127) as u8);
+      NoteEvent::new(step.note, vel, step.length)
+    })
+  }
+}
+
+impl<T: Iterator<Item = Step>> TimedSequence for T {}
+
+#[cfg(test)]
+mod tests {
+  use crate::seq::{Seq, Sequence};
+
+  use super::*;
+
+  #[test]
+  fn test_timed() {
+    let steps: Vec<_> = Seq::from([1, 2]).timed().take(3).collect();
+    assert_eq!(
+      steps,
+      [Step::new(1, 1.0), Step::new(2, 1.0), Step::new(1, 1.0)]
+    );
+  }
+
+  #[test]
+  fn test_swing() {
+    let steps: Vec<_> = Seq::from([1, 2, 3]).timed().swing(0.5).take(4).collect();
+    assert_eq!(
+      steps,
+      [
+        Step::new(1, 1.5),
+        Step::new(2, 0.5),
+        Step::new(3, 1.5),
+        Step::new(1, 0.5)
+      ]
+    );
+
+    // steps of different lengths are scaled separately
+    let steps: Vec<_> = [Step::new(1, 2.0), Step::new(2, 1.0)]
+      .into_iter()
+      .swing(0.5)
+      .collect();
+    assert_eq!(steps, [Step::new(1, 3.0), Step::new(2, 0.5)]);
+  }
+
+  #[test]
+  fn test_stutter() {
+    let seq = Seq::try_from("1 . 2").unwrap();
+    let steps: Vec<_> = seq.timed().stutter(2).take(5).collect();
+    assert_eq!(
+      steps,
+      [
+        Step::new(1, 0.5),
+        Step::new(1, 0.5),
+        Step::new(None, 1.0),
+        Step::new(2, 0.5),
+        Step::new(2, 0.5)
+      ]
+    );
+  }
+}
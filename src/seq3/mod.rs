@@ -1,60 +1,160 @@
-use std::ops::{Add, Mul};
+use std::{
+  iter::Peekable,
+  ops::{Add, Mul, Neg, Sub},
+  str::FromStr,
+};
 
-use crate::exts::numbers::UnsignedExt;
+use num_traits::Signed;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+  exts::numbers::UnsignedExt,
+  seq::{SeqValue, read_neg, read_note_as, read_num},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Sequence {
+pub enum Sequence<T = i8> {
   Silence,
-  Note(i8),
+  Note(T),
   Pattern {
-    pattern: Vec<Sequence>,
+    pattern: Vec<Sequence<T>>,
     index: usize,
   },
   Add {
-    left: Box<Sequence>,
-    right: Box<Sequence>,
+    left: Box<Sequence<T>>,
+    right: Box<Sequence<T>>,
+  },
+  Sub {
+    left: Box<Sequence<T>>,
+    right: Box<Sequence<T>>,
   },
+  Neg(Box<Sequence<T>>),
   Mul {
-    num: Box<Sequence>,
-    den: Box<Sequence>,
-    value: Option<i8>,
-    remaining: i8,
+    num: Box<Sequence<T>>,
+    den: Box<Sequence<T>>,
+    value: Option<T>,
+    remaining: T,
   },
   Random {
-    min: i8,
-    max: i8,
+    min: T,
+    max: T,
+    rng: Option<Box<StdRng>>,
+  },
+  Cat {
+    sections: Vec<(Sequence<T>, usize)>,
+    index: usize,
+    played: usize,
   },
 }
 
-impl Sequence {
-  pub fn sample(self, n: usize) -> Vec<i8> {
+impl<T: SeqValue> Sequence<T> {
+  pub fn rand(min: T, max: T) -> Self {
+    Self::Random {
+      min: min.min(max),
+      max: min.max(max),
+      rng: None,
+    }
+  }
+
+  /// Makes every random part of the sequence reproducible.
+  pub fn with_seed(mut self, seed: u64) -> Self {
+    self.seed(&mut StdRng::seed_from_u64(seed));
+    self
+  }
+
+  fn seed(&mut self, seeder: &mut StdRng) {
+    match self {
+      Sequence::Silence | Sequence::Note(_) => {}
+      Sequence::Random { rng, .. } => *rng = Some(Box::new(StdRng::from_rng(seeder))),
+      Sequence::Pattern { pattern, index: _ } => pattern.iter_mut().for_each(|p| p.seed(seeder)),
+      Sequence::Add { left, right } | Sequence::Sub { left, right } => {
+        left.seed(seeder);
+        right.seed(seeder);
+      }
+      Sequence::Neg(inner) => inner.seed(seeder),
+      Sequence::Mul { num, den, .. } => {
+        num.seed(seeder);
+        den.seed(seeder);
+      }
+      Sequence::Cat { sections, .. } => sections.iter_mut().for_each(|(s, _)| s.seed(seeder)),
+    }
+  }
+
+  /// Shifts every note of the sequence by `offset`.
+  pub fn transpose(self, offset: T) -> Self {
+    self + Sequence::Note(offset)
+  }
+
+  pub fn sample(self, n: usize) -> Vec<T> {
     self.take(n).flatten().collect()
   }
 
+  /// Samples exactly one period of the sequence, with rests as None like `Seq::cycle_vec`,
+  /// or None if the period is unknown.
+  pub fn sample_period(self) -> Option<Vec<Option<T>>> {
+    let period = self.period()?;
+    Some(self.take(period).collect())
+  }
+
   pub fn period(&self) -> Option<usize> {
     match self {
       Sequence::Silence => Some(1),
       Sequence::Note(_) => Some(1),
       Sequence::Pattern { pattern, index: _ } => {
-        Some(pattern.iter().filter_map(|p| p.period()).sum())
+        // each element only advances once per pass through the pattern
+        let passes = pattern
+          .iter()
+          .filter_map(|p| p.period())
+          .fold(1, |acc, p| acc.lcm(p));
+        Some(pattern.len() * passes)
       }
-      Sequence::Add { left, right } => left
-        .period()
-        .zip(right.period())
-        .map(|(l, r)| (l as u64).lcm(r as u64) as usize),
-      Sequence::Random { min: _, max: _ } => None,
+      Sequence::Add { left, right } | Sequence::Sub { left, right } => {
+        left.period().zip(right.period()).map(|(l, r)| l.lcm(r))
+      }
+      Sequence::Neg(inner) => inner.period(),
+      Sequence::Random { .. } => None,
       Sequence::Mul {
         num: _,
         den: _,
         value: _,
         remaining: _,
       } => None,
+      Sequence::Cat {
+        sections,
+        index: _,
+        played: _,
+      } => Some(sections.iter().map(|(_, steps)| steps).sum()),
+    }
+  }
+
+  /// Plays a full period of `self`, followed by a full period of `other`.
+  /// Panics if either sequence does not have a known period.
+  pub fn cat(self, other: impl Into<Sequence<T>>) -> Sequence<T> {
+    self.then(other, 1)
+  }
+
+  /// Plays `cycles` full periods of `self`, followed by a full period of `other`.
+  /// With 0 cycles, only `other` is played.
+  /// Panics if either sequence does not have a known period.
+  pub fn then(self, other: impl Into<Sequence<T>>, cycles: usize) -> Sequence<T> {
+    let other = other.into();
+    let first = self.period().expect("sequence has no period") * cycles;
+    let second = other.period().expect("sequence has no period");
+    let sections = if first == 0 {
+      vec![(other, second)]
+    } else {
+      vec![(self, first), (other, second)]
+    };
+    Sequence::Cat {
+      sections,
+      index: 0,
+      played: 0,
     }
   }
 }
 
-impl Iterator for Sequence {
-  type Item = Option<i8>;
+impl<T: SeqValue> Iterator for Sequence<T> {
+  type Item = Option<T>;
 
   fn next(&mut self) -> Option<Self::Item> {
     let res = match self {
@@ -69,33 +169,56 @@ impl Iterator for Sequence {
         .next()
         .flatten()
         .zip(right.next().flatten())
-        .map(|(l, r)| l + r),
-      Sequence::Random { min, max } => Some(rand::random_range(*min..=*max)),
+        .map(|(l, r)| l.wrapping_add(&r)),
+      Sequence::Sub { left, right } => left
+        .next()
+        .flatten()
+        .zip(right.next().flatten())
+        .map(|(l, r)| l.wrapping_sub(&r)),
+      Sequence::Neg(inner) => inner.next().flatten().map(|n| T::zero().wrapping_sub(&n)),
+      Sequence::Random { min, max, rng } => Some(match rng {
+        Some(rng) => rng.random_range(*min..=*max),
+        None => rand::random_range(*min..=*max),
+      }),
       Sequence::Mul {
         num,
         den,
         value,
         remaining,
       } => {
-        if *remaining == 0 {
-          *remaining = den.next().unwrap().unwrap_or(0);
+        if remaining.is_zero() {
+          *remaining = den.next().unwrap().unwrap_or(T::zero());
           *value = num.next().unwrap();
-          if *remaining == 0 {
+          if remaining.is_zero() {
             return None;
           }
         }
-        *remaining -= 1;
+        *remaining = *remaining - T::one();
         *value
       }
+      Sequence::Cat {
+        sections,
+        index,
+        played,
+      } => {
+        let (seq, steps) = sections.get_mut(*index)?;
+        let next = seq.next().flatten();
+        *played += 1;
+        if *played >= *steps {
+          *played = 0;
+          *index = (*index + 1) % sections.len();
+        }
+        next
+      }
     };
     Some(res)
   }
 }
 
-impl<T: Into<Sequence>> Add<T> for Sequence {
-  type Output = Sequence;
+impl<T: SeqValue, R: Into<Sequence<T>>> Add<R> for Sequence<T> {
+  type Output = Sequence<T>;
 
-  fn add(self, rhs: T) -> Self::Output {
+  fn add(self, rhs: R) -> Self::Output {
     Self::Add {
       left: self.into(),
       right: rhs.into().into(),
@@ -103,31 +226,167 @@ impl<T: Into<Sequence>> Add<T> for Sequence {
   }
 }
 
-impl<T: Into<Sequence>> Mul<T> for Sequence {
-  type Output = Sequence;
+impl<T: SeqValue, R: Into<Sequence<T>>> Sub<R> for Sequence<T> {
+  type Output = Sequence<T>;
+
+  fn sub(self, rhs: R) -> Self::Output {
+    Self::Sub {
+      left: self.into(),
+      right: rhs.into().into(),
+    }
+  }
+}
+
+impl<T: SeqValue + Signed> Neg for Sequence<T> {
+  type Output = Sequence<T>;
+
+  fn neg(self) -> Self::Output {
+    Self::Neg(self.into())
+  }
+}
+
+impl<T: SeqValue, R: Into<Sequence<T>>> Mul<R> for Sequence<T> {
+  type Output = Sequence<T>;
 
-  fn mul(self, rhs: T) -> Self::Output {
+  fn mul(self, rhs: R) -> Self::Output {
     Self::Mul {
       num: self.into(),
       den: rhs.into().into(),
       value: None,
-      remaining: 0,
+      remaining: T::zero(),
     }
   }
 }
 
-impl<R> From<R> for Sequence
-where
-  R: AsRef<[i8]>,
-{
-  fn from(value: R) -> Self {
+macro_rules! impl_from_value {
+  ($type:ty) => {
+    impl From<$type> for Sequence<$type> {
+      fn from(value: $type) -> Self {
+        Self::Note(value)
+      }
+    }
+  };
+}
+
+impl_from_value!(i8);
+impl_from_value!(u8);
+impl_from_value!(i16);
+impl_from_value!(u16);
+impl_from_value!(i32);
+impl_from_value!(u32);
+impl_from_value!(i64);
+impl_from_value!(u64);
+
+impl<T: SeqValue> From<&[T]> for Sequence<T> {
+  fn from(value: &[T]) -> Self {
     Self::Pattern {
-      pattern: value.as_ref().iter().copied().map(Sequence::Note).collect(),
+      pattern: value.iter().copied().map(Sequence::Note).collect(),
       index: 0,
     }
   }
 }
 
+impl<T: SeqValue> From<Vec<T>> for Sequence<T> {
+  fn from(value: Vec<T>) -> Self {
+    value.as_slice().into()
+  }
+}
+
+impl<T: SeqValue, const L: usize> From<[T; L]> for Sequence<T> {
+  fn from(value: [T; L]) -> Self {
+    value.as_slice().into()
+  }
+}
+
+impl TryFrom<&str> for Sequence {
+  type Error = anyhow::Error;
+
+  fn try_from(text: &str) -> Result<Self, Self::Error> {
+    text.parse()
+  }
+}
+
+impl<T: SeqValue> FromStr for Sequence<T> {
+  type Err = anyhow::Error;
+
+  /// Parses a pattern such as `"c4 <0 ~ 7> <1 2> * 2 + 12"`.
+  /// Space-separated steps form a pattern, `<...>` nests a pattern, `~` or `.` is a rest,
+  /// and `*` binds tighter than `+`.
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    fn skip_spaces<I: Iterator<Item = char>>(chars: &mut Peekable<I>) {
+      while chars.next_if_eq(&' ').is_some() {}
+    }
+
+    fn atom<T: SeqValue, I: Iterator<Item = char>>(
+      chars: &mut Peekable<I>,
+    ) -> anyhow::Result<Sequence<T>> {
+      let c = *chars.peek().ok_or(anyhow::anyhow!("unexpected end"))?;
+      Ok(match c {
+        '~' | '.' => {
+          chars.next();
+          Sequence::Silence
+        }
+        '<' => {
+          chars.next();
+          let seq = pattern(chars)?;
+          anyhow::ensure!(chars.next() == Some('>'), "expected >");
+          seq
+        }
+        '-' => {
+          chars.next();
+          Sequence::Note(read_neg(chars)?)
+        }
+        '0'..='9' => Sequence::Note(read_num(chars)?),
+        'a'..='g' | 'A'..='G' => Sequence::Note(read_note_as(chars)?),
+        tok => anyhow::bail!("unexpected token: {}", tok),
+      })
+    }
+
+    fn product<T: SeqValue, I: Iterator<Item = char>>(
+      chars: &mut Peekable<I>,
+    ) -> anyhow::Result<Sequence<T>> {
+      let mut res = atom(chars)?;
+      skip_spaces(chars);
+      while chars.next_if_eq(&'*').is_some() {
+        skip_spaces(chars);
+        res = res * atom(chars)?;
+        skip_spaces(chars);
+      }
+      Ok(res)
+    }
+
+    fn sum<T: SeqValue, I: Iterator<Item = char>>(
+      chars: &mut Peekable<I>,
+    ) -> anyhow::Result<Sequence<T>> {
+      let mut res = product(chars)?;
+      while chars.next_if_eq(&'+').is_some() {
+        skip_spaces(chars);
+        res = res + product(chars)?;
+      }
+      Ok(res)
+    }
+
+    fn pattern<T: SeqValue, I: Iterator<Item = char>>(
+      chars: &mut Peekable<I>,
+    ) -> anyhow::Result<Sequence<T>> {
+      let mut pattern = vec![];
+      skip_spaces(chars);
+      while chars.peek().is_some_and(|c| *c != '>') {
+        pattern.push(sum(chars)?);
+      }
+      anyhow::ensure!(!pattern.is_empty(), "empty pattern");
+      Ok(Sequence::Pattern { pattern, index: 0 })
+    }
+
+    let mut chars = text.chars().peekable();
+    let res = pattern(&mut chars)?;
+    if let Some(c) = chars.next() {
+      anyhow::bail!("unexpected token: {}", c)
+    }
+    Ok(res)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -150,4 +409,117 @@ mod tests {
     let b = a * [1, 2, 3];
     assert_eq!(b.sample(10), vec![1, 2, 2, 3, 3, 3, 1, 2, 2, 3])
   }
+
+  #[test]
+  fn test_parse() {
+    let a = Sequence::try_from("1 ~ 3 .").unwrap();
+    assert_eq!(
+      a.take(5).collect::<Vec<_>>(),
+      [Some(1), None, Some(3), None, Some(1)]
+    );
+
+    let a = Sequence::try_from("c4 <e4 g4>").unwrap();
+    assert_eq!(a.sample(4), vec![0, 4, 0, 7]);
+  }
+
+  #[test]
+  fn test_parse_operators() {
+    let a = Sequence::try_from("<1 2 3> + <2 -1 1 0>").unwrap();
+    assert_eq!(a.sample(10), vec![3, 1, 4, 1, 4, 2, 2, 2, 5, 0]);
+
+    let a = Sequence::try_from("<1 2 3>*<1 2 3>").unwrap();
+    assert_eq!(a.sample(10), vec![1, 2, 2, 3, 3, 3, 1, 2, 2, 3]);
+
+    let a = Sequence::try_from("<1 2> * 2 + 10").unwrap();
+    assert_eq!(a.sample(4), vec![11, 11, 12, 12]);
+  }
+
+  #[test]
+  fn test_parse_invalid() {
+    assert!(Sequence::try_from("").is_err());
+    assert!(Sequence::try_from("<1 2").is_err());
+    assert!(Sequence::try_from("1 2>").is_err());
+    assert!(Sequence::try_from("1 +").is_err());
+    assert!(Sequence::try_from("x").is_err());
+  }
+
+  #[test]
+  fn test_period() {
+    let a = Sequence::try_from("1 2 3").unwrap();
+    assert_eq!(a.period(), Some(3));
+
+    let a = Sequence::try_from("1 <2 3>").unwrap();
+    assert_eq!(a.period(), Some(4));
+  }
+
+  #[test]
+  fn test_cat() {
+    let a: Sequence = [1, 2, 3].into();
+    let b = a.cat([7, 8]);
+    assert_eq!(b.period(), Some(5));
+    assert_eq!(b.sample(7), vec![1, 2, 3, 7, 8, 1, 2]);
+
+    let a = Sequence::try_from("1 <2 3>").unwrap();
+    let b = a.then(0, 2);
+    assert_eq!(b.sample(10), vec![1, 2, 1, 3, 1, 2, 1, 3, 0, 1]);
+
+    let a: Sequence = [1, 2, 3].into();
+    let b = a.then([7, 8], 0);
+    assert_eq!(b.period(), Some(2));
+    assert_eq!(b.sample(3), vec![7, 8, 7]);
+  }
+
+  #[test]
+  fn test_seed() {
+    let make = || Sequence::from([1, 2, 3]) + Sequence::rand(0, 100);
+    let a = make().with_seed(4711).sample(20);
+    let b = make().with_seed(4711).sample(20);
+    assert_eq!(a, b);
+    let c = make().with_seed(1337).sample(20);
+    assert_ne!(a, c);
+  }
+
+  #[test]
+  fn test_sample_period() {
+    let a = Sequence::try_from("1 <2 3> ~").unwrap();
+    assert_eq!(
+      a.sample_period(),
+      Some(vec![Some(1), Some(2), None, Some(1), Some(3), None])
+    );
+    assert_eq!(Sequence::rand(0, 1).sample_period(), None);
+  }
+
+  #[test]
+  fn test_generic() {
+    let a: Sequence<u16> = "<1000 2000> + 24".parse().unwrap();
+    assert_eq!(a.sample(3), vec![1024, 2024, 1024]);
+
+    let a: Sequence<u8> = Sequence::from([10, 20]) * [1, 2];
+    assert_eq!(a.sample(4), vec![10, 20, 20, 10]);
+  }
+
+  #[test]
+  fn test_sub_neg() {
+    let a: Sequence = [1, 2, 3].into();
+    let b = a - [2, -1];
+    assert_eq!(b.sample(4), vec![-1, 3, 1, 2]);
+
+    let a = -Sequence::try_from("0 4 ~ 7").unwrap();
+    assert_eq!(a.period(), Some(4));
+    assert_eq!(a.sample(4), vec![0, -4, -7]);
+
+    // like `Seq`, the arithmetic wraps instead of overflowing
+    let a: Sequence<u8> = Sequence::from([10u8]) - [20u8];
+    assert_eq!(a.sample(1), vec![246]);
+    let a: Sequence<u8> = Sequence::from([250u8]) + [10u8];
+    assert_eq!(a.sample(1), vec![4]);
+    let a: Sequence<i8> = -Sequence::from([i8::MIN]);
+    assert_eq!(a.sample(1), vec![i8::MIN]);
+  }
+
+  #[test]
+  fn test_transpose() {
+    let a = Sequence::try_from("c4 e4 g4").unwrap().transpose(12);
+    assert_eq!(a.sample(3), vec![12, 16, 19]);
+  }
 }
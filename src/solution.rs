@@ -0,0 +1,380 @@
+use std::{
+  fmt::{Display, Write},
+  time::Duration,
+};
+
+use yansi::Paint;
+
+use crate::{exts::duration::DurationExt, input, time, timed};
+
+/// An example input from the puzzle text, with the answers it should give.
+/// Parts without an answer, e.g. because the example uses other parameters, are not checked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sample {
+  pub input: &'static str,
+  pub part1: Option<&'static str>,
+  pub part2: Option<&'static str>,
+}
+
+/// The puzzles of one day. The input is parsed once and shared by both parts.
+/// Parse errors are best returned as `AocError::Parse`, so that they name the line at fault.
+pub trait Solution {
+  const DAY: u32;
+  const TITLE: &'static str;
+
+  type Parsed;
+
+  /// Run by `check_samples`.
+  const SAMPLES: &'static [Sample] = &[];
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed>;
+
+  fn part1(parsed: &Self::Parsed) -> impl Display;
+
+  fn part2(parsed: &Self::Parsed) -> impl Display;
+}
+
+/// Loads the day's input and prints both answers, followed by the timing report.
+pub fn run_day<S: Solution>() -> anyhow::Result<()> {
+  time::color_only_in_terminal();
+  solve::<S>(&input::load(S::DAY)?, None)?;
+  time::report();
+  Ok(())
+}
+
+/// Parses `input` and prints the answer to `part`, or to both parts if None.
+pub fn solve<S: Solution>(input: &str, part: Option<u8>) -> anyhow::Result<()> {
+  print!("{}", solve_to_string::<S>(input, part)?);
+  Ok(())
+}
+
+/// Like `solve`, but returns the output instead of printing it, so that days can run concurrently.
+pub fn solve_to_string<S: Solution>(input: &str, part: Option<u8>) -> anyhow::Result<String> {
+  anyhow::ensure!(
+    part.is_none_or(|p| p == 1 || p == 2),
+    "there is no part {}",
+    part.unwrap_or_default()
+  );
+  let mut out = String::new();
+  writeln!(out, "AoC Day {:02}: {}", S::DAY, S::TITLE)?;
+  let day = S::DAY;
+  let parsed = timed!(format!("Day {day:02} parse"), S::parse(input))
+    .map_err(|e| e.context(format!("cannot parse the input of day {day:02}")))?;
+  if part.is_none_or(|p| p == 1) {
+    let answer = timed!(format!("Day {day:02} part 1"), S::part1(&parsed));
+    writeln!(out, "Part 1: {answer}")?;
+  }
+  if part.is_none_or(|p| p == 2) {
+    let answer = timed!(format!("Day {day:02} part 2"), S::part2(&parsed));
+    writeln!(out, "Part 2: {answer}")?;
+  }
+  Ok(out)
+}
+
+/// Parses `input` and returns the answer to `part` without printing it.
+pub fn answer<S: Solution>(input: &str, part: u8) -> anyhow::Result<String> {
+  let parsed = S::parse(input)?;
+  match part {
+    1 => Ok(S::part1(&parsed).to_string()),
+    2 => Ok(S::part2(&parsed).to_string()),
+    _ => anyhow::bail!("there is no part {part}"),
+  }
+}
+
+/// Runs every sample, and returns a line per checked part and how many of them failed.
+pub fn check_samples<S: Solution>() -> anyhow::Result<(String, usize)> {
+  let mut out = String::new();
+  let mut failed = 0;
+  writeln!(out, "AoC Day {:02}: {}", S::DAY, S::TITLE)?;
+  if S::SAMPLES.is_empty() {
+    writeln!(out, "No samples")?;
+  }
+  for (i, sample) in S::SAMPLES.iter().enumerate() {
+    let parsed = match S::parse(sample.input) {
+      Ok(parsed) => parsed,
+      Err(e) => {
+        writeln!(out, "Sample {}: {} {e}", i + 1, "cannot parse:".red())?;
+        failed += 1;
+        continue;
+      }
+    };
+    for (part, expected) in [(1, sample.part1), (2, sample.part2)] {
+      let Some(expected) = expected else {
+        continue;
+      };
+      let answer = match part {
+        1 => S::part1(&parsed).to_string(),
+        _ => S::part2(&parsed).to_string(),
+      };
+      if answer == expected {
+        writeln!(
+          out,
+          "Sample {} part {part}: {answer} {}",
+          i + 1,
+          "ok".green()
+        )?;
+      } else {
+        failed += 1;
+        let status = format!("expected {expected}").red().to_string();
+        writeln!(out, "Sample {} part {part}: {answer} {status}", i + 1)?;
+      }
+    }
+  }
+  Ok((out, failed))
+}
+
+/// The mean timings of the steps of a day, see `bench`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DayBench {
+  pub parse: Duration,
+  pub part1: Duration,
+  pub part2: Duration,
+}
+
+impl DayBench {
+  pub fn total(&self) -> Duration {
+    self.parse + self.part1 + self.part2
+  }
+}
+
+/// Times parsing and both parts separately, each over `iterations` runs.
+pub fn bench<S: Solution>(input: &str, iterations: usize) -> anyhow::Result<DayBench> {
+  let parsed = S::parse(input)?;
+  Ok(DayBench {
+    parse: time::bench_quiet("parse", iterations, || S::parse(input)).mean,
+    part1: time::bench_quiet("part 1", iterations, || S::part1(&parsed).to_string()).mean,
+    part2: time::bench_quiet("part 2", iterations, || S::part2(&parsed).to_string()).mean,
+  })
+}
+
+/// Benchmarks of several days. Displays as a table with a row per day, followed by the totals.
+pub struct BenchTable(pub Vec<(u32, &'static str, DayBench)>);
+
+impl BenchTable {
+  pub fn total(&self) -> DayBench {
+    let sum = |step: fn(&DayBench) -> Duration| self.0.iter().map(|(_, _, b)| step(b)).sum();
+    DayBench {
+      parse: sum(|b| b.parse),
+      part1: sum(|b| b.part1),
+      part2: sum(|b| b.part2),
+    }
+  }
+}
+
+impl Display for BenchTable {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let width = self
+      .0
+      .iter()
+      .map(|(_, title, _)| title.len())
+      .max()
+      .unwrap_or(0)
+      .max(5);
+    writeln!(
+      f,
+      "day  {:<width$}  {:>8}  {:>8}  {:>8}  {:>8}",
+      "title", "parse", "part 1", "part 2", "total"
+    )?;
+    for (day, title, bench) in &self.0 {
+      writeln!(
+        f,
+        "{day:>3}  {title:<width$}  {}  {}  {}  {}",
+        bench.parse.display_precise(2),
+        bench.part1.display_precise(2),
+        bench.part2.display_precise(2),
+        time::highlight_slow(bench.total())
+      )?;
+    }
+    let total = self.total();
+    writeln!(
+      f,
+      "     {:<width$}  {}  {}  {}  {}",
+      "total",
+      total.parse.display_precise(2),
+      total.part1.display_precise(2),
+      total.part2.display_precise(2),
+      time::highlight_slow(total.total())
+    )
+  }
+}
+
+/// A solution registered with `days!`, with its input.
+/// The input is loaded when it is needed, so a missing input only fails its own day.
+pub struct Day {
+  pub day: u32,
+  pub title: &'static str,
+  /// Runs `solve_to_string` on the day's input.
+  pub solve: fn(Option<u8>) -> anyhow::Result<String>,
+  /// Runs `answer` on the day's input.
+  pub answer: fn(u8) -> anyhow::Result<String>,
+  /// Runs `bench` on the day's input.
+  pub bench: fn(usize) -> anyhow::Result<DayBench>,
+  /// Runs `check_samples`.
+  pub samples: fn() -> anyhow::Result<(String, usize)>,
+}
+
+/// Lists solutions as an array of `Day`, e.g. `days![Day07, Day08]`.
+/// Inputs are loaded with `input::load`, unless given as in `days![Day07 => SAMPLE]`.
+#[macro_export]
+macro_rules! days {
+  (@input $solution:ty) => {
+    &$crate::input::load(<$solution as $crate::solution::Solution>::DAY)?
+  };
+  (@input $solution:ty, $input:expr) => {
+    $input
+  };
+  ($($solution:ty $(=> $input:expr)?),* $(,)?) => {
+    [$($crate::solution::Day {
+      day: <$solution as $crate::solution::Solution>::DAY,
+      title: <$solution as $crate::solution::Solution>::TITLE,
+      solve: |part| {
+        $crate::solution::solve_to_string::<$solution>($crate::days!(@input $solution $(, $input)?), part)
+      },
+      answer: |part| {
+        $crate::solution::answer::<$solution>($crate::days!(@input $solution $(, $input)?), part)
+      },
+      bench: |iterations| {
+        $crate::solution::bench::<$solution>($crate::days!(@input $solution $(, $input)?), iterations)
+      },
+      samples: $crate::solution::check_samples::<$solution>,
+    }),*]
+  };
+}
+
+/// Expands to a test asserting the answer to a part for a sample input,
+/// e.g. `aoc_test!(Day07, part1, SAMPLE_INPUT, 21)`.
+/// The test is named after the part, unless a name is given as in `aoc_test!(small: Day07, ...)`.
+#[macro_export]
+macro_rules! aoc_test {
+  (@test $name:ident, $solution:ty, $part:literal, $input:expr, $expected:expr) => {
+    #[test]
+    fn $name() {
+      let answer = $crate::solution::answer::<$solution>($input, $part).unwrap();
+      assert_eq!(answer, $expected.to_string());
+    }
+  };
+  ($name:ident: $solution:ty, part1, $input:expr, $expected:expr $(,)?) => {
+    $crate::aoc_test!(@test $name, $solution, 1, $input, $expected);
+  };
+  ($name:ident: $solution:ty, part2, $input:expr, $expected:expr $(,)?) => {
+    $crate::aoc_test!(@test $name, $solution, 2, $input, $expected);
+  };
+  ($solution:ty, $part:ident, $input:expr, $expected:expr $(,)?) => {
+    $crate::aoc_test!($part: $solution, $part, $input, $expected);
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use core::assert_matches;
+
+  use super::*;
+  use crate::error::AocError;
+
+  struct Sums;
+
+  impl Solution for Sums {
+    const DAY: u32 = 0;
+    const TITLE: &'static str = "Sums";
+
+    type Parsed = Vec<u32>;
+
+    const SAMPLES: &'static [Sample] = &[
+      Sample {
+        input: "2,3,4",
+        part1: Some("9"),
+        part2: Some("24"),
+      },
+      Sample {
+        input: "5",
+        part1: None,
+        part2: Some("6"),
+      },
+      Sample {
+        input: "x",
+        part1: Some("0"),
+        part2: None,
+      },
+    ];
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+      Ok(crate::parse::comma_separated(input)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> impl Display {
+      parsed.iter().sum::<u32>()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> impl Display {
+      parsed.iter().product::<u32>()
+    }
+  }
+
+  #[test]
+  fn test_run_day() {
+    assert_eq!(Sums::part1(&Sums::parse("2,3,4").unwrap()).to_string(), "9");
+    assert!(solve::<Sums>("2,3,4", None).is_ok());
+
+    let error = solve::<Sums>("2,\nx", None).unwrap_err();
+    assert_eq!(error.to_string(), "cannot parse the input of day 00");
+    assert_matches!(
+      error.downcast_ref::<AocError>(),
+      Some(AocError::Parse { line: 2, .. })
+    );
+  }
+
+  #[test]
+  fn test_check_samples() {
+    let (out, failed) = check_samples::<Sums>().unwrap();
+    assert_eq!(failed, 2);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[1], format!("Sample 1 part 1: 9 {}", "ok".green()));
+    assert_eq!(
+      lines[3],
+      format!("Sample 2 part 2: 5 {}", "expected 6".red())
+    );
+    assert!(lines[4].starts_with("Sample 3: "));
+  }
+
+  #[test]
+  fn test_days() {
+    let days = crate::days![Sums => "1,2"];
+    assert_eq!((days[0].day, days[0].title), (0, "Sums"));
+    assert_eq!(
+      (days[0].solve)(Some(2)).unwrap(),
+      "AoC Day 00: Sums\nPart 2: 2\n"
+    );
+    assert!((days[0].solve)(Some(3)).is_err());
+    assert_eq!((days[0].answer)(1).unwrap(), "3");
+  }
+
+  #[test]
+  fn test_bench() {
+    let timings = bench::<Sums>("2,3,4", 3).unwrap();
+    assert_eq!(
+      timings.total(),
+      timings.parse + timings.part1 + timings.part2
+    );
+    assert!(bench::<Sums>("x", 3).is_err());
+
+    let ms = Duration::from_millis;
+    let row = |n| DayBench {
+      parse: ms(n),
+      part1: ms(2 * n),
+      part2: ms(3 * n),
+    };
+    let table = BenchTable(vec![(1, "One", row(1)), (2, "Two", row(10))]);
+    assert_eq!(table.total(), row(11));
+    let text = table.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("day  title"));
+    assert!(lines[1].starts_with("  1  One  "));
+    assert!(lines[3].contains("11.00ms"));
+  }
+
+  crate::aoc_test!(Sums, part1, "2,3,4", 9);
+  crate::aoc_test!(Sums, part2, "2,3,4", "24");
+  crate::aoc_test!(single: Sums, part2, "5", 5);
+}
@@ -0,0 +1,94 @@
+use std::{
+  marker::PhantomData,
+  ops::{Bound, RangeBounds},
+};
+
+use crate::segment_tree::Idempotent;
+
+/// Combines any range of a fixed list of values in O(1), after O(n log n) setup.
+///
+/// Ranges are covered by two overlapping blocks, so `O` must be idempotent, like `Min` and `Max`.
+pub struct SparseTable<T, O: Idempotent<T>> {
+  /// `levels[k][i]` combines the `2^k` values starting at `i`.
+  levels: Vec<Vec<T>>,
+  _op: PhantomData<O>,
+}
+
+impl<T: Clone, O: Idempotent<T>> SparseTable<T, O> {
+  pub fn new(values: &[T]) -> Self {
+    let mut levels = vec![values.to_vec()];
+    let mut width = 1;
+    while 2 * width <= values.len() {
+      let prev = levels.last().unwrap();
+      let level = (0..prev.len() - width)
+        .map(|i| O::combine(&prev[i], &prev[i + width]))
+        .collect();
+      levels.push(level);
+      width *= 2;
+    }
+    Self {
+      levels,
+      _op: PhantomData,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.levels[0].len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// The values in `range` combined, or the identity if it is empty.
+  /// Panics if the range goes past the end.
+  pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+    let start = match range.start_bound() {
+      Bound::Included(&s) => s,
+      Bound::Excluded(&s) => s + 1,
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(&e) => e + 1,
+      Bound::Excluded(&e) => e,
+      Bound::Unbounded => self.len(),
+    };
+    assert!(end <= self.len(), "range out of bounds: {start}..{end}");
+    if start >= end {
+      return O::identity();
+    }
+    let k = (end - start).ilog2() as usize;
+    let level = &self.levels[k];
+    O::combine(&level[start], &level[end - (1 << k)])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::segment_tree::{Max, Min};
+
+  #[test]
+  fn test_matches_brute_force() {
+    let values: Vec<i32> = (0..45).map(|i| (i * 7919) % 101 - 50).collect();
+    let mins: SparseTable<i32, Min> = SparseTable::new(&values);
+    let maxes: SparseTable<i32, Max> = SparseTable::new(&values);
+    for start in 0..values.len() {
+      for end in start + 1..=values.len() {
+        let window = &values[start..end];
+        assert_eq!(mins.query(start..end), *window.iter().min().unwrap());
+        assert_eq!(maxes.query(start..end), *window.iter().max().unwrap());
+      }
+    }
+    assert_eq!(mins.query(..), -50);
+    assert_eq!(maxes.query(3..=3), values[3]);
+    assert_eq!(maxes.query(4..4), i32::MIN);
+  }
+
+  #[test]
+  fn test_empty() {
+    let table: SparseTable<u8, Min> = SparseTable::new(&[]);
+    assert!(table.is_empty());
+    assert_eq!(table.query(..), u8::MAX);
+  }
+}
@@ -0,0 +1,191 @@
+use std::collections::BinaryHeap;
+
+use crate::vex::Vex;
+
+/// The squared distance between two points.
+pub fn distance2<const D: usize>(a: &Vex<i64, D>, b: &Vex<i64, D>) -> u64 {
+  (*a - *b).length2()
+}
+
+/// A k-d tree over points with integer coordinates, for nearest neighbor queries.
+/// Points are identified by their index in the list the tree was built from,
+/// and distances are squared, so they stay exact.
+#[derive(Clone, Debug)]
+pub struct KdTree<const D: usize> {
+  /// Each subtree is a slice with its root in the middle, and the points before and after
+  /// it on either side of its splitting axis.
+  nodes: Vec<(Vex<i64, D>, usize)>,
+}
+
+impl<const D: usize> KdTree<D> {
+  /// Builds a balanced tree from all the points at once.
+  pub fn new(points: impl IntoIterator<Item = Vex<i64, D>>) -> Self {
+    let mut nodes: Vec<_> = points
+      .into_iter()
+      .enumerate()
+      .map(|(i, p)| (p, i))
+      .collect();
+    Self::build(&mut nodes, 0);
+    Self { nodes }
+  }
+
+  fn build(nodes: &mut [(Vex<i64, D>, usize)], axis: usize) {
+    if nodes.len() <= 1 {
+      return;
+    }
+    let mid = nodes.len() / 2;
+    nodes.select_nth_unstable_by_key(mid, |(point, _)| point.0[axis]);
+    let (left, right) = nodes.split_at_mut(mid);
+    Self::build(left, (axis + 1) % D);
+    Self::build(&mut right[1..], (axis + 1) % D);
+  }
+
+  pub fn len(&self) -> usize {
+    self.nodes.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// The `k` points closest to `target`, closest first, as their index and squared distance.
+  /// If `target` is one of the points, it is included.
+  pub fn nearest(&self, target: &Vex<i64, D>, k: usize) -> Vec<(usize, u64)> {
+    let mut best = BinaryHeap::new();
+    if k > 0 {
+      Self::search_nearest(&self.nodes, 0, target, k, &mut best);
+    }
+    let mut best: Vec<_> = best.into_iter().map(|(d, i)| (i, d)).collect();
+    best.sort_unstable_by_key(|&(i, d)| (d, i));
+    best
+  }
+
+  fn search_nearest(
+    nodes: &[(Vex<i64, D>, usize)],
+    axis: usize,
+    target: &Vex<i64, D>,
+    k: usize,
+    best: &mut BinaryHeap<(u64, usize)>,
+  ) {
+    if nodes.is_empty() {
+      return;
+    }
+    let mid = nodes.len() / 2;
+    let (point, index) = &nodes[mid];
+    best.push((distance2(point, target), *index));
+    if best.len() > k {
+      best.pop();
+    }
+    let diff = target.0[axis] - point.0[axis];
+    let (near, far) = if diff < 0 {
+      (&nodes[..mid], &nodes[mid + 1..])
+    } else {
+      (&nodes[mid + 1..], &nodes[..mid])
+    };
+    let next = (axis + 1) % D;
+    Self::search_nearest(near, next, target, k, best);
+    // the far side can only help if the splitting plane is closer than the worst found
+    let plane = diff.unsigned_abs().pow(2);
+    if best.len() < k || best.peek().is_some_and(|(worst, _)| plane < *worst) {
+      Self::search_nearest(far, next, target, k, best);
+    }
+  }
+
+  /// The points at most `radius` from `target`, closest first, as their index and squared
+  /// distance. If `target` is one of the points, it is included.
+  pub fn within_radius(&self, target: &Vex<i64, D>, radius: u64) -> Vec<(usize, u64)> {
+    let mut found = vec![];
+    Self::search_radius(
+      &self.nodes,
+      0,
+      target,
+      radius.saturating_mul(radius),
+      &mut found,
+    );
+    found.sort_unstable_by_key(|&(i, d)| (d, i));
+    found
+  }
+
+  fn search_radius(
+    nodes: &[(Vex<i64, D>, usize)],
+    axis: usize,
+    target: &Vex<i64, D>,
+    radius2: u64,
+    found: &mut Vec<(usize, u64)>,
+  ) {
+    if nodes.is_empty() {
+      return;
+    }
+    let mid = nodes.len() / 2;
+    let (point, index) = &nodes[mid];
+    let distance = distance2(point, target);
+    if distance <= radius2 {
+      found.push((*index, distance));
+    }
+    let diff = target.0[axis] - point.0[axis];
+    let next = (axis + 1) % D;
+    let plane = diff.unsigned_abs().pow(2);
+    if diff < 0 || plane <= radius2 {
+      Self::search_radius(&nodes[..mid], next, target, radius2, found);
+    }
+    if diff >= 0 || plane <= radius2 {
+      Self::search_radius(&nodes[mid + 1..], next, target, radius2, found);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rand::{Rng, SeedableRng, rngs::StdRng};
+
+  use super::*;
+
+  fn random_points(rng: &mut StdRng, n: usize) -> Vec<Vex<i64, 3>> {
+    (0..n)
+      .map(|_| Vex(std::array::from_fn(|_| rng.random_range(-100..100))))
+      .collect()
+  }
+
+  /// The same queries by checking every point.
+  fn brute_force(points: &[Vex<i64, 3>], target: &Vex<i64, 3>) -> Vec<(usize, u64)> {
+    let mut all: Vec<_> = points
+      .iter()
+      .enumerate()
+      .map(|(i, p)| (i, distance2(p, target)))
+      .collect();
+    all.sort_unstable_by_key(|&(i, d)| (d, i));
+    all
+  }
+
+  #[test]
+  fn test_nearest() {
+    let points = [Vex([0, 0]), Vex([5, 5]), Vex([1, 0]), Vex([-3, 4])];
+    let tree = KdTree::new(points);
+    assert_eq!(tree.len(), 4);
+    assert_eq!(tree.nearest(&Vex([0, 0]), 2), [(0, 0), (2, 1)]);
+    assert_eq!(tree.nearest(&Vex([4, 4]), 1), [(1, 2)]);
+    assert_eq!(tree.nearest(&Vex([0, 0]), 10).len(), 4);
+    assert!(tree.nearest(&Vex([0, 0]), 0).is_empty());
+    assert_eq!(
+      tree.within_radius(&Vex([0, 0]), 5),
+      [(0, 0), (2, 1), (3, 25)]
+    );
+  }
+
+  #[test]
+  fn test_matches_brute_force() {
+    let mut rng = StdRng::seed_from_u64(8);
+    let points = random_points(&mut rng, 300);
+    let tree = KdTree::new(points.iter().copied());
+    for target in random_points(&mut rng, 20) {
+      let all = brute_force(&points, &target);
+      let nearest = tree.nearest(&target, 7);
+      // ties may pick different points, but never at a different distance
+      let distances = |found: &[(usize, u64)]| found.iter().map(|(_, d)| *d).collect::<Vec<_>>();
+      assert_eq!(distances(&nearest), distances(&all[..7]));
+
+      let within: Vec<_> = all.iter().copied().filter(|(_, d)| *d <= 30 * 30).collect();
+      assert_eq!(tree.within_radius(&target, 30), within);
+    }
+  }
+}
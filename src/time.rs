@@ -1,12 +1,365 @@
-use std::time::{Duration, Instant};
+use std::{
+  cell::RefCell,
+  fmt::Display,
+  fs::File,
+  hint::black_box,
+  io::{BufWriter, IsTerminal, Write},
+  marker::PhantomData,
+  panic::Location,
+  path::Path,
+  sync::Mutex,
+  time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-pub fn time<T, V>(func: T) -> (V, Duration)
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use yansi::Paint;
+
+use crate::exts::duration::DurationExt;
+
+/// A recorded timing, with the peak bytes allocated if the `memory` feature is enabled.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ReportEntry {
+  pub label: String,
+  pub duration: Duration,
+  pub peak_bytes: Option<usize>,
+}
+
+struct Registry {
+  /// Whether `time` and `time_try` record their timings.
+  enabled: bool,
+  entries: Vec<ReportEntry>,
+}
+
+/// Timings recorded by `timed!`, and by `time` and `time_try` if enabled.
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+  enabled: false,
+  entries: vec![],
+});
+
+/// Makes `time` and `time_try` record their timings for `report`, labeled by where they were called.
+pub fn enable_registry() {
+  REGISTRY.lock().unwrap().enabled = true;
+}
+
+/// Records a timing, if the registry is enabled.
+pub fn record(label: impl Into<String>, duration: Duration) {
+  record_with_peak(label, duration, None);
+}
+
+/// Records a timing and the peak bytes allocated while it ran, if the registry is enabled.
+pub fn record_with_peak(label: impl Into<String>, duration: Duration, peak: Option<usize>) {
+  let mut registry = REGISTRY.lock().unwrap();
+  if registry.enabled {
+    push_entry(&mut registry, label.into(), duration, peak);
+  }
+}
+
+fn push_entry(registry: &mut Registry, label: String, duration: Duration, peak: Option<usize>) {
+  registry.entries.push(ReportEntry {
+    label,
+    duration,
+    peak_bytes: peak,
+  });
+}
+
+#[track_caller]
+fn record_caller(duration: Duration, peak: Option<usize>) {
+  let caller = Location::caller();
+  record_with_peak(
+    format!("{}:{}", caller.file(), caller.line()),
+    duration,
+    peak,
+  );
+}
+
+/// Starts measuring the peak bytes allocated by the current thread, which is only known with the
+/// `memory` feature.
+/// Call the returned function to stop.
+pub fn track_peak() -> impl FnOnce() -> Option<usize> {
+  #[cfg(feature = "memory")]
+  {
+    let scope = crate::alloc::PeakScope::start();
+    move || Some(scope.finish())
+  }
+  #[cfg(not(feature = "memory"))]
+  {
+    || None
+  }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5MiB`.
+pub fn display_bytes(bytes: usize) -> String {
+  const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+  let mut value = bytes as f64;
+  let mut unit = 0;
+  while value >= 1024.0 && unit < UNITS.len() - 1 {
+    value /= 1024.0;
+    unit += 1;
+  }
+  if unit == 0 {
+    format!("{bytes}B")
+  } else {
+    format!("{value:.1}{}", UNITS[unit])
+  }
+}
+
+/// Durations from which reported timings are shown in yellow and red.
+static SLOW_THRESHOLDS: Mutex<(Duration, Duration)> =
+  Mutex::new((Duration::from_millis(100), Duration::from_secs(1)));
+
+/// Sets when reported timings are highlighted, by default from 100ms in yellow and from 1s in red.
+pub fn set_slow_thresholds(warn: Duration, slow: Duration) {
+  *SLOW_THRESHOLDS.lock().unwrap() = (warn, slow);
+}
+
+/// Turns off colored output, such as the highlighting of slow timings, unless stdout is a terminal,
+/// so that piped output has no escape codes.
+pub fn color_only_in_terminal() {
+  if !std::io::stdout().is_terminal() {
+    yansi::disable();
+  }
+}
+
+/// The duration in a fixed width, colored if it is over a threshold and color is enabled.
+pub(crate) fn highlight_slow(duration: Duration) -> String {
+  let (warn, slow) = *SLOW_THRESHOLDS.lock().unwrap();
+  let text = duration.display_precise(2).to_string();
+  if duration >= slow {
+    text.red().to_string()
+  } else if duration >= warn {
+    text.yellow().to_string()
+  } else {
+    text
+  }
+}
+
+/// A snapshot of the recorded timings. Displays as a table, slowest first, followed by the total.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Report {
+  /// When the report was collected, in seconds since the Unix epoch.
+  pub timestamp: u64,
+  pub entries: Vec<ReportEntry>,
+}
+
+impl Report {
+  pub fn collect() -> Self {
+    let mut entries = REGISTRY.lock().unwrap().entries.clone();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.duration));
+    let timestamp = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |since| since.as_secs());
+    Self { timestamp, entries }
+  }
+
+  pub fn total(&self) -> Duration {
+    self.entries.iter().map(|entry| entry.duration).sum()
+  }
+
+  /// Writes one line per entry, with the duration in nanoseconds.
+  pub fn write_csv(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "timestamp,label,duration_ns,peak_bytes")?;
+    for entry in &self.entries {
+      let label = entry.label.replace('"', "\"\"");
+      let peak = entry.peak_bytes.map(|p| p.to_string()).unwrap_or_default();
+      writeln!(
+        out,
+        "{},\"{label}\",{},{peak}",
+        self.timestamp,
+        entry.duration.as_nanos()
+      )?;
+    }
+    out.flush()?;
+    Ok(())
+  }
+
+  /// Reads a report written by `write_csv`.
+  pub fn read_csv(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+    let csv = std::fs::read_to_string(path)?;
+    let mut timestamp = 0;
+    let mut entries = vec![];
+    for (i, line) in csv.lines().enumerate().skip(1) {
+      let bad_line = || anyhow::anyhow!("line {}: not a timing: {line}", i + 1);
+      let (time, rest) = line.split_once(",\"").ok_or_else(bad_line)?;
+      let (label, rest) = unquote(rest).ok_or_else(bad_line)?;
+      let (nanos, peak) = rest
+        .strip_prefix(',')
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(bad_line)?;
+      timestamp = time.parse()?;
+      entries.push(ReportEntry {
+        label,
+        duration: Duration::from_nanos(nanos.parse()?),
+        peak_bytes: if peak.is_empty() {
+          None
+        } else {
+          Some(peak.parse()?)
+        },
+      });
+    }
+    Ok(Self { timestamp, entries })
+  }
+
+  /// The change of every timing that is also in `previous`, matched by label.
+  pub fn compare(&self, previous: &Report) -> Vec<Delta> {
+    self
+      .entries
+      .iter()
+      .filter_map(|entry| {
+        let before = previous.entries.iter().find(|e| e.label == entry.label)?;
+        Some(Delta {
+          label: entry.label.clone(),
+          before: before.duration,
+          after: entry.duration,
+        })
+      })
+      .collect()
+  }
+
+  #[cfg(feature = "serde")]
+  pub fn write_json(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut out, self)?;
+    out.flush()?;
+    Ok(())
+  }
+}
+
+impl Display for Report {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let width = self
+      .entries
+      .iter()
+      .map(|entry| entry.label.len())
+      .max()
+      .unwrap_or(0)
+      .max(5);
+    for entry in &self.entries {
+      write!(
+        f,
+        "{:<width$}  {}",
+        entry.label,
+        highlight_slow(entry.duration)
+      )?;
+      if let Some(peak) = entry.peak_bytes {
+        write!(f, "  {:>9}", display_bytes(peak))?;
+      }
+      writeln!(f)?;
+    }
+    writeln!(
+      f,
+      "{:<width$}  {}",
+      "total",
+      self.total().display_precise(2)
+    )
+  }
+}
+
+/// Splits a CSV field that follows an opening quote from the rest of the line,
+/// undoing the doubling of quotes within it.
+fn unquote(field: &str) -> Option<(String, &str)> {
+  let mut value = String::new();
+  let mut chars = field.char_indices().peekable();
+  while let Some((i, c)) = chars.next() {
+    if c != '"' {
+      value.push(c);
+    } else if chars.next_if(|&(_, c)| c == '"').is_some() {
+      value.push('"');
+    } else {
+      return Some((value, &field[i + 1..]));
+    }
+  }
+  None
+}
+
+/// How a timing changed between two reports. Displays as e.g. `part 2: 48.00ms → 13.00ms, -73%`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+  pub label: String,
+  pub before: Duration,
+  pub after: Duration,
+}
+
+impl Delta {
+  /// The change relative to before, e.g. -73.0 for a run that takes about a quarter of the time,
+  /// or None if before took no time at all.
+  pub fn percent(&self) -> Option<f64> {
+    if self.before.is_zero() {
+      return None;
+    }
+    Some((self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0) * 100.0)
+  }
+}
+
+impl Display for Delta {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let percent = match self.percent() {
+      Some(percent) if self.after > self.before => format!("{percent:+.0}%").red().to_string(),
+      Some(percent) => format!("{percent:+.0}%").green().to_string(),
+      None => "n/a".to_string(),
+    };
+    write!(
+      f,
+      "{}: {} → {}, {percent}",
+      self.label,
+      self.before.display_precise(2).to_string().trim_start(),
+      self.after.display_precise(2).to_string().trim_start()
+    )
+  }
+}
+
+/// Prints the recorded timings, colored only if stdout is a terminal.
+pub fn report() {
+  color_only_in_terminal();
+  print!("{}", Report::collect());
+}
+
+/// Evaluates an expression with `time_labeled` and returns its value, recording how long it took
+/// under `label`, or under the expression's own text if no label is given. See `report`.
+///
+/// The expression runs in a closure, so `?` and `return` within it do not leave the caller.
+#[macro_export]
+macro_rules! timed {
+  ($expr:expr) => {
+    $crate::timed!(stringify!($expr), $expr)
+  };
+  ($label:expr, $expr:expr) => {
+    $crate::time::time_labeled($label, || $expr).value
+  };
+}
+
+/// Runs `func`, returning how long it took and the peak bytes allocated meanwhile.
+fn measure<V>(func: impl FnOnce() -> V) -> (V, Duration, Option<usize>) {
+  let peak = track_peak();
+  let start = Instant::now();
+  let result = func();
+  let elapsed = start.elapsed();
+  (result, elapsed, peak())
+}
+
+/// Runs `func` and returns its value together with how long it took.
+#[track_caller]
+pub fn time<T, V>(func: T) -> TimedResult<V>
 where
   T: FnOnce() -> V,
 {
-  let start = Instant::now();
-  let result = func();
-  (result, start.elapsed())
+  let (value, duration, peak) = measure(func);
+  record_caller(duration, peak);
+  TimedResult { value, duration }
+}
+
+/// Like `time`, but records the timing under `label` even if the registry is not enabled.
+pub fn time_labeled<T, V>(label: impl Into<String>, func: T) -> TimedResult<V>
+where
+  T: FnOnce() -> V,
+{
+  let label = label.into();
+  let (value, duration, peak) = measure(func);
+  push_entry(&mut REGISTRY.lock().unwrap(), label, duration, peak);
+  TimedResult { value, duration }
 }
 
 pub trait Timing<T> {
@@ -16,26 +369,508 @@ pub trait Timing<T> {
 }
 
 impl<T> Timing<T> for Option<T> {
-  type Out = Option<(T, Duration)>;
+  type Out = Option<TimedResult<T>>;
 
   fn with_duration(self, duration: Duration) -> Self::Out {
-    self.map(|v| (v, duration))
+    self.map(|value| TimedResult { value, duration })
   }
 }
 
 impl<T, E> Timing<T> for Result<T, E> {
-  type Out = Result<(T, Duration), E>;
+  type Out = Result<TimedResult<T>, E>;
 
   fn with_duration(self, duration: Duration) -> Self::Out {
-    self.map(|v| (v, duration))
+    self.map(|value| TimedResult { value, duration })
   }
 }
 
+/// Like `time`, but for an `Option` or `Result`, with the timing inside it.
+#[track_caller]
 pub fn time_try<I, T>(func: fn() -> I) -> I::Out
 where
   I: Timing<T>,
 {
-  let start = Instant::now();
-  let result = func();
-  result.with_duration(start.elapsed())
+  let (result, elapsed, peak) = measure(func);
+  record_caller(elapsed, peak);
+  result.with_duration(elapsed)
+}
+
+/// A value and how long it took to compute. Displays as `value (in duration)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimedResult<V> {
+  pub value: V,
+  pub duration: Duration,
+}
+
+impl<V> TimedResult<V> {
+  /// Runs `func` with `time`.
+  #[track_caller]
+  pub fn measure(func: impl FnOnce() -> V) -> Self {
+    time(func)
+  }
+
+  pub fn into_inner(self) -> V {
+    self.value
+  }
+}
+
+impl<V, E> TimedResult<Result<V, E>> {
+  /// Moves the timing inside the result, so that errors can be handled with `?`.
+  pub fn transpose(self) -> Result<TimedResult<V>, E> {
+    let duration = self.duration;
+    self.value.map(|value| TimedResult { value, duration })
+  }
+}
+
+impl<V> From<(V, Duration)> for TimedResult<V> {
+  fn from((value, duration): (V, Duration)) -> Self {
+    Self { value, duration }
+  }
+}
+
+impl<V: Display> Display for TimedResult<V> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} (in {})", self.value, self.duration.display())
+  }
+}
+
+/// Timings of repeated runs of the same function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchResult<'a> {
+  pub name: &'a str,
+  pub iterations: usize,
+  pub min: Duration,
+  pub mean: Duration,
+  pub stddev: Duration,
+  /// The largest peak allocation of any run, with the `memory` feature.
+  pub peak_bytes: Option<usize>,
+}
+
+impl Display for BenchResult<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}: min {} mean {} ± {} ({} runs)",
+      self.name,
+      self.min.display_precise(2),
+      self.mean.display_precise(2),
+      self.stddev.display_precise(2),
+      self.iterations
+    )?;
+    if let Some(peak) = self.peak_bytes {
+      write!(f, ", peak {}", display_bytes(peak))?;
+    }
+    Ok(())
+  }
+}
+
+/// Runs `func` `iterations` times after a short warmup, then prints and returns the timings.
+pub fn bench<T, V>(name: &str, iterations: usize, func: T) -> BenchResult<'_>
+where
+  T: FnMut() -> V,
+{
+  let result = bench_quiet(name, iterations, func);
+  println!("{result}");
+  result
+}
+
+/// Like `bench`, but without printing the timings.
+pub fn bench_quiet<T, V>(name: &str, iterations: usize, mut func: T) -> BenchResult<'_>
+where
+  T: FnMut() -> V,
+{
+  assert!(iterations > 0, "iterations cannot be 0");
+  for _ in 0..iterations.div_ceil(10) {
+    black_box(func());
+  }
+
+  let mut peak_bytes = None;
+  let runs: Vec<f64> = (0..iterations)
+    .map(|_| {
+      let peak = track_peak();
+      let start = Instant::now();
+      black_box(func());
+      let elapsed = start.elapsed();
+      peak_bytes = peak_bytes.max(peak());
+      elapsed.as_secs_f64()
+    })
+    .collect();
+  let mean = runs.iter().sum::<f64>() / iterations as f64;
+  let variance = runs.iter().map(|run| (run - mean).powi(2)).sum::<f64>() / iterations as f64;
+  BenchResult {
+    name,
+    iterations,
+    min: Duration::from_secs_f64(runs.iter().copied().fold(f64::INFINITY, f64::min)),
+    mean: Duration::from_secs_f64(mean),
+    stddev: Duration::from_secs_f64(variance.sqrt()),
+    peak_bytes,
+  }
+}
+
+/// An in-place progress bar on stderr, see `progress`.
+pub struct Progress<'a> {
+  name: &'a str,
+  total: usize,
+  done: usize,
+  start: Instant,
+  last_draw: Option<Instant>,
+}
+
+/// Starts a progress bar for `total` steps, which is redrawn at most ten times a second.
+pub fn progress(name: &str, total: usize) -> Progress<'_> {
+  Progress {
+    name,
+    total,
+    done: 0,
+    start: Instant::now(),
+    last_draw: None,
+  }
+}
+
+impl Progress<'_> {
+  const WIDTH: usize = 30;
+
+  pub fn tick(&mut self) {
+    self.tick_by(1);
+  }
+
+  pub fn tick_by(&mut self, steps: usize) {
+    self.done = (self.done + steps).min(self.total);
+    let due = self
+      .last_draw
+      .is_none_or(|last| last.elapsed() >= Duration::from_millis(100));
+    if due || self.done == self.total {
+      self.last_draw = Some(Instant::now());
+      // clear the rest of the line, in case the previous draw was longer
+      eprint!("\r{}\x1b[K", self.render(self.start.elapsed()));
+    }
+  }
+
+  /// The bar after `elapsed`, with the remaining time estimated from the rate so far.
+  fn render(&self, elapsed: Duration) -> String {
+    let fraction = if self.total == 0 {
+      1.0
+    } else {
+      self.done as f64 / self.total as f64
+    };
+    let filled = (fraction * Self::WIDTH as f64) as usize;
+    let eta = if self.done == 0 {
+      "?".to_string()
+    } else {
+      elapsed
+        .mul_f64((self.total - self.done) as f64 / self.done as f64)
+        .display()
+        .to_string()
+    };
+    format!(
+      "{} [{}{}] {}/{} {} eta {eta}",
+      self.name,
+      "#".repeat(filled),
+      ".".repeat(Self::WIDTH - filled),
+      self.done,
+      self.total,
+      elapsed.display()
+    )
+  }
+}
+
+impl Drop for Progress<'_> {
+  fn drop(&mut self) {
+    if self.last_draw.is_some() {
+      eprintln!("\r{}\x1b[K", self.render(self.start.elapsed()));
+    }
+  }
+}
+
+struct ScopeRecord {
+  label: &'static str,
+  depth: usize,
+  duration: Option<Duration>,
+}
+
+thread_local! {
+  /// Scopes in the order they were started, and how many are currently open.
+  static SCOPES: RefCell<(Vec<ScopeRecord>, usize)> = const { RefCell::new((vec![], 0)) };
+}
+
+/// Measures the time until it is dropped, nested under any scopes that are still open.
+/// Scopes are recorded per thread, so a scope cannot be sent to another thread.
+pub struct TimingScope {
+  index: usize,
+  depth: usize,
+  start: Instant,
+  _not_send: PhantomData<*const ()>,
+}
+
+/// Starts a timing scope, e.g. `let _t = scope("parse");`.
+pub fn scope(label: &'static str) -> TimingScope {
+  let (index, depth) = SCOPES.with_borrow_mut(|(records, depth)| {
+    records.push(ScopeRecord {
+      label,
+      depth: *depth,
+      duration: None,
+    });
+    *depth += 1;
+    (records.len() - 1, *depth - 1)
+  });
+  TimingScope {
+    index,
+    depth,
+    start: Instant::now(),
+    _not_send: PhantomData,
+  }
+}
+
+impl Drop for TimingScope {
+  fn drop(&mut self) {
+    let elapsed = self.start.elapsed();
+    SCOPES.with_borrow_mut(|(records, depth)| {
+      records[self.index].duration = Some(elapsed);
+      // usually this is the innermost open scope, but if it is dropped before the scopes
+      // inside it, new scopes go back to its depth rather than below the ones still open
+      *depth = (*depth).min(self.depth);
+    });
+  }
+}
+
+/// An indented breakdown of all finished scopes, which are then forgotten.
+/// Does nothing while any scope is still open.
+pub fn scope_report() -> String {
+  SCOPES.with_borrow_mut(|(records, _)| {
+    if records.iter().any(|r| r.duration.is_none()) {
+      return String::new();
+    }
+    let width = records
+      .iter()
+      .map(|r| 2 * r.depth + r.label.len())
+      .max()
+      .unwrap_or(0);
+    let mut report = String::new();
+    for record in records.drain(..) {
+      let label = format!("{}{}", "  ".repeat(record.depth), record.label);
+      let duration = record.duration.expect("all scopes are closed");
+      report += &format!("{label:<width$}  {}\n", duration.display_precise(2));
+    }
+    report
+  })
+}
+
+pub fn print_scopes() {
+  print!("{}", scope_report());
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_display_bytes() {
+    assert_eq!(display_bytes(512), "512B");
+    assert_eq!(display_bytes(1536), "1.5KiB");
+    assert_eq!(display_bytes(3 << 30), "3.0GiB");
+  }
+
+  #[test]
+  fn test_bench() {
+    let mut calls = 0;
+    let result = bench("sum", 20, || {
+      calls += 1;
+      (0..1000u64).sum::<u64>()
+    });
+    // two warmup runs
+    assert_eq!(calls, 22);
+    assert_eq!(result.iterations, 20);
+    assert!(result.min <= result.mean);
+  }
+
+  #[test]
+  fn test_scopes() {
+    {
+      let _total = scope("total");
+      let _parse = scope("parse");
+      drop(_parse);
+      let _solve = scope("solve");
+      {
+        let _inner = scope("inner");
+        assert_eq!(scope_report(), "");
+      }
+    }
+    let report = scope_report();
+    let lines: Vec<_> = report.lines().collect();
+    assert_eq!(lines.len(), 4);
+    for (line, label) in lines
+      .iter()
+      .zip(["total ", "  parse ", "  solve ", "    inner "])
+    {
+      assert!(line.starts_with(label), "{line:?}");
+    }
+    assert_eq!(scope_report(), "");
+  }
+
+  #[test]
+  fn test_scopes_out_of_order() {
+    let outer = scope("outer");
+    let inner = scope("inner");
+    drop(outer);
+    let next = scope("next");
+    assert_eq!(scope_report(), "");
+    drop(inner);
+    drop(next);
+    let report = scope_report();
+    let lines: Vec<_> = report.lines().collect();
+    assert!(lines[0].starts_with("outer "));
+    assert!(lines[1].starts_with("  inner "));
+    assert!(lines[2].starts_with("next "));
+  }
+
+  #[test]
+  fn test_registry() {
+    enable_registry();
+    let slow = time(|| std::thread::sleep(Duration::from_millis(2))).duration;
+    record("fast", Duration::from_nanos(1));
+    let report = Report::collect().to_string();
+    let lines: Vec<_> = report.lines().collect();
+    let slow_line = lines
+      .iter()
+      .position(|l| l.starts_with("src/time.rs:"))
+      .unwrap();
+    let fast_line = lines.iter().position(|l| l.starts_with("fast")).unwrap();
+    assert!(slow_line < fast_line);
+    assert!(slow >= Duration::from_millis(2));
+    assert!(lines.last().unwrap().starts_with("total"));
+  }
+
+  #[test]
+  fn test_timed() {
+    let sum = crate::timed!((1..=10).sum::<u32>());
+    let product = crate::timed!("product", (1..=5).product::<u32>());
+    assert_eq!((sum, product), (55, 120));
+    let report = Report::collect().to_string();
+    assert!(
+      report
+        .lines()
+        .any(|l| l.starts_with("(1..=10).sum::<u32>() "))
+    );
+    assert!(report.lines().any(|l| l.starts_with("product ")));
+  }
+
+  #[test]
+  fn test_highlight_slow() {
+    set_slow_thresholds(Duration::from_millis(100), Duration::from_secs(1));
+    assert_eq!(highlight_slow(Duration::from_millis(5)), "  5.00ms");
+    assert_eq!(
+      highlight_slow(Duration::from_millis(500)),
+      "500.00ms".yellow().to_string()
+    );
+    assert_eq!(
+      highlight_slow(Duration::from_secs(2)),
+      "  2.00s ".red().to_string()
+    );
+  }
+
+  #[test]
+  fn test_timed_result() {
+    let timed = TimedResult::measure(|| 6 * 7);
+    assert_eq!(timed.value, 42);
+    let shown = TimedResult {
+      value: "answer",
+      duration: Duration::from_millis(20),
+    };
+    assert_eq!(shown.to_string(), "answer (in 20ms)");
+
+    let parsed = TimedResult::measure(|| "12".parse::<u8>())
+      .transpose()
+      .unwrap();
+    assert_eq!(parsed.into_inner(), 12);
+    assert!(
+      TimedResult::measure(|| "x".parse::<u8>())
+        .transpose()
+        .is_err()
+    );
+
+    let timed = time_try(|| "7".parse::<u8>()).unwrap();
+    assert_eq!(timed.value, 7);
+    assert_eq!(time_try(|| None::<u8>), None);
+  }
+
+  #[test]
+  fn test_progress_render() {
+    let mut bar = progress("search", 4);
+    assert_eq!(
+      bar.render(Duration::ZERO),
+      format!("search [{}] 0/4 0ns eta ?", ".".repeat(30))
+    );
+    bar.done = 1;
+    assert_eq!(
+      bar.render(Duration::from_millis(10)),
+      format!(
+        "search [{}{}] 1/4 10ms eta 30ms",
+        "#".repeat(7),
+        ".".repeat(23)
+      )
+    );
+    bar.tick_by(10);
+    assert_eq!(bar.done, 4);
+  }
+
+  #[test]
+  fn test_write_csv() {
+    let report = Report {
+      timestamp: 1700000000,
+      entries: vec![ReportEntry {
+        label: "say \"hi\"".into(),
+        duration: Duration::from_micros(3),
+        peak_bytes: None,
+      }],
+    };
+    let path = std::env::temp_dir().join("aoc25_test_report.csv");
+    report.write_csv(&path).unwrap();
+    let csv = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+      csv,
+      "timestamp,label,duration_ns,peak_bytes\n1700000000,\"say \"\"hi\"\"\",3000,\n"
+    );
+  }
+
+  #[test]
+  fn test_compare() {
+    let entry = |label: &str, ms, peak| ReportEntry {
+      label: label.into(),
+      duration: Duration::from_millis(ms),
+      peak_bytes: peak,
+    };
+    let previous = Report {
+      timestamp: 1700000000,
+      entries: vec![
+        entry("part 1", 10, Some(64)),
+        entry("part\",\" 2", 48, None),
+      ],
+    };
+    let path = std::env::temp_dir().join("aoc25_test_compare.csv");
+    previous.write_csv(&path).unwrap();
+    let read = Report::read_csv(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read, previous);
+
+    let current = Report {
+      timestamp: 1700000100,
+      entries: vec![entry("part\",\" 2", 13, None), entry("parse", 1, None)],
+    };
+    let deltas = current.compare(&previous);
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(deltas[0].percent().map(f64::round), Some(-73.0));
+    assert_eq!(
+      deltas[0].to_string(),
+      format!("part\",\" 2: 48.00ms → 13.00ms, {}", "-73%".green())
+    );
+
+    let instant = Delta {
+      label: "parse".into(),
+      before: Duration::ZERO,
+      after: Duration::ZERO,
+    };
+    assert_eq!(instant.percent(), None);
+    assert!(instant.to_string().ends_with("n/a"));
+  }
 }
@@ -0,0 +1,218 @@
+use std::{
+  fs::{self, File},
+  path::PathBuf,
+  str::FromStr,
+  sync::Mutex,
+};
+
+use image::{
+  Delay, DynamicImage, Rgb, RgbImage,
+  codecs::gif::{GifEncoder, Repeat},
+};
+
+use crate::grid::Grid;
+
+/// The size in pixels of a cell when frames are written as images.
+pub const CELL: u32 = 4;
+
+/// How long each frame is shown in a GIF.
+const FRAME_DELAY_MS: u32 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+  /// One file per frame, `.txt` for text frames and `.png` for image frames.
+  Text,
+  /// One image per frame.
+  Png,
+  /// All frames in one animation.
+  Gif,
+}
+
+impl FromStr for Format {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(match s {
+      "text" => Self::Text,
+      "png" => Self::Png,
+      "gif" => Self::Gif,
+      _ => anyhow::bail!("unknown format {s:?}, expected text, png or gif"),
+    })
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Frame {
+  Text(String),
+  Image(RgbImage),
+}
+
+impl Frame {
+  /// The frame as an image, with text drawn as a cell per character.
+  pub fn to_image(&self) -> RgbImage {
+    match self {
+      Frame::Image(image) => image.clone(),
+      Frame::Text(text) => render_text(text),
+    }
+  }
+}
+
+/// Draws a cell per character, dark for `.` and spaces and light for anything else.
+pub fn render_text(text: &str) -> RgbImage {
+  let lines: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+  let width = lines.iter().map(Vec::len).max().unwrap_or(0).max(1) as u32;
+  let height = lines.len().max(1) as u32;
+  RgbImage::from_fn(width * CELL, height * CELL, |x, y| {
+    let c = lines
+      .get((y / CELL) as usize)
+      .and_then(|line| line.get((x / CELL) as usize));
+    match c {
+      None | Some('.' | ' ') => Rgb([16, 16, 24]),
+      Some(_) => Rgb([230, 230, 220]),
+    }
+  })
+}
+
+struct Recorder {
+  dir: PathBuf,
+  format: Format,
+  frames: Vec<Frame>,
+}
+
+/// The frames of the running solution, if frames are being recorded.
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Starts recording frames, to be written to `dir` by `finish`.
+pub fn enable(dir: impl Into<PathBuf>, format: Format) {
+  *RECORDER.lock().unwrap() = Some(Recorder {
+    dir: dir.into(),
+    format,
+    frames: vec![],
+  });
+}
+
+pub fn is_enabled() -> bool {
+  RECORDER.lock().unwrap().is_some()
+}
+
+/// Records a frame. `frame` is only called while recording, so it can be expensive.
+/// It is called without holding the recorder, so it may record frames of its own.
+pub fn frame(frame: impl FnOnce() -> Frame) {
+  if !is_enabled() {
+    return;
+  }
+  let frame = frame();
+  if let Some(recorder) = RECORDER.lock().unwrap().as_mut() {
+    recorder.frames.push(frame);
+  }
+}
+
+pub fn text(text: impl FnOnce() -> String) {
+  frame(|| Frame::Text(text()));
+}
+
+/// Records the grid as text.
+pub fn grid<T: std::fmt::Display>(grid: &Grid<T>) {
+  text(|| grid.to_string());
+}
+
+/// Records the grid as an image, with a cell of `CELL` pixels per item.
+pub fn grid_colored<T>(grid: &Grid<T>, color: impl Fn(&T) -> Rgb<u8>) {
+  frame(|| {
+    let (height, width) = grid.dimensions();
+    Frame::Image(RgbImage::from_fn(
+      width as u32 * CELL,
+      height as u32 * CELL,
+      |x, y| color(&grid[((x / CELL) as usize, (y / CELL) as usize)]),
+    ))
+  });
+}
+
+/// Stops recording and writes the frames, returning how many there were.
+/// Does nothing if frames are not being recorded.
+pub fn finish() -> anyhow::Result<usize> {
+  let Some(recorder) = RECORDER.lock().unwrap().take() else {
+    return Ok(0);
+  };
+  if recorder.frames.is_empty() {
+    return Ok(0);
+  }
+  fs::create_dir_all(&recorder.dir)?;
+  let path = |i: usize, ext: &str| recorder.dir.join(format!("frame_{i:04}.{ext}"));
+  match recorder.format {
+    Format::Text => {
+      for (i, frame) in recorder.frames.iter().enumerate() {
+        match frame {
+          Frame::Text(text) => fs::write(path(i, "txt"), text)?,
+          Frame::Image(image) => image.save(path(i, "png"))?,
+        }
+      }
+    }
+    Format::Png => {
+      for (i, frame) in recorder.frames.iter().enumerate() {
+        frame.to_image().save(path(i, "png"))?;
+      }
+    }
+    Format::Gif => {
+      let mut encoder = GifEncoder::new(File::create(recorder.dir.join("animation.gif"))?);
+      encoder.set_repeat(Repeat::Infinite)?;
+      let delay = Delay::from_numer_denom_ms(FRAME_DELAY_MS, 1);
+      encoder.encode_frames(recorder.frames.iter().map(|frame| {
+        let rgba = DynamicImage::ImageRgb8(frame.to_image()).to_rgba8();
+        image::Frame::from_parts(rgba, 0, 0, delay)
+      }))?;
+    }
+  }
+  Ok(recorder.frames.len())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_render_text() {
+    let image = render_text("#.\n.##\n");
+    assert_eq!(image.dimensions(), (3 * CELL, 2 * CELL));
+    assert_ne!(image.get_pixel(0, 0), image.get_pixel(CELL, 0));
+    // short lines are padded with background
+    assert_eq!(image.get_pixel(2 * CELL, 0), image.get_pixel(CELL, 0));
+  }
+
+  #[test]
+  fn test_record() {
+    let dir = std::env::temp_dir().join("aoc25_test_viz");
+    let _ = fs::remove_dir_all(&dir);
+
+    text(|| unreachable!("not recording"));
+    assert_eq!(finish().unwrap(), 0);
+
+    enable(&dir, Format::Text);
+    assert!(is_enabled());
+    let grid: Grid<char> = "#..\n.#.".parse().unwrap();
+    self::grid(&grid);
+    grid_colored(&grid, |c| Rgb([*c as u8, 0, 0]));
+    assert_eq!(finish().unwrap(), 2);
+    assert!(!is_enabled());
+    assert_eq!(
+      fs::read_to_string(dir.join("frame_0000.txt")).unwrap(),
+      "#..\n.#.\n"
+    );
+    let image = image::open(dir.join("frame_0001.png")).unwrap();
+    assert_eq!((image.width(), image.height()), (3 * CELL, 2 * CELL));
+
+    enable(&dir, Format::Gif);
+    text(|| "#".into());
+    text(|| ".".into());
+    assert_eq!(finish().unwrap(), 2);
+    assert!(dir.join("animation.gif").exists());
+
+    enable(&dir, Format::Text);
+    text(|| {
+      text(|| "inner".into());
+      "outer".into()
+    });
+    assert_eq!(finish().unwrap(), 2);
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}